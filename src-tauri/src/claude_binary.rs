@@ -477,18 +477,39 @@ struct PrioritizedInstallation {
 /// Current app version - used to detect upgrades and clear stale caches
 const APP_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+/// 已考察过的候选路径（去重 + 按顺序记录来源，供诊断使用）
+#[derive(Default)]
+struct CandidateTrace {
+    keys: HashSet<String>,
+    considered: Vec<(String, String)>,
+}
+
+/// 单个候选路径的诊断信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinaryCandidate {
+    pub path: String,
+    /// 发现来源（env:CLAUDE_PATH、PATH、registry、common-path、user-config 等）
+    pub source: String,
+    pub exists: bool,
+    pub version: Option<String>,
+    /// 是否为自动检测会选中的路径
+    pub selected: bool,
+}
+
 /// 将路径加入候选列表并去重/校验
 fn push_candidate(
     list: &mut Vec<PrioritizedInstallation>,
-    seen: &mut std::collections::HashSet<String>,
+    seen: &mut CandidateTrace,
     path: String,
     source: &str,
     priority: u8,
 ) {
     let normalized = path.to_lowercase();
-    if !seen.insert(normalized.clone()) {
+    if !seen.keys.insert(normalized) {
         return;
     }
+    seen.considered.push((path.clone(), source.to_string()));
 
     let path_obj = PathBuf::from(&path);
     let looks_like_path = path.contains('\\') || path.contains('/');
@@ -521,30 +542,41 @@ fn collect_runtime_candidates(
     env_var: &str,
     env: &RuntimeEnvironment,
     user_section: Option<BinarySearchSection>,
+) -> Vec<PrioritizedInstallation> {
+    let mut seen = CandidateTrace::default();
+    collect_traced_candidates(tool, env_var, env, user_section, &mut seen)
+}
+
+/// 同 collect_runtime_candidates，并在 `seen` 中记录所有考察过的路径
+fn collect_traced_candidates(
+    tool: &str,
+    env_var: &str,
+    env: &RuntimeEnvironment,
+    user_section: Option<BinarySearchSection>,
+    seen: &mut CandidateTrace,
 ) -> Vec<PrioritizedInstallation> {
     let mut candidates: Vec<PrioritizedInstallation> = Vec::new();
-    let mut seen: HashSet<String> = HashSet::new();
     let aliases = get_tool_aliases(tool, env);
 
     // 1. 环境变量覆盖
     if let Ok(val) = std::env::var(env_var) {
         if !val.trim().is_empty() {
             info!("Using {} from env var {}", tool, env_var);
-            push_candidate(&mut candidates, &mut seen, val, &format!("env:{}", env_var), 0);
+            push_candidate(&mut candidates, seen, val, &format!("env:{}", env_var), 0);
         }
     }
 
     // 2. PATH 中的命令
     for alias in &aliases {
         if let Some(resolved) = resolve_command_in_path(alias, env) {
-            push_candidate(&mut candidates, &mut seen, resolved, "PATH", 1);
+            push_candidate(&mut candidates, seen, resolved, "PATH", 1);
         }
     }
 
     // 3. Windows 注册表（仅在 Windows 下有效）
     for alias in &aliases {
         for reg_path in query_registry_paths(alias) {
-            push_candidate(&mut candidates, &mut seen, reg_path, "registry", 2);
+            push_candidate(&mut candidates, seen, reg_path, "registry", 2);
         }
     }
 
@@ -594,7 +626,7 @@ fn collect_runtime_candidates(
             for root in search_roots {
                 for alias in &aliases {
                     let candidate = format!(r"{}\{}", root, alias);
-                    push_candidate(&mut candidates, &mut seen, candidate, "common-path", 3);
+                    push_candidate(&mut candidates, seen, candidate, "common-path", 3);
                 }
             }
         }
@@ -635,7 +667,7 @@ fn collect_runtime_candidates(
                     } else {
                         format!("{}/{}", root, alias)
                     };
-                    push_candidate(&mut candidates, &mut seen, candidate, "common-path", 3);
+                    push_candidate(&mut candidates, seen, candidate, "common-path", 3);
                 }
             }
         }
@@ -666,7 +698,7 @@ fn collect_runtime_candidates(
             for root in search_roots {
                 for alias in &aliases {
                     let candidate = format!("{}/{}", root, alias);
-                    push_candidate(&mut candidates, &mut seen, candidate, "common-path", 3);
+                    push_candidate(&mut candidates, seen, candidate, "common-path", 3);
                 }
             }
         }
@@ -675,10 +707,10 @@ fn collect_runtime_candidates(
     // 5. 用户配置文件中的额外搜索路径（优先级最低但可覆盖奇异环境）
     if let Some(section) = user_section {
         if let Some(custom) = section.override_path {
-            push_candidate(&mut candidates, &mut seen, custom, "user-config", 4);
+            push_candidate(&mut candidates, seen, custom, "user-config", 4);
         }
         for path in section.search_paths {
-            push_candidate(&mut candidates, &mut seen, path, "user-config", 4);
+            push_candidate(&mut candidates, seen, path, "user-config", 4);
         }
     }
 
@@ -688,7 +720,7 @@ fn collect_runtime_candidates(
         for mount in &windows_mounts {
             for alias in &aliases {
                 let candidate = format!("{}/Program Files/{}/{}", mount, tool, alias);
-                push_candidate(&mut candidates, &mut seen, candidate, "wsl-host", 3);
+                push_candidate(&mut candidates, seen, candidate, "wsl-host", 3);
             }
         }
     }
@@ -824,7 +856,18 @@ pub fn find_claude_binary(app_handle: &tauri::AppHandle) -> Result<String, Strin
     #[cfg(target_os = "windows")]
     info!("Platform: Windows");
 
-    // First check if we have a stored path in the database
+    // 用户通过 set_custom_claude_path 指定的路径最优先，不受升级时清理缓存的影响
+    if let Some(custom) = pick_section(&load_binary_search_config(), "claude")
+        .and_then(|section| section.override_path)
+    {
+        if PathBuf::from(&custom).is_file() && test_claude_binary(&custom) {
+            info!("✅ Using custom Claude CLI path from binaries.json: {}", custom);
+            return Ok(custom);
+        }
+        warn!("❌ Custom Claude CLI path is not usable, falling back to detection: {}", custom);
+    }
+
+    // Then check if we have a stored path in the database
     if let Ok(app_data_dir) = app_handle.path().app_data_dir() {
         let db_path = app_data_dir.join("agents.db");
         if db_path.exists() {
@@ -951,6 +994,44 @@ pub fn find_claude_binary(app_handle: &tauri::AppHandle) -> Result<String, Strin
     }
 }
 
+/// 列出 Claude 检测逻辑考察过的全部候选路径（含不存在的），用于诊断 "claude not found"
+pub fn collect_claude_binary_candidates() -> Vec<BinaryCandidate> {
+    let runtime_env = detect_runtime_environment();
+    let user_cfg = load_binary_search_config();
+    let user_section = pick_section(&user_cfg, "claude");
+
+    let mut seen = CandidateTrace::default();
+    let mut prioritized =
+        collect_traced_candidates("claude", "CLAUDE_PATH", &runtime_env, user_section, &mut seen);
+    for inst in discover_system_installations() {
+        if seen.keys.insert(inst.path.to_lowercase()) {
+            seen.considered.push((inst.path.clone(), inst.source.clone()));
+        }
+        prioritized.push(PrioritizedInstallation {
+            priority: 5,
+            installation: inst,
+        });
+    }
+
+    let found: Vec<ClaudeInstallation> =
+        prioritized.iter().map(|p| p.installation.clone()).collect();
+    let selected = select_best_with_priority(prioritized).map(|best| best.path);
+
+    seen.considered
+        .into_iter()
+        .map(|(path, source)| {
+            let installation = found.iter().find(|inst| inst.path == path);
+            BinaryCandidate {
+                exists: installation.is_some() || PathBuf::from(&path).exists(),
+                version: installation.and_then(|inst| inst.version.clone()),
+                selected: selected.as_deref() == Some(path.as_str()),
+                path,
+                source,
+            }
+        })
+        .collect()
+}
+
 /// Store Claude CLI path in database for future use
 fn store_claude_path(app_handle: &tauri::AppHandle, path: &str) -> Result<(), String> {
     if let Ok(app_data_dir) = app_handle.path().app_data_dir() {
@@ -1928,7 +2009,7 @@ fn select_best_installation(installations: Vec<ClaudeInstallation>) -> Option<Cl
 /// Windows-specific: Resolve .cmd wrapper to actual Node.js script path
 /// Returns (node_path, script_path) if successful
 #[cfg(target_os = "windows")]
-fn resolve_cmd_wrapper(cmd_path: &str) -> Option<(String, String)> {
+pub(crate) fn resolve_cmd_wrapper(cmd_path: &str) -> Option<(String, String)> {
     use std::fs;
 
     debug!("Attempting to resolve .cmd wrapper: {}", cmd_path);
@@ -1971,12 +2052,12 @@ fn resolve_cmd_wrapper(cmd_path: &str) -> Option<(String, String)> {
 }
 
 #[cfg(not(target_os = "windows"))]
-fn resolve_cmd_wrapper(_cmd_path: &str) -> Option<(String, String)> {
+pub(crate) fn resolve_cmd_wrapper(_cmd_path: &str) -> Option<(String, String)> {
     None
 }
 
 /// Compare two version strings
-fn compare_versions(a: &str, b: &str) -> Ordering {
+pub(crate) fn compare_versions(a: &str, b: &str) -> Ordering {
     // Simple semantic version comparison
     let a_parts: Vec<u32> = a
         .split('.')
@@ -2114,5 +2195,10 @@ pub fn create_command_with_env(program: &str) -> Command {
         }
     }
 
+    // 离线模式：禁止遥测、错误上报和自动更新
+    for (key, value) in crate::commands::network_mode::offline_env_vars() {
+        cmd.env(key, value);
+    }
+
     cmd
 }
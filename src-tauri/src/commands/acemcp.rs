@@ -14,8 +14,8 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::process::Stdio;
-use std::path::PathBuf;
-use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::collections::{HashMap, HashSet};
 use tauri::AppHandle;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::Command;
@@ -290,6 +290,48 @@ struct AcemcpClient {
     request_id: u64,
 }
 
+/// 解析要启动的程序及其前置参数
+/// Windows 上 npx、node 工具等裸命令在 PATH 中实际是 .cmd 包装脚本，Command::new 无法直接启动：
+/// 与 create_command_with_env 一样优先改为 node 直接运行脚本，解析失败时经 cmd /C 执行
+#[cfg(target_os = "windows")]
+fn resolve_program(program: &str) -> (String, Vec<String>) {
+    let is_shim = |path: &Path| {
+        path.extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("cmd") || ext.eq_ignore_ascii_case("bat"))
+    };
+
+    let resolved = if Path::new(program).extension().is_some() {
+        Some(PathBuf::from(program))
+    } else {
+        std::env::var_os("PATH").and_then(|path_var| {
+            std::env::split_paths(&path_var).find_map(|dir| {
+                [".exe", ".cmd", ".bat"]
+                    .iter()
+                    .map(|ext| dir.join(format!("{}{}", program, ext)))
+                    .find(|candidate| candidate.is_file())
+            })
+        })
+    };
+
+    match resolved {
+        Some(path) if is_shim(&path) => {
+            let path = path.to_string_lossy().to_string();
+            match crate::claude_binary::resolve_cmd_wrapper(&path) {
+                Some((node, script)) => (node, vec![script]),
+                None => ("cmd".to_string(), vec!["/C".to_string(), path]),
+            }
+        }
+        Some(path) => (path.to_string_lossy().to_string(), Vec::new()),
+        None => (program.to_string(), Vec::new()),
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn resolve_program(program: &str) -> (String, Vec<String>) {
+    (program.to_string(), Vec::new())
+}
+
 impl AcemcpClient {
     /// 获取或提取 sidecar 可执行文件路径
     fn get_or_extract_sidecar() -> Result<PathBuf> {
@@ -383,11 +425,29 @@ impl AcemcpClient {
         }
 
         // 使用 tokio Command 启动 sidecar（保持 stdio 通信）
-        let mut cmd = Command::new("node");
-        cmd.arg(&sidecar_path)
+        let client = Self::spawn(
+            "node",
+            &[sidecar_path.to_string_lossy().to_string()],
+            &HashMap::new(),
+        )
+        .map_err(|e| anyhow::anyhow!("Failed to spawn sidecar: {}. Path: {:?}", e, sidecar_path))?;
+
+        info!("Acemcp sidecar started successfully");
+
+        Ok(client)
+    }
+
+    /// 以 stdio 方式启动任意 MCP server 进程
+    fn spawn(program: &str, args: &[String], env: &HashMap<String, String>) -> Result<Self> {
+        let (program, shim_args) = resolve_program(program);
+        let mut cmd = Command::new(&program);
+        cmd.args(&shim_args)
+            .args(args)
+            .envs(env)
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
-            .stderr(Stdio::null());
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
 
         // Windows: 隐藏控制台窗口
         #[cfg(target_os = "windows")]
@@ -396,11 +456,7 @@ impl AcemcpClient {
             cmd.creation_flags(CREATE_NO_WINDOW);
         }
 
-        let child = cmd.spawn()
-            .map_err(|e| anyhow::anyhow!("Failed to spawn sidecar: {}. Path: {:?}", e, sidecar_path))?;
-
-        info!("Acemcp sidecar started successfully");
-
+        let child = cmd.spawn()?;
         Ok(Self {
             child,
             request_id: 0
@@ -530,12 +586,46 @@ impl AcemcpClient {
         Err(anyhow::anyhow!("Invalid search_context response format"))
     }
 
+    /// 带重试的 search_context：仅对瞬时错误（网络抖动/超时）做指数退避重试，
+    /// 认证和上下文长度错误立即返回
+    async fn search_context_with_retry(
+        &mut self,
+        project_path: &str,
+        query: &str,
+        max_attempts: u32,
+    ) -> Result<String> {
+        let max_attempts = max_attempts.max(1);
+        let mut attempt = 1;
+
+        loop {
+            debug!("search_context attempt {}/{}", attempt, max_attempts);
+            match self.search_context(project_path, query).await {
+                Ok(result) => return Ok(result),
+                Err(e) => {
+                    let message = e.to_string();
+                    if attempt >= max_attempts || !is_transient_error(&message) {
+                        return Err(e);
+                    }
+
+                    let delay_ms = RETRY_BASE_DELAY_MS * 2u64.pow(attempt - 1);
+                    warn!(
+                        "search_context attempt {}/{} failed with transient error: {}. Retrying in {}ms",
+                        attempt, max_attempts, message, delay_ms
+                    );
+                    tokio::time::sleep(tokio::time::Duration::from_millis(delay_ms)).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+
     /// 多轮搜索：使用不同的查询策略获取更全面的上下文
     async fn multi_round_search(
         &mut self,
         project_path: &str,
         queries: &[String],
         max_total_length: usize,
+        max_attempts: u32,
     ) -> Result<String> {
         info!("Starting multi-round search with {} queries", queries.len());
 
@@ -549,7 +639,7 @@ impl AcemcpClient {
 
             info!("Round {}: searching with query: {}", round + 1, query);
 
-            match self.search_context(project_path, query).await {
+            match self.search_context_with_retry(project_path, query, max_attempts).await {
                 Ok(result) => {
                     // 简单去重：按代码片段切分
                     for snippet in result.split("\n\nPath:") {
@@ -609,6 +699,75 @@ impl AcemcpClient {
     }
 }
 
+/// MCP server 存活检查的超时时间（秒）
+const MCP_PROBE_TIMEOUT_SECS: u64 = 10;
+
+/// 检查 MCP server 是否可用：启动进程、完成 initialize 握手后立即结束进程
+pub async fn probe_mcp_server(
+    command: &str,
+    args: &[String],
+    env: &HashMap<String, String>,
+) -> Result<(), String> {
+    let mut client = AcemcpClient::spawn(command, args, env)
+        .map_err(|e| format!("Failed to start {}: {}", command, e))?;
+
+    let handshake = tokio::time::timeout(
+        tokio::time::Duration::from_secs(MCP_PROBE_TIMEOUT_SECS),
+        client.initialize(),
+    )
+    .await;
+    let _ = client.shutdown().await;
+
+    match handshake {
+        Ok(Ok(())) => Ok(()),
+        Ok(Err(e)) => Err(format!("Initialize handshake failed: {}", e)),
+        Err(_) => Err(format!("No initialize response within {}s", MCP_PROBE_TIMEOUT_SECS)),
+    }
+}
+
+// ============================================================================
+// 重试策略
+// ============================================================================
+
+/// 默认最大尝试次数（含首次）
+const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+
+/// 指数退避的基础延迟（毫秒）：500ms, 1s, 2s ...
+const RETRY_BASE_DELAY_MS: u64 = 500;
+
+/// 判断错误是否为瞬时错误（值得重试）
+/// 认证失败和上下文过长属于永久错误，重试不会成功
+fn is_transient_error(message: &str) -> bool {
+    classify_error(message) == "network"
+}
+
+/// 错误分类："auth"、"context-length"、"network"（瞬时错误）或 "other"
+/// 同时用于增强重试策略和运行失败原因展示
+pub fn classify_error(message: &str) -> &'static str {
+    const AUTH_MARKERS: &[&str] = &[
+        "401", "403", "unauthorized", "forbidden", "invalid token", "invalid api key",
+    ];
+    const CONTEXT_LENGTH_MARKERS: &[&str] = &[
+        "context length", "context_length", "maximum context", "too long",
+    ];
+    const NETWORK_MARKERS: &[&str] = &[
+        "timeout", "timed out", "econnreset", "econnrefused", "etimedout", "enotfound",
+        "eai_again", "socket hang up", "connection reset", "connection refused",
+        "network", "502", "503", "504", "429", "rate limit", "temporarily unavailable",
+    ];
+
+    let lower = message.to_lowercase();
+    if AUTH_MARKERS.iter().any(|m| lower.contains(m)) {
+        "auth"
+    } else if CONTEXT_LENGTH_MARKERS.iter().any(|m| lower.contains(m)) {
+        "context-length"
+    } else if NETWORK_MARKERS.iter().any(|m| lower.contains(m)) {
+        "network"
+    } else {
+        "other"
+    }
+}
+
 // ============================================================================
 // 关键词提取
 // ============================================================================
@@ -911,6 +1070,78 @@ fn truncate_utf8_safe(s: &str, max_bytes: usize) -> &str {
     }
 }
 
+/// 展开提示词中的 @文件 引用，返回上下文片段（total_limit 按文件数平均分配）
+/// 只读取项目根目录内的文件；未找到的引用以提示行列出
+fn expand_file_references(prompt: &str, project_path: &str, total_limit: usize) -> Option<String> {
+    lazy_static::lazy_static! {
+        static ref FILE_REF_RE: Regex = Regex::new(r"(?:^|\s)@([\w./\\-]+)").unwrap();
+    }
+
+    let mut refs: Vec<String> = Vec::new();
+    for caps in FILE_REF_RE.captures_iter(prompt) {
+        // 去掉句末标点（如 "@src/main.rs."）
+        let reference = caps[1].trim_end_matches(|c| c == '.' || c == ',').to_string();
+        if !reference.is_empty() && !refs.contains(&reference) {
+            refs.push(reference);
+        }
+    }
+    if refs.is_empty() {
+        return None;
+    }
+
+    let root = std::path::Path::new(project_path)
+        .canonicalize()
+        .unwrap_or_else(|_| PathBuf::from(project_path));
+    let per_file_limit = (total_limit / refs.len()).max(200);
+
+    let mut sections = Vec::new();
+    let mut missing = Vec::new();
+    for reference in &refs {
+        let resolved = root.join(reference).canonicalize();
+        let content = match resolved {
+            Ok(path) if path.starts_with(&root) && path.is_file() => std::fs::read_to_string(&path).ok(),
+            _ => None,
+        };
+        match content {
+            Some(content) => {
+                let body = if content.len() > per_file_limit {
+                    format!("{}\n... [文件已截断]", truncate_utf8_safe(&content, per_file_limit))
+                } else {
+                    content
+                };
+                sections.push(format!("Path: {}\n```\n{}\n```", reference, body.trim_end()));
+            }
+            None => {
+                warn!("Referenced file not found in project: {}", reference);
+                missing.push(reference.clone());
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        sections.push(format!("[未找到引用的文件: {}]", missing.join(", ")));
+    }
+    info!("Expanded {} of {} @file references", refs.len() - missing.len(), refs.len());
+    Some(sections.join("\n\n"))
+}
+
+/// acemcp 未能提供上下文时的结果：已展开的 @文件 引用仍会附加到提示词
+fn file_refs_only_result(prompt: String, file_context: Option<&str>, error: String) -> EnhancementResult {
+    let (enhanced_prompt, context_count) = match file_context {
+        Some(context) => (
+            format!("{}\n\n--- 项目上下文 (来自 @文件 引用) ---\n{}", prompt.trim(), context),
+            context.matches("Path:").count(),
+        ),
+        None => (prompt.clone(), 0),
+    };
+    EnhancementResult {
+        original_prompt: prompt,
+        enhanced_prompt,
+        context_count,
+        acemcp_used: false,
+        error: Some(error),
+    }
+}
 
 #[tauri::command]
 pub async fn enhance_prompt_with_context(
@@ -921,7 +1152,17 @@ pub async fn enhance_prompt_with_context(
     project_id: Option<String>,      // 新增：项目 ID
     max_context_length: Option<usize>,
     enable_multi_round: Option<bool>, // 新增：是否启用多轮搜索
+    max_attempts: Option<u32>,        // 新增：瞬时错误的最大尝试次数（默认 3）
+    expand_file_refs: Option<bool>,   // 新增：是否内联 @文件 引用的内容
+    normalize_line_endings: Option<bool>, // 新增：是否将 CRLF 换行统一为 LF（默认 true）
 ) -> Result<EnhancementResult, String> {
+    // Windows 下的 CRLF 会混入提示词，统一为 LF 后再处理
+    let prompt = if normalize_line_endings.unwrap_or(true) {
+        crate::commands::codex::session::normalize_line_endings(&prompt)
+    } else {
+        prompt
+    };
+
     info!(
         "enhance_prompt_with_context: prompt_len={}, project={}, has_history={}, multi_round={}",
         prompt.len(),
@@ -935,6 +1176,7 @@ pub async fn enhance_prompt_with_context(
     const MAX_TOTAL_OUTPUT_LENGTH: usize = 150_000; // 最大输出长度
 
     let max_length = max_context_length.unwrap_or(3000);
+    let max_attempts = max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
 
     // ⚡ 检查提示词长度
     if prompt.len() > MAX_PROMPT_LENGTH {
@@ -963,6 +1205,18 @@ pub async fn enhance_prompt_with_context(
         });
     }
 
+    // 📎 先展开 @文件 引用：不依赖 acemcp，之后任何失败返回都会带上它们
+    let file_context = if expand_file_refs.unwrap_or(false) {
+        expand_file_references(&prompt, &project_path, max_length)
+    } else {
+        None
+    };
+
+    // 离线模式下不调用 acemcp，只保留 @文件 引用
+    if let Err(e) = crate::commands::network_mode::ensure_online("Prompt enhancement") {
+        return Ok(file_refs_only_result(prompt, file_context.as_deref(), e));
+    }
+
     // 🎯 智能查询生成：根据是否有历史上下文选择策略
     let (search_queries, has_history) = if let (Some(sid), Some(pid)) = (&session_id, &project_id) {
         // 有历史：使用智能查询生成
@@ -1018,13 +1272,11 @@ pub async fn enhance_prompt_with_context(
 
     if valid_queries.is_empty() {
         warn!("No valid search queries generated");
-        return Ok(EnhancementResult {
-            original_prompt: prompt.clone(),
-            enhanced_prompt: prompt,
-            context_count: 0,
-            acemcp_used: false,
-            error: Some("No keywords could be extracted from prompt".to_string()),
-        });
+        return Ok(file_refs_only_result(
+            prompt,
+            file_context.as_deref(),
+            "No keywords could be extracted from prompt".to_string(),
+        ));
     }
 
     info!("📋 Generated {} search queries (history_aware={})", valid_queries.len(), has_history);
@@ -1037,13 +1289,11 @@ pub async fn enhance_prompt_with_context(
         Ok(c) => c,
         Err(e) => {
             error!("Failed to start acemcp: {}", e);
-            return Ok(EnhancementResult {
-                original_prompt: prompt.clone(),
-                enhanced_prompt: prompt,
-                context_count: 0,
-                acemcp_used: false,
-                error: Some(format!("Failed to start acemcp: {}", e)),
-            });
+            return Ok(file_refs_only_result(
+                prompt,
+                file_context.as_deref(),
+                format!("Failed to start acemcp: {}", e),
+            ));
         }
     };
 
@@ -1051,53 +1301,54 @@ pub async fn enhance_prompt_with_context(
     if let Err(e) = client.initialize().await {
         error!("Failed to initialize MCP session: {}", e);
         let _ = client.shutdown().await;
-        return Ok(EnhancementResult {
-            original_prompt: prompt.clone(),
-            enhanced_prompt: prompt,
-            context_count: 0,
-            acemcp_used: false,
-            error: Some(format!("Failed to initialize MCP: {}", e)),
-        });
+        return Ok(file_refs_only_result(
+            prompt,
+            file_context.as_deref(),
+            format!("Failed to initialize MCP: {}", e),
+        ));
     }
 
-    // 🚀 执行搜索（单轮或多轮）
-    let context_result = if valid_queries.len() > 1 && enable_multi_round.unwrap_or(true) {
-        info!("🔄 Using multi-round search with {} queries", valid_queries.len());
-        match client.multi_round_search(&project_path, &valid_queries, max_length * 2).await {
-            Ok(ctx) => ctx,
-            Err(e) => {
-                error!("Failed to perform multi-round search: {}", e);
-                let _ = client.shutdown().await;
-                return Ok(EnhancementResult {
-                    original_prompt: prompt.clone(),
-                    enhanced_prompt: prompt,
-                    context_count: 0,
-                    acemcp_used: false,
-                    error: Some(format!("Failed to search context: {}", e)),
-                });
-            }
-        }
-    } else {
-        info!("🔍 Using single-round search");
-        match client.search_context(&project_path, &valid_queries[0]).await {
-            Ok(ctx) => ctx,
-            Err(e) => {
-                error!("Failed to search context: {}", e);
-                let _ = client.shutdown().await;
-                return Ok(EnhancementResult {
-                    original_prompt: prompt.clone(),
-                    enhanced_prompt: prompt,
-                    context_count: 0,
-                    acemcp_used: false,
-                    error: Some(format!("Failed to search context: {}", e)),
-                });
-            }
+    // 🚀 执行搜索（单轮或多轮），受设置中的 enhancer 超时约束
+    let search_timeout = crate::commands::execution_timeout::enhancer_timeout(&app);
+    let search = async {
+        let result = if valid_queries.len() > 1 && enable_multi_round.unwrap_or(true) {
+            info!("🔄 Using multi-round search with {} queries", valid_queries.len());
+            client
+                .multi_round_search(&project_path, &valid_queries, max_length * 2, max_attempts)
+                .await
+        } else {
+            info!("🔍 Using single-round search");
+            client
+                .search_context_with_retry(&project_path, &valid_queries[0], max_attempts)
+                .await
+        };
+        result.map_err(|e| format!("Failed to search context: {}", e))
+    };
+    let search_result = match search_timeout {
+        Some(timeout) => tokio::time::timeout(timeout, search).await.unwrap_or_else(|_| {
+            Err(format!("Context search timed out after {}s", timeout.as_secs()))
+        }),
+        None => search.await,
+    };
+    let context_result = match search_result {
+        Ok(ctx) => ctx,
+        Err(e) => {
+            error!("{}", e);
+            let _ = client.shutdown().await;
+            return Ok(file_refs_only_result(prompt, file_context.as_deref(), e));
         }
     };
 
     // 关闭客户端
     let _ = client.shutdown().await;
 
+    // 📎 @文件 引用放在语义搜索结果之前，截断时优先保留
+    let context_result = match file_context {
+        Some(file_context) if context_result.trim().is_empty() => file_context,
+        Some(file_context) => format!("{}\n\n{}", file_context, context_result),
+        None => context_result,
+    };
+
     // ⚡ 改进：智能处理上下文结果
     let trimmed_context = if context_result.len() > max_length {
         warn!("Context too long ({} chars), truncating to {} chars",
@@ -1181,6 +1432,10 @@ pub async fn enhance_prompt_with_context(
 #[tauri::command]
 pub async fn test_acemcp_availability(app: AppHandle) -> Result<bool, String> {
     info!("Testing acemcp availability...");
+    if crate::commands::network_mode::is_offline() {
+        info!("Offline mode: acemcp reported as unavailable");
+        return Ok(false);
+    }
 
     match AcemcpClient::start(&app).await {
         Ok(mut client) => {
@@ -1200,6 +1455,112 @@ pub async fn test_acemcp_availability(app: AppHandle) -> Result<bool, String> {
     }
 }
 
+// ============================================================================
+// 单次检索调试
+// ============================================================================
+
+/// 一条 search_context 检索结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcemcpSearchHit {
+    /// 片段所在文件（结果中没有 Path: 行时为 None）
+    pub path: Option<String>,
+    pub content: String,
+}
+
+/// acemcp_search 的返回结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcemcpSearchResult {
+    pub query: String,
+    pub hits: Vec<AcemcpSearchHit>,
+    /// 截断前的结果总数
+    pub total_hits: usize,
+    /// search_context 返回的原始文本
+    pub raw: String,
+    pub elapsed_ms: u64,
+}
+
+/// 只执行一次 search_context 并返回结构化结果，不做提示词增强
+/// 用于调试语义检索的相关性
+#[tauri::command]
+pub async fn acemcp_search(
+    app: AppHandle,
+    project_path: String,
+    query: String,
+    top_k: Option<usize>,
+) -> Result<AcemcpSearchResult, String> {
+    info!("acemcp_search: project={}, query={}, top_k={:?}", project_path, query, top_k);
+
+    crate::commands::network_mode::ensure_online("acemcp search")?;
+    if query.trim().is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+    if !std::path::Path::new(&project_path).is_dir() {
+        return Err(format!("Project path does not exist: {}", project_path));
+    }
+
+    let mut client = AcemcpClient::start(&app)
+        .await
+        .map_err(|e| format!("Failed to start acemcp: {}", e))?;
+    if let Err(e) = client.initialize().await {
+        let _ = client.shutdown().await;
+        return Err(format!("Failed to initialize acemcp: {}", e));
+    }
+
+    let started = std::time::Instant::now();
+    let result = client.search_context(&project_path, &query).await;
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    let _ = client.shutdown().await;
+
+    let raw = result.map_err(|e| format!("search_context failed: {}", e))?;
+    let mut hits = parse_search_hits(&raw);
+    let total_hits = hits.len();
+    if let Some(top_k) = top_k {
+        hits.truncate(top_k);
+    }
+
+    info!("acemcp_search returned {} hit(s) in {}ms", total_hits, elapsed_ms);
+    Ok(AcemcpSearchResult {
+        query,
+        hits,
+        total_hits,
+        raw,
+        elapsed_ms,
+    })
+}
+
+/// 按 "Path:" 切分 search_context 的文本结果（与多轮搜索的去重切分方式一致）
+fn parse_search_hits(raw: &str) -> Vec<AcemcpSearchHit> {
+    raw.split("\n\nPath:")
+        .enumerate()
+        .filter_map(|(i, snippet)| {
+            if snippet.trim().is_empty() {
+                return None;
+            }
+            let snippet = if i == 0 {
+                match snippet.trim_start().strip_prefix("Path:") {
+                    Some(rest) => rest,
+                    // 没有 Path: 前缀的开头文本（如 "未找到相关代码"）
+                    None => {
+                        return Some(AcemcpSearchHit {
+                            path: None,
+                            content: snippet.trim().to_string(),
+                        })
+                    }
+                }
+            } else {
+                snippet
+            };
+            let (path, content) = snippet.split_once('\n').unwrap_or((snippet, ""));
+            Some(AcemcpSearchHit {
+                path: Some(path.trim().to_string()).filter(|p| !p.is_empty()),
+                content: content.trim_end().to_string(),
+            })
+        })
+        .collect()
+}
+
 // ============================================================================
 // Acemcp 配置管理
 // ============================================================================
@@ -1234,7 +1595,6 @@ pub async fn save_acemcp_config(
     max_lines_per_blob: Option<u32>,
 ) -> Result<(), String> {
     use std::fs;
-    use std::collections::HashMap;
 
     info!("Saving acemcp config: base_url={}", base_url);
 
@@ -1449,15 +1809,182 @@ fn extract_toml_number_value(line: &str) -> Option<u32> {
     None
 }
 
+// ============================================================================
+// 索引范围配置（TEXT_EXTENSIONS / EXCLUDE_PATTERNS）
+// ============================================================================
+
+/// acemcp 索引范围；未在配置中设置的键返回空数组（使用 acemcp 内置默认值）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AcemcpIndexPatterns {
+    pub text_extensions: Vec<String>,
+    pub exclude_patterns: Vec<String>,
+}
+
+/// 读取 ~/.acemcp/config.toml 中的 TEXT_EXTENSIONS / EXCLUDE_PATTERNS
+#[tauri::command]
+pub async fn get_acemcp_index_patterns() -> Result<AcemcpIndexPatterns, String> {
+    use std::fs;
+
+    let config_file = dirs::home_dir()
+        .ok_or("Cannot find home directory")?
+        .join(".acemcp")
+        .join("config.toml");
+
+    if !config_file.exists() {
+        return Ok(AcemcpIndexPatterns {
+            text_extensions: Vec::new(),
+            exclude_patterns: Vec::new(),
+        });
+    }
+
+    let content = fs::read_to_string(&config_file)
+        .map_err(|e| format!("Failed to read config: {}", e))?;
+    let table: toml::Table = toml::from_str(&content)
+        .map_err(|e| format!("Failed to parse config: {}", e))?;
+
+    let string_array = |key: &str| -> Vec<String> {
+        table
+            .get(key)
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|item| item.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    Ok(AcemcpIndexPatterns {
+        text_extensions: string_array("TEXT_EXTENSIONS"),
+        exclude_patterns: string_array("EXCLUDE_PATTERNS"),
+    })
+}
+
+/// 写入 TEXT_EXTENSIONS / EXCLUDE_PATTERNS，保留配置文件中的其他内容
+#[tauri::command]
+pub async fn set_acemcp_index_patterns(
+    text_extensions: Vec<String>,
+    exclude_patterns: Vec<String>,
+) -> Result<(), String> {
+    use std::fs;
+
+    let text_extensions: Vec<String> = text_extensions
+        .iter()
+        .map(|ext| ext.trim())
+        .filter(|ext| !ext.is_empty())
+        .map(|ext| if ext.starts_with('.') { ext.to_string() } else { format!(".{}", ext) })
+        .collect();
+    let exclude_patterns: Vec<String> = exclude_patterns
+        .iter()
+        .map(|pattern| pattern.trim().to_string())
+        .filter(|pattern| !pattern.is_empty())
+        .collect();
+
+    if text_extensions.is_empty() {
+        return Err("TEXT_EXTENSIONS must contain at least one extension".to_string());
+    }
+    if exclude_patterns.is_empty() {
+        return Err("EXCLUDE_PATTERNS must contain at least one pattern".to_string());
+    }
+
+    let config_dir = dirs::home_dir()
+        .ok_or("Cannot find home directory")?
+        .join(".acemcp");
+    let config_file = config_dir.join("config.toml");
+
+    // 与 save_acemcp_config 一致：目录由 acemcp 首次运行时创建
+    if !config_dir.exists() {
+        return Err(format!(
+            "配置目录不存在：{:?}\n\n\
+            这是因为 acemcp 尚未运行。请先点击「测试连接」按钮，\n\
+            这会触发 acemcp 启动并自动创建配置目录。",
+            config_dir
+        ));
+    }
+
+    let existing_content = if config_file.exists() {
+        fs::read_to_string(&config_file)
+            .map_err(|e| format!("Failed to read existing config: {}", e))?
+    } else {
+        String::new()
+    };
+
+    let mut toml_content =
+        remove_toml_entries(&existing_content, &["TEXT_EXTENSIONS", "EXCLUDE_PATTERNS"]);
+    if !toml_content.is_empty() && !toml_content.ends_with('\n') {
+        toml_content.push('\n');
+    }
+    toml_content.push_str(&render_toml_string_array("TEXT_EXTENSIONS", &text_extensions));
+    toml_content.push_str(&render_toml_string_array("EXCLUDE_PATTERNS", &exclude_patterns));
+
+    // 写入前校验结果仍是合法 TOML，避免破坏用户配置
+    toml::from_str::<toml::Table>(&toml_content)
+        .map_err(|e| format!("Refusing to write invalid config: {}", e))?;
+
+    fs::write(&config_file, toml_content)
+        .map_err(|e| format!("Failed to write config: {}", e))?;
+
+    info!(
+        "Acemcp index patterns saved: {} extensions, {} exclude patterns",
+        text_extensions.len(),
+        exclude_patterns.len()
+    );
+    Ok(())
+}
+
+/// 删除指定的顶层键（支持多行数组），其余行原样保留
+fn remove_toml_entries(content: &str, keys: &[&str]) -> String {
+    let mut kept = Vec::new();
+    let mut skipping_array = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if skipping_array {
+            if trimmed.ends_with(']') {
+                skipping_array = false;
+            }
+            continue;
+        }
+
+        let key = trimmed.split('=').next().unwrap_or("").trim();
+        if trimmed.contains('=') && keys.contains(&key) {
+            let value_part = trimmed[trimmed.find('=').unwrap_or(0) + 1..].trim();
+            skipping_array = value_part.starts_with('[') && !value_part.ends_with(']');
+            continue;
+        }
+        kept.push(line);
+    }
+
+    kept.join("\n")
+}
+
+/// 渲染为多行 TOML 字符串数组
+fn render_toml_string_array(key: &str, values: &[String]) -> String {
+    let mut out = format!("{} = [\n", key);
+    for value in values {
+        out.push_str(&format!("    {},\n", toml::Value::String(value.clone())));
+    }
+    out.push_str("]\n");
+    out
+}
+
 // ============================================================================
 // 后台预索引
 // ============================================================================
 
 /// 后台预索引项目（不阻塞 UI）
 /// 在用户选择项目后自动调用，提前完成索引以加快后续搜索
+/// `force` 为 true 时先清除已有索引，强制完整重建
 #[tauri::command]
-pub async fn preindex_project(app: AppHandle, project_path: String) -> Result<(), String> {
+pub async fn preindex_project(
+    app: AppHandle,
+    project_path: String,
+    force: Option<bool>,
+) -> Result<(), String> {
     info!("Starting background pre-indexing for project: {}", project_path);
+    crate::commands::network_mode::ensure_online("acemcp indexing")?;
 
     // 检查项目路径是否存在
     if !std::path::Path::new(&project_path).exists() {
@@ -1465,6 +1992,10 @@ pub async fn preindex_project(app: AppHandle, project_path: String) -> Result<()
         return Ok(());
     }
 
+    if force.unwrap_or(false) {
+        clear_acemcp_index(project_path.clone()).await?;
+    }
+
     // 启动后台任务进行索引
     tauri::async_runtime::spawn(async move {
         match preindex_project_internal(&app, &project_path).await {
@@ -1501,6 +2032,165 @@ async fn preindex_project_internal(app: &AppHandle, project_path: &str) -> Resul
     Ok(())
 }
 
+/// 清除项目索引，下次搜索时会重新上传并索引全部文件
+/// sidecar 将每个项目已上传的 blob 列表保存在 ~/.acemcp/projects.json（键为规范化路径）
+/// 返回是否存在并删除了该项目的索引
+#[tauri::command]
+pub async fn clear_acemcp_index(project_path: String) -> Result<bool, String> {
+    use std::fs;
+
+    info!("Clearing acemcp index for project: {}", project_path);
+
+    let projects_file = dirs::home_dir()
+        .ok_or("Cannot find home directory")?
+        .join(".acemcp")
+        .join("projects.json");
+
+    if !projects_file.exists() {
+        info!("No acemcp index file found, nothing to clear");
+        return Ok(false);
+    }
+
+    let content = fs::read_to_string(&projects_file)
+        .map_err(|e| format!("Failed to read projects.json: {}", e))?;
+    let mut projects: serde_json::Map<String, Value> = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse projects.json: {}", e))?;
+
+    // 与 sidecar 的 normalizeProjectPath 保持一致：正斜杠、无尾部斜杠
+    let normalize = |path: &str| {
+        let normalized = path.trim().replace('\\', "/");
+        if normalized.len() > 1 {
+            normalized.trim_end_matches('/').to_string()
+        } else {
+            normalized
+        }
+    };
+    let target = normalize(&project_path);
+
+    let original_len = projects.len();
+    projects.retain(|key, _| normalize(key) != target);
+    if projects.len() == original_len {
+        info!("Project was not indexed: {}", target);
+        return Ok(false);
+    }
+
+    let content = serde_json::to_string_pretty(&projects)
+        .map_err(|e| format!("Failed to serialize projects.json: {}", e))?;
+    fs::write(&projects_file, content)
+        .map_err(|e| format!("Failed to write projects.json: {}", e))?;
+
+    info!("✅ Cleared acemcp index for: {}", target);
+    Ok(true)
+}
+
+// ============================================================================
+// 本地文件排序（未配置 acemcp 时的轻量回退）
+// ============================================================================
+
+/// 遍历项目时最多访问的文件数
+const RANK_MAX_FILES: usize = 20_000;
+
+/// 遍历项目的最大目录深度
+const RANK_MAX_DEPTH: usize = 12;
+
+/// 按提示词关键词与文件名/路径的重合度对项目文件排序，返回前 top_k 个相对路径
+/// 不依赖 acemcp 索引，作为增强上下文的本地回退
+#[tauri::command]
+pub async fn rank_project_files_for_prompt(
+    project_path: String,
+    prompt: String,
+    top_k: usize,
+) -> Result<Vec<String>, String> {
+    let root = PathBuf::from(&project_path);
+    if !root.is_dir() {
+        return Err(format!("Project path is not a directory: {}", project_path));
+    }
+
+    let mut keywords: Vec<String> = Vec::new();
+    for keyword in extract_keywords(&prompt).split_whitespace() {
+        let keyword = keyword.to_lowercase();
+        if !keywords.contains(&keyword) {
+            keywords.push(keyword);
+        }
+    }
+    if keywords.is_empty() {
+        return Ok(Vec::new());
+    }
+    info!("Ranking files in {} for keywords {:?}", project_path, keywords);
+
+    tokio::task::spawn_blocking(move || {
+        let mut files = Vec::new();
+        collect_rankable_files(&root, &root, 0, &mut files);
+
+        let mut scored: Vec<(usize, String)> = files
+            .into_iter()
+            .filter_map(|relative| {
+                let score = score_path_for_keywords(&relative, &keywords);
+                (score > 0).then_some((score, relative))
+            })
+            .collect();
+        // 分数相同时优先路径较短（层级更浅）的文件
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.len().cmp(&b.1.len())));
+        scored.into_iter().take(top_k).map(|(_, path)| path).collect()
+    })
+    .await
+    .map_err(|e| format!("Failed to rank project files: {}", e))
+}
+
+/// 收集项目内的文件相对路径（统一使用 / 分隔），跳过隐藏目录和依赖/构建目录
+fn collect_rankable_files(root: &Path, dir: &Path, depth: usize, files: &mut Vec<String>) {
+    if depth > RANK_MAX_DEPTH || files.len() >= RANK_MAX_FILES {
+        return;
+    }
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) => {
+            debug!("Skipping unreadable directory {:?}: {}", dir, e);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            if !crate::commands::claude::is_skipped_search_dir(&name) {
+                collect_rankable_files(root, &path, depth + 1, files);
+            }
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            files.push(relative.to_string_lossy().replace('\\', "/"));
+            if files.len() >= RANK_MAX_FILES {
+                return;
+            }
+        }
+    }
+}
+
+/// 计算路径与关键词的匹配分：文件名完全匹配 5 分，文件名包含 3 分，仅目录路径包含 1 分
+fn score_path_for_keywords(relative_path: &str, keywords: &[String]) -> usize {
+    let lower = relative_path.to_lowercase();
+    let file_name = lower.rsplit('/').next().unwrap_or(&lower);
+    let stem = file_name.split('.').next().unwrap_or(file_name);
+
+    keywords
+        .iter()
+        .map(|keyword| {
+            if stem == keyword.as_str() {
+                5
+            } else if file_name.contains(keyword.as_str()) {
+                3
+            } else if lower.contains(keyword.as_str()) {
+                1
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
 // ============================================================================
 // Sidecar 导出（用于 CLI 配置）
 // ============================================================================
@@ -1569,6 +2259,39 @@ pub async fn export_acemcp_sidecar(target_path: String) -> Result<String, String
     Ok(final_path_str)
 }
 
+/// 注册到 Claude 配置中的 MCP server 名称
+const ACEMCP_MCP_SERVER_NAME: &str = "acemcp";
+
+/// 导出 sidecar 到 ~/.acemcp/ 并在 Claude 配置（~/.claude.json）中添加或更新 acemcp MCP server
+/// sidecar 固定从 ~/.acemcp/config.toml 读取配置，因此无需额外传入配置路径
+/// 返回被修改的配置文件路径
+#[tauri::command]
+pub async fn register_acemcp_with_claude() -> Result<String, String> {
+    info!("Registering acemcp sidecar with Claude");
+
+    let sidecar_path = export_acemcp_sidecar("~/.acemcp".to_string()).await?;
+
+    let config_file = dirs::home_dir()
+        .ok_or("Cannot find home directory")?
+        .join(".acemcp")
+        .join("config.toml");
+    if !config_file.exists() {
+        warn!("Acemcp config not found at {:?}, the MCP server will fail until it is saved", config_file);
+    }
+
+    let claude_config = crate::commands::mcp::write_claude_mcp_server(
+        ACEMCP_MCP_SERVER_NAME,
+        "node",
+        &[sidecar_path],
+        &HashMap::new(),
+        true,
+    )?;
+
+    let claude_config = claude_config.to_string_lossy().to_string();
+    info!("✅ Registered acemcp MCP server in: {}", claude_config);
+    Ok(claude_config)
+}
+
 /// 获取 ~/.acemcp/ 目录中的 sidecar 路径（如果存在）
 #[tauri::command]
 pub async fn get_extracted_sidecar_path() -> Result<Option<String>, String> {
@@ -0,0 +1,248 @@
+//! Activity heatmap
+//!
+//! Counts the prompts sent per local calendar day over the last N days, read
+//! from the timestamps in stored session files, for a contribution-graph style
+//! dashboard. Files not modified since the start of the range are skipped
+//! without being read.
+
+use chrono::{DateTime, Duration, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::claude::{get_claude_dir, list_projects, project_path_key, ProjectPathMatcher};
+use super::codex::config::get_codex_sessions_dir;
+use super::codex::session::parse_codex_session_file;
+use super::gemini::config::{get_gemini_dir, get_project_session_dir};
+use super::transcript::is_claude_prompt;
+
+/// Longest range accepted, in days
+const MAX_DAYS: u32 = 3660;
+
+/// Prompts sent on one day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityDay {
+    /// "YYYY-MM-DD" in local time
+    pub date: String,
+    pub count: u32,
+}
+
+/// Returns one entry per day for the last `days` days (oldest first, today
+/// included, days without prompts count 0) for "claude", "codex" or "gemini",
+/// optionally limited to one project
+#[tauri::command]
+pub async fn get_activity_heatmap(
+    tool: String,
+    days: u32,
+    project_path: Option<String>,
+) -> Result<Vec<ActivityDay>, String> {
+    if days == 0 || days > MAX_DAYS {
+        return Err(format!("Days must be between 1 and {}", MAX_DAYS));
+    }
+    let project_path = project_path.filter(|p| !p.trim().is_empty());
+    let since = SystemTime::now() - std::time::Duration::from_secs(u64::from(days) * 86_400);
+
+    let timestamps = match tool.as_str() {
+        "claude" => claude_prompt_timestamps(project_path.as_deref(), since).await?,
+        "codex" => codex_prompt_timestamps(project_path.as_deref(), since)?,
+        "gemini" => gemini_prompt_timestamps(project_path.as_deref(), since)?,
+        _ => return Err(format!("Unsupported tool: {}", tool)),
+    };
+
+    let heatmap = bucket_by_day(&timestamps, Local::now().date_naive(), days);
+    log::info!(
+        "Activity heatmap for {}: {} prompt(s) over {} day(s)",
+        tool,
+        heatmap.iter().map(|d| d.count).sum::<u32>(),
+        days
+    );
+    Ok(heatmap)
+}
+
+async fn claude_prompt_timestamps(
+    project_path: Option<&str>,
+    since: SystemTime,
+) -> Result<Vec<String>, String> {
+    let projects_dir = get_claude_dir()
+        .map_err(|e| e.to_string())?
+        .join("projects");
+    let project_key = project_path.map(project_path_key);
+
+    let mut timestamps = Vec::new();
+    for project in list_projects().await? {
+        if let Some(key) = &project_key {
+            if project_path_key(&project.path) != *key {
+                continue;
+            }
+        }
+        for session_id in &project.sessions {
+            let path = projects_dir
+                .join(&project.id)
+                .join(format!("{}.jsonl", session_id));
+            if modified_since(&path, since) {
+                timestamps.extend(jsonl_timestamps(&path, is_claude_prompt));
+            }
+        }
+    }
+    Ok(timestamps)
+}
+
+fn codex_prompt_timestamps(
+    project_path: Option<&str>,
+    since: SystemTime,
+) -> Result<Vec<String>, String> {
+    let sessions_dir = get_codex_sessions_dir()?;
+    if !sessions_dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut project_matcher = project_path.map(ProjectPathMatcher::new);
+
+    let mut timestamps = Vec::new();
+    for entry in walkdir::WalkDir::new(&sessions_dir).into_iter().flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("jsonl")
+            || !modified_since(path, since)
+        {
+            continue;
+        }
+        if let Some(matcher) = &mut project_matcher {
+            let in_project = parse_codex_session_file(path)
+                .is_some_and(|session| matcher.matches(&session.project_path));
+            if !in_project {
+                continue;
+            }
+        }
+        timestamps.extend(jsonl_timestamps(path, is_codex_prompt));
+    }
+    Ok(timestamps)
+}
+
+fn gemini_prompt_timestamps(
+    project_path: Option<&str>,
+    since: SystemTime,
+) -> Result<Vec<String>, String> {
+    // Chats live in ~/.gemini/tmp/<project hash>/chats/
+    let project_dirs: Vec<PathBuf> = match project_path {
+        Some(path) => vec![get_project_session_dir(path)?],
+        None => match fs::read_dir(get_gemini_dir()?.join("tmp")) {
+            Ok(entries) => entries.flatten().map(|e| e.path()).collect(),
+            Err(_) => Vec::new(),
+        },
+    };
+
+    let mut timestamps = Vec::new();
+    for project_dir in project_dirs {
+        let entries = match fs::read_dir(project_dir.join("chats")) {
+            Ok(entries) => entries,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json")
+                || !modified_since(&path, since)
+            {
+                continue;
+            }
+            let chat: Value = match fs::read_to_string(&path)
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+            {
+                Some(chat) => chat,
+                None => continue,
+            };
+            for message in chat["messages"].as_array().into_iter().flatten() {
+                if message["type"].as_str() == Some("user") {
+                    if let Some(timestamp) = message["timestamp"].as_str() {
+                        timestamps.push(timestamp.to_string());
+                    }
+                }
+            }
+        }
+    }
+    Ok(timestamps)
+}
+
+fn modified_since(path: &Path, since: SystemTime) -> bool {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .map(|modified| modified >= since)
+        .unwrap_or(false)
+}
+
+/// Timestamps of the JSONL lines accepted by `is_prompt`
+fn jsonl_timestamps(path: &Path, is_prompt: fn(&Value) -> bool) -> Vec<String> {
+    let file = match fs::File::open(path) {
+        Ok(file) => file,
+        Err(_) => return Vec::new(),
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok())
+        .filter(|entry| is_prompt(entry))
+        .filter_map(|entry| entry["timestamp"].as_str().map(|t| t.to_string()))
+        .collect()
+}
+
+fn is_codex_prompt(entry: &Value) -> bool {
+    entry["type"].as_str() == Some("event_msg")
+        && entry["payload"]["type"].as_str() == Some("user_message")
+}
+
+/// Counts timestamps per local day for the `days` days ending with `today`
+fn bucket_by_day(timestamps: &[String], today: NaiveDate, days: u32) -> Vec<ActivityDay> {
+    let first_day = today - Duration::days(i64::from(days) - 1);
+
+    let mut counts: HashMap<NaiveDate, u32> = HashMap::new();
+    for timestamp in timestamps {
+        if let Ok(time) = DateTime::parse_from_rfc3339(timestamp) {
+            let day = time.with_timezone(&Local).date_naive();
+            if day >= first_day && day <= today {
+                *counts.entry(day).or_insert(0) += 1;
+            }
+        }
+    }
+
+    first_day
+        .iter_days()
+        .take(days as usize)
+        .map(|day| ActivityDay {
+            date: day.format("%Y-%m-%d").to_string(),
+            count: counts.get(&day).copied().unwrap_or(0),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_prompts_into_contiguous_days() {
+        let today = Local::now().date_naive();
+        let noon = |day: NaiveDate| {
+            day.and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_local_timezone(Local)
+                .unwrap()
+                .to_rfc3339()
+        };
+        let timestamps = vec![
+            noon(today),
+            noon(today),
+            noon(today - Duration::days(2)),
+            noon(today - Duration::days(30)),
+            "not a timestamp".to_string(),
+        ];
+
+        let heatmap = bucket_by_day(&timestamps, today, 3);
+        let counts: Vec<u32> = heatmap.iter().map(|d| d.count).collect();
+        assert_eq!(counts, vec![1, 0, 2]);
+        assert_eq!(heatmap[2].date, today.format("%Y-%m-%d").to_string());
+    }
+}
@@ -0,0 +1,123 @@
+//! File and directory bookmarks
+//!
+//! Backs the "favorites" section of the file picker. Bookmarks are stored in
+//! ~/.claude/bookmarks.json next to the other app-managed config files.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::claude::get_claude_dir;
+
+/// A stored bookmark
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct StoredBookmark {
+    path: String,
+    label: String,
+    created_at: i64,
+}
+
+/// A bookmark as returned to the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bookmark {
+    pub path: String,
+    pub label: String,
+    pub created_at: i64,
+    pub is_directory: bool,
+    /// False when the bookmarked path has since been moved or deleted
+    pub exists: bool,
+}
+
+fn get_bookmarks_path() -> Result<PathBuf, String> {
+    Ok(get_claude_dir().map_err(|e| e.to_string())?.join("bookmarks.json"))
+}
+
+fn load_bookmarks() -> Result<Vec<StoredBookmark>, String> {
+    let path = get_bookmarks_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read bookmarks.json: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse bookmarks.json: {}", e))
+}
+
+fn save_bookmarks(bookmarks: &[StoredBookmark]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(bookmarks)
+        .map_err(|e| format!("Failed to serialize bookmarks: {}", e))?;
+    fs::write(get_bookmarks_path()?, content)
+        .map_err(|e| format!("Failed to write bookmarks.json: {}", e))
+}
+
+fn to_bookmark(stored: StoredBookmark) -> Bookmark {
+    let path = Path::new(&stored.path);
+    Bookmark {
+        exists: path.exists(),
+        is_directory: path.is_dir(),
+        path: stored.path,
+        label: stored.label,
+        created_at: stored.created_at,
+    }
+}
+
+/// Bookmarks a file or directory; re-adding an existing path updates its label
+#[tauri::command]
+pub async fn add_bookmark(path: String, label: Option<String>) -> Result<Bookmark, String> {
+    log::info!("Adding bookmark: {}", path);
+
+    let target = Path::new(&path);
+    if !target.exists() {
+        return Err(format!("Path does not exist: {}", path));
+    }
+
+    let label = label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty())
+        .or_else(|| target.file_name().map(|n| n.to_string_lossy().to_string()))
+        .unwrap_or_else(|| path.clone());
+
+    let mut bookmarks = load_bookmarks()?;
+    let stored = match bookmarks.iter_mut().find(|b| b.path == path) {
+        Some(existing) => {
+            existing.label = label;
+            existing.clone()
+        }
+        None => {
+            let stored = StoredBookmark {
+                path,
+                label,
+                created_at: chrono::Utc::now().timestamp(),
+            };
+            bookmarks.push(stored.clone());
+            stored
+        }
+    };
+
+    save_bookmarks(&bookmarks)?;
+    Ok(to_bookmark(stored))
+}
+
+/// Removes a bookmark; returns false if the path was not bookmarked
+#[tauri::command]
+pub async fn remove_bookmark(path: String) -> Result<bool, String> {
+    log::info!("Removing bookmark: {}", path);
+
+    let mut bookmarks = load_bookmarks()?;
+    let original_len = bookmarks.len();
+    bookmarks.retain(|b| b.path != path);
+
+    if bookmarks.len() == original_len {
+        return Ok(false);
+    }
+    save_bookmarks(&bookmarks)?;
+    Ok(true)
+}
+
+/// Lists all bookmarks in insertion order, including stale ones
+#[tauri::command]
+pub async fn list_bookmarks() -> Result<Vec<Bookmark>, String> {
+    Ok(load_bookmarks()?.into_iter().map(to_bookmark).collect())
+}
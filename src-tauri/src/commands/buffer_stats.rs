@@ -0,0 +1,73 @@
+//! Running output buffer limits
+//!
+//! Codex and Claude keep the output of each running session in memory so a
+//! reloaded frontend can catch up. Buffers are capped (5MB by default) and drop
+//! their oldest lines when full; these commands expose the cap and per-session usage.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, State};
+
+use super::codex::CodexProcessState;
+use super::settings_store::{self, SettingKey};
+use crate::process::{output_buffer, OutputBufferStats, ProcessRegistryState};
+
+/// Buffer usage of one running session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionBufferStats {
+    pub session_id: String,
+    /// "codex" or "claude"
+    pub tool: String,
+    #[serde(flatten)]
+    pub stats: OutputBufferStats,
+}
+
+/// Returns the size of a running session's output buffer and whether it was truncated
+#[tauri::command]
+pub async fn get_session_buffer_stats(
+    session_id: String,
+    codex_state: State<'_, CodexProcessState>,
+    registry: State<'_, ProcessRegistryState>,
+) -> Result<SessionBufferStats, String> {
+    if let Some(buffer) = codex_state.output_buffers.lock().await.get(&session_id) {
+        return Ok(SessionBufferStats {
+            session_id,
+            tool: "codex".to_string(),
+            stats: buffer.stats(),
+        });
+    }
+
+    if let Some(process) = registry.0.get_claude_session_by_id(&session_id)? {
+        if let Some(stats) = registry.0.get_live_output_stats(process.run_id)? {
+            return Ok(SessionBufferStats {
+                session_id,
+                tool: "claude".to_string(),
+                stats,
+            });
+        }
+    }
+
+    Err(format!("No running session: {}", session_id))
+}
+
+/// Sets the per-session output buffer cap in bytes; 0 removes the cap
+/// Applies to running sessions from their next output line.
+#[tauri::command]
+pub async fn set_output_buffer_cap(app: AppHandle, bytes: usize) -> Result<(), String> {
+    settings_store::set_setting(&app, SettingKey::OutputBufferCap, &bytes.to_string())?;
+    output_buffer::set_output_buffer_cap(bytes);
+    log::info!("Output buffer cap set to {} bytes", bytes);
+    Ok(())
+}
+
+/// Restores the persisted cap; called once at startup
+pub fn load_output_buffer_cap(app: &AppHandle) {
+    match settings_store::get_setting(app, SettingKey::OutputBufferCap) {
+        Ok(Some(value)) => match value.parse::<usize>() {
+            Ok(bytes) => output_buffer::set_output_buffer_cap(bytes),
+            Err(e) => log::warn!("Invalid output buffer cap {:?}: {}", value, e),
+        },
+        Ok(None) => {}
+        Err(e) => log::warn!("Failed to load output buffer cap: {}", e),
+    }
+}
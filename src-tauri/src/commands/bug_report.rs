@@ -0,0 +1,87 @@
+//! Bug report bundle
+//!
+//! Collects what is usually asked for in an issue — app version, OS, tool
+//! versions, recent log lines and redacted configuration — into one zip that
+//! can be attached as-is. The log tail comes from the in-memory buffer kept by
+//! `log_buffer`, since the app writes no log file.
+
+use serde::Serialize;
+use std::fs;
+use std::io::Write;
+use tauri::{AppHandle, Manager};
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::config_backup::read_redacted_config;
+use super::tool_status::{get_all_tool_status, ToolStatus};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BugReport {
+    created_at: String,
+    app_version: String,
+    os: OsInfo,
+    tools: Vec<ToolStatus>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct OsInfo {
+    os: String,
+    arch: String,
+    family: String,
+}
+
+/// Writes a bug report zip to the app log directory and returns its path
+/// The zip holds `report.json`, `app.log` with the recent log lines, and each
+/// config file under `config/`, with secrets replaced by a placeholder.
+#[tauri::command]
+pub async fn generate_bug_report(app: AppHandle) -> Result<String, String> {
+    log::info!("Generating bug report");
+
+    let report_dir = app
+        .path()
+        .app_log_dir()
+        .map_err(|e| format!("Failed to get log directory: {}", e))?;
+
+    let tools = get_all_tool_status(app.clone()).await?;
+    let report = BugReport {
+        created_at: chrono::Utc::now().to_rfc3339(),
+        app_version: app.package_info().version.to_string(),
+        os: OsInfo {
+            os: std::env::consts::OS.to_string(),
+            arch: std::env::consts::ARCH.to_string(),
+            family: std::env::consts::FAMILY.to_string(),
+        },
+        tools,
+    };
+    let json = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Failed to serialize bug report: {}", e))?;
+
+    fs::create_dir_all(&report_dir)
+        .map_err(|e| format!("Failed to create log directory: {}", e))?;
+    let path = report_dir.join(format!(
+        "bug-report-{}.zip",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    let file =
+        fs::File::create(&path).map_err(|e| format!("Failed to create bug report: {}", e))?;
+
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut add_entry = |name: &str, content: &str| -> Result<(), String> {
+        zip.start_file(name, options)
+            .and_then(|_| zip.write_all(content.as_bytes()).map_err(Into::into))
+            .map_err(|e| format!("Failed to write {} to bug report: {}", name, e))
+    };
+    add_entry("report.json", &json)?;
+    add_entry("app.log", &crate::log_buffer::recent_log_text())?;
+    for (relative, content) in read_redacted_config()? {
+        add_entry(&format!("config/{}", relative), &content)?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to finish bug report: {}", e))?;
+
+    log::info!("Bug report written to {:?}", path);
+    Ok(path.to_string_lossy().to_string())
+}
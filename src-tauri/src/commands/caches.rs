@@ -0,0 +1,60 @@
+//! Cache cleanup
+//!
+//! One call that drops every cache the app manages: the acemcp upload index
+//! (~/.acemcp/projects.json, rebuilt on the next search) and the in-memory
+//! translation cache. Session data, configuration and the extracted acemcp
+//! sidecar (which Claude's MCP config may point at) are never touched.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::translator::get_translation_service;
+
+/// A cache removed by `clear_all_caches`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearedCache {
+    /// "acemcp-index" or "translation"
+    pub name: String,
+    /// File that was removed, None for in-memory caches
+    pub path: Option<String>,
+    pub reclaimed_bytes: u64,
+}
+
+/// Clears all app-managed caches and reports what was removed
+/// Caches that were already empty are left out of the result.
+#[tauri::command]
+pub async fn clear_all_caches() -> Result<Vec<ClearedCache>, String> {
+    log::info!("Clearing all caches");
+    let mut cleared = Vec::new();
+
+    let acemcp_index = dirs::home_dir()
+        .ok_or_else(|| "Failed to get home directory".to_string())?
+        .join(".acemcp")
+        .join("projects.json");
+    if let Ok(metadata) = fs::metadata(&acemcp_index) {
+        fs::remove_file(&acemcp_index)
+            .map_err(|e| format!("Failed to remove {}: {}", acemcp_index.display(), e))?;
+        cleared.push(ClearedCache {
+            name: "acemcp-index".to_string(),
+            path: Some(acemcp_index.to_string_lossy().to_string()),
+            reclaimed_bytes: metadata.len(),
+        });
+    }
+
+    let translation_bytes = get_translation_service().lock().await.clear_cache().await;
+    if translation_bytes > 0 {
+        cleared.push(ClearedCache {
+            name: "translation".to_string(),
+            path: None,
+            reclaimed_bytes: translation_bytes,
+        });
+    }
+
+    log::info!(
+        "Cleared {} cache(s), {} bytes reclaimed",
+        cleared.len(),
+        cleared.iter().map(|c| c.reclaimed_bytes).sum::<u64>()
+    );
+    Ok(cleared)
+}
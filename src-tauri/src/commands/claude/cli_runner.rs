@@ -1,11 +1,14 @@
 use std::fs;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::process::{Child, Command};
 use tokio::sync::Mutex;
 
+use crate::commands::execution_timeout::spawn_timeout_watchdog;
+use crate::commands::run_error::{record_run_outcome, LastRunErrors, StderrTail};
 use crate::commands::permission_config::{
     ClaudePermissionConfig, ClaudeExecutionConfig, build_execution_args,
 };
@@ -17,12 +20,15 @@ use super::platform;
 /// Global state to track current Claude process
 pub struct ClaudeProcessState {
     pub current_process: Arc<Mutex<Option<Child>>>,
+    /// Exit code and stderr tail of each session's last failed run
+    pub last_errors: LastRunErrors,
 }
 
 impl Default for ClaudeProcessState {
     fn default() -> Self {
         Self {
             current_process: Arc::new(Mutex::new(None)),
+            last_errors: Arc::new(Mutex::new(std::collections::HashMap::new())),
         }
     }
 }
@@ -192,6 +198,11 @@ fn create_command_with_env(program: &str) -> Command {
         }
     }
 
+    // 离线模式：禁止遥测、错误上报和自动更新
+    for (key, value) in crate::commands::network_mode::offline_env_vars() {
+        tokio_cmd.env(key, value);
+    }
+
     tokio_cmd
 }
 
@@ -652,6 +663,15 @@ async fn spawn_claude_process(app: AppHandle, mut cmd: Command, prompt: String,
         *current_process = Some(child);
     }
 
+    // Kill the run once the configured Claude timeout elapses
+    let finished = Arc::new(AtomicBool::new(false));
+    if pid != 0 {
+        let session_id_holder_timeout = session_id_holder.clone();
+        spawn_timeout_watchdog(app.clone(), "claude", pid, finished.clone(), move || {
+            session_id_holder_timeout.lock().unwrap().clone()
+        });
+    }
+
     // Check if auto-compact state is available
     let auto_compact_available = app.try_state::<crate::commands::context_manager::AutoCompactState>().is_some();
 
@@ -792,10 +812,15 @@ async fn spawn_claude_process(app: AppHandle, mut cmd: Command, prompt: String,
 
     let app_handle_stderr = app.clone();
     let session_id_holder_clone2 = session_id_holder.clone();
+    let stderr_tail = Arc::new(std::sync::Mutex::new(StderrTail::default()));
+    let stderr_tail_reader = stderr_tail.clone();
     let stderr_task = tokio::spawn(async move {
         let mut lines = stderr_reader.lines();
         while let Ok(Some(line)) = lines.next_line().await {
             log::error!("Claude stderr: {}", line);
+            if let Ok(mut tail) = stderr_tail_reader.lock() {
+                tail.push(&line);
+            }
             // Emit error lines to the frontend with session isolation if we have session ID
             if let Some(ref session_id) = *session_id_holder_clone2.lock().unwrap() {
                 let _ = app_handle_stderr.emit(&format!("claude-error:{}", session_id), &line);
@@ -808,6 +833,7 @@ async fn spawn_claude_process(app: AppHandle, mut cmd: Command, prompt: String,
     // Wait for the process to complete
     let app_handle_wait = app.clone();
     let claude_state_wait = claude_state.current_process.clone();
+    let last_errors_wait = claude_state.last_errors.clone();
     let session_id_holder_clone3 = session_id_holder.clone();
     let run_id_holder_clone2 = run_id_holder.clone();
     let registry_clone2 = registry.0.clone();
@@ -818,7 +844,17 @@ async fn spawn_claude_process(app: AppHandle, mut cmd: Command, prompt: String,
         // Get the child from the state to wait on it
         let mut current_process = claude_state_wait.lock().await;
         if let Some(mut child) = current_process.take() {
-            match child.wait().await {
+            let wait_result = child.wait().await;
+            let session_id = session_id_holder_clone3.lock().unwrap().clone();
+            if let Some(ref session_id) = session_id {
+                let (success, code) = match &wait_result {
+                    Ok(status) => (status.success(), status.code()),
+                    Err(_) => (false, None),
+                };
+                let tail = stderr_tail.lock().map(|t| t.text()).unwrap_or_default();
+                record_run_outcome(&last_errors_wait, session_id, success, code, tail).await;
+            }
+            match wait_result {
                 Ok(status) => {
                     log::info!("Claude process exited with status: {}", status);
                     // Add a small delay to ensure all messages are processed
@@ -862,6 +898,7 @@ async fn spawn_claude_process(app: AppHandle, mut cmd: Command, prompt: String,
                 }
             }
         }
+        finished.store(true, Ordering::SeqCst);
 
         // Unregister from ProcessRegistry if we have a run_id
         if let Some(run_id) = *run_id_holder_clone2.lock().unwrap() {
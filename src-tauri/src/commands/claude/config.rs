@@ -2,11 +2,10 @@ use std::fs;
 use std::path::PathBuf;
 use std::time::SystemTime;
 
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 use tauri_plugin_shell::ShellExt;
 use regex::Regex;
 use dirs;
-use rusqlite;
 
 
 
@@ -14,8 +13,13 @@ use super::paths::{get_claude_dir, get_codex_dir};
 use super::platform;
 use crate::commands::permission_config::{
     ClaudeExecutionConfig, ClaudePermissionConfig, PermissionMode,
-    DEVELOPMENT_TOOLS, SAFE_TOOLS, ALL_TOOLS
+    DEVELOPMENT_TOOLS, SAFE_TOOLS, ALL_TOOLS,
+    KNOWN_TOOLS,
 };
+use crate::commands::codex::config::{
+    clear_binary_override, get_binary_override, update_binary_override,
+};
+use crate::commands::settings_store::{self, SettingKey};
 use super::{ClaudeMdFile, ClaudeSettings, ClaudeVersionStatus};
 
 #[tauri::command]
@@ -445,10 +449,7 @@ fn find_claude_md_recursive(
         if path.is_dir() {
             // Skip common directories that shouldn't be searched
             if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
-                if matches!(
-                    dir_name,
-                    "node_modules" | "target" | ".git" | "dist" | "build" | ".next" | "__pycache__"
-                ) {
+                if super::file_ops::is_skipped_search_dir(dir_name) {
                     continue;
                 }
             }
@@ -559,38 +560,9 @@ pub async fn set_custom_claude_path(app: AppHandle, custom_path: String) -> Resu
     }
 
     // Store the custom path in database
-    if let Ok(app_data_dir) = app.path().app_data_dir() {
-        if let Err(e) = std::fs::create_dir_all(&app_data_dir) {
-            return Err(format!("Failed to create app data directory: {}", e));
-        }
-
-        let db_path = app_data_dir.join("agents.db");
-        match rusqlite::Connection::open(&db_path) {
-            Ok(conn) => {
-                if let Err(e) = conn.execute(
-                    "CREATE TABLE IF NOT EXISTS app_settings (
-                        key TEXT PRIMARY KEY,
-                        value TEXT NOT NULL
-                    )",
-                    [],
-                ) {
-                    return Err(format!("Failed to create settings table: {}", e));
-                }
-
-                if let Err(e) = conn.execute(
-                    "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
-                    rusqlite::params!["claude_binary_path", path_str],
-                ) {
-                    return Err(format!("Failed to store custom Claude path: {}", e));
-                }
-
-                log::info!("Successfully stored custom Claude CLI path: {}", path_str);
-            }
-            Err(e) => return Err(format!("Failed to open database: {}", e)),
-        }
-    } else {
-        return Err("Failed to get app data directory".to_string());
-    }
+    settings_store::set_setting(&app, SettingKey::ClaudeBinaryPath, &path_str)
+        .map_err(|e| format!("Failed to store custom Claude path: {}", e))?;
+    log::info!("Successfully stored custom Claude CLI path: {}", path_str);
 
     // 记录到 binaries.json 供跨平台检测复用
     if let Err(e) = update_binary_override("claude", &path_str) {
@@ -604,22 +576,17 @@ pub async fn set_custom_claude_path(app: AppHandle, custom_path: String) -> Resu
 #[tauri::command]
 pub async fn get_claude_path(app: AppHandle) -> Result<String, String> {
     log::info!("Getting current Claude CLI path");
-    
-    // Try to get from database first
-    if let Ok(app_data_dir) = app.path().app_data_dir() {
-        let db_path = app_data_dir.join("agents.db");
-        if db_path.exists() {
-            if let Ok(conn) = rusqlite::Connection::open(&db_path) {
-                if let Ok(stored_path) = conn.query_row(
-                    "SELECT value FROM app_settings WHERE key = 'claude_binary_path'",
-                    [],
-                    |row| row.get::<_, String>(0),
-                ) {
-                    log::info!("Found stored Claude path: {}", stored_path);
-                    return Ok(stored_path);
-                }
-            }
-        }
+
+    // binaries.json override first, same order as find_claude_binary
+    if let Some(override_path) = get_binary_override("claude") {
+        log::info!("Found Claude path override: {}", override_path);
+        return Ok(override_path);
+    }
+
+    // Then the path stored in the database
+    if let Ok(Some(stored_path)) = settings_store::get_setting(&app, SettingKey::ClaudeBinaryPath) {
+        log::info!("Found stored Claude path: {}", stored_path);
+        return Ok(stored_path);
     }
     
     // Fall back to auto-detection
@@ -632,34 +599,31 @@ pub async fn get_claude_path(app: AppHandle) -> Result<String, String> {
     }
 }
 
+/// Lists every path the Claude CLI detection considered, with existence and version
+/// Runs discovery fresh, ignoring the cached path, so the list reflects the current system.
+#[tauri::command]
+pub async fn list_claude_binary_candidates() -> Result<Vec<crate::claude_binary::BinaryCandidate>, String> {
+    log::info!("Listing Claude CLI binary candidates");
+    tokio::task::spawn_blocking(crate::claude_binary::collect_claude_binary_candidates)
+        .await
+        .map_err(|e| format!("Failed to list Claude binary candidates: {}", e))
+}
+
 /// Clear custom Claude CLI path and revert to auto-detection
 #[tauri::command]
 pub async fn clear_custom_claude_path(app: AppHandle) -> Result<(), String> {
     log::info!("Clearing custom Claude CLI path");
     
-    if let Ok(app_data_dir) = app.path().app_data_dir() {
-        let db_path = app_data_dir.join("agents.db");
-        if db_path.exists() {
-            if let Ok(conn) = rusqlite::Connection::open(&db_path) {
-                if let Err(e) = conn.execute(
-                    "DELETE FROM app_settings WHERE key = 'claude_binary_path'",
-                    [],
-                ) {
-                    return Err(format!("Failed to clear custom Claude path: {}", e));
-                }
-            }
-        }
-
-        // 清理 binaries.json 覆盖记录（忽略错误）
-        if let Err(e) = clear_binary_override("claude") {
-            log::warn!("Failed to clear binaries.json override: {}", e);
-        }
+    settings_store::delete_setting(&app, SettingKey::ClaudeBinaryPath)
+        .map_err(|e| format!("Failed to clear custom Claude path: {}", e))?;
 
-        log::info!("Successfully cleared custom Claude CLI path");
-        return Ok(());
+    // 清理 binaries.json 覆盖记录（忽略错误）
+    if let Err(e) = clear_binary_override("claude") {
+        log::warn!("Failed to clear binaries.json override: {}", e);
     }
 
-    Err("Failed to get app data directory".to_string())
+    log::info!("Successfully cleared custom Claude CLI path");
+    Ok(())
 }
 
 fn expand_user_path(input: &str) -> Result<PathBuf, String> {
@@ -689,74 +653,6 @@ fn expand_user_path(input: &str) -> Result<PathBuf, String> {
     Ok(path)
 }
 
-fn update_binary_override(tool: &str, override_path: &str) -> Result<(), String> {
-    let home = dirs::home_dir().ok_or("Cannot find home directory".to_string())?;
-    let config_path = home.join(".claude").join("binaries.json");
-
-    // Ensure parent dir exists
-    if let Some(parent) = config_path.parent() {
-        std::fs::create_dir_all(parent)
-            .map_err(|e| format!("Failed to create config directory: {}", e))?;
-    }
-
-    let mut json: serde_json::Value = if config_path.exists() {
-        let content = std::fs::read_to_string(&config_path)
-            .map_err(|e| format!("Failed to read binaries.json: {}", e))?;
-        serde_json::from_str(&content).unwrap_or_else(|_| serde_json::json!({}))
-    } else {
-        serde_json::json!({})
-    };
-
-    let section = json
-        .as_object_mut()
-        .ok_or("Invalid binaries.json format (not an object)".to_string())?;
-
-    let entry = section
-        .entry(tool.to_string())
-        .or_insert_with(|| serde_json::json!({}));
-
-    if let Some(obj) = entry.as_object_mut() {
-        obj.insert(
-            "override_path".to_string(),
-            serde_json::Value::String(override_path.to_string()),
-        );
-    }
-
-    let serialized = serde_json::to_string_pretty(&json)
-        .map_err(|e| format!("Failed to serialize binaries.json: {}", e))?;
-    std::fs::write(&config_path, serialized)
-        .map_err(|e| format!("Failed to write binaries.json: {}", e))?;
-
-    Ok(())
-}
-
-fn clear_binary_override(tool: &str) -> Result<(), String> {
-    let home = dirs::home_dir().ok_or("Cannot find home directory".to_string())?;
-    let config_path = home.join(".claude").join("binaries.json");
-    if !config_path.exists() {
-        return Ok(());
-    }
-
-    let content = std::fs::read_to_string(&config_path)
-        .map_err(|e| format!("Failed to read binaries.json: {}", e))?;
-    let mut json: serde_json::Value =
-        serde_json::from_str(&content).unwrap_or_else(|_| serde_json::json!({}));
-
-    if let Some(section) = json.as_object_mut() {
-        if let Some(entry) = section.get_mut(tool) {
-            if let Some(obj) = entry.as_object_mut() {
-                obj.remove("override_path");
-            }
-        }
-    }
-
-    let serialized = serde_json::to_string_pretty(&json)
-        .map_err(|e| format!("Failed to serialize binaries.json: {}", e))?;
-    std::fs::write(&config_path, serialized)
-        .map_err(|e| format!("Failed to write binaries.json: {}", e))?;
-
-    Ok(())
-}
 /// 获取当前Claude执行配置
 #[tauri::command]
 pub async fn get_claude_execution_config(_app: AppHandle) -> Result<ClaudeExecutionConfig, String> {
@@ -917,6 +813,40 @@ pub async fn validate_permission_config(
     Ok(validation_result)
 }
 
+/// 校验权限配置并附带当前安装的 Claude CLI 版本（claudeVersion，检测失败时为 null）
+/// 在 validate_permission_config 的基础上，对不在 KNOWN_TOOLS 中的工具名给出警告；
+/// 工具的引入/废弃版本没有可靠来源，因此该检查与版本无关
+#[tauri::command]
+pub async fn validate_permission_config_for_version(
+    app: AppHandle,
+    config: ClaudePermissionConfig,
+) -> Result<serde_json::Value, String> {
+    let mut validation_result = validate_permission_config(config.clone()).await?;
+
+    let warning_list = validation_result["warnings"].as_array_mut().unwrap();
+    for tool in config.allowed_tools.iter().chain(config.disallowed_tools.iter()) {
+        // 规则形式如 "Bash(git:*)" 只校验工具名；MCP 工具由服务器提供，不做校验
+        let name = tool.split('(').next().unwrap_or(tool).trim();
+        if name.starts_with("mcp__") {
+            continue;
+        }
+        if !KNOWN_TOOLS.contains(&name) {
+            warning_list.push(serde_json::json!(format!(
+                "未知工具: {}（不在已知的 Claude CLI 内置工具列表中）",
+                name
+            )));
+        }
+    }
+
+    let version_status = check_claude_version(app).await?;
+    validation_result["claudeVersion"] = match version_status.version {
+        Some(version) => serde_json::json!(version),
+        None => serde_json::Value::Null,
+    };
+
+    Ok(validation_result)
+}
+
 /// Reads the AGENTS.md system prompt file from Codex directory
 #[tauri::command]
 pub async fn get_codex_system_prompt() -> Result<String, String> {
@@ -0,0 +1,186 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use super::paths::get_claude_dir;
+
+/// One settings file taking part in the merge, lowest precedence first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsSource {
+    /// "user", "project", "local" or "managed"
+    pub scope: String,
+    pub path: String,
+    pub exists: bool,
+    /// Set when the file exists but could not be read or parsed; it is then skipped
+    pub error: Option<String>,
+}
+
+/// Settings as Claude resolves them for a project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EffectiveClaudeSettings {
+    pub settings: Value,
+    /// Dotted key path (e.g. "env.ANTHROPIC_MODEL") -> scopes that supplied the value.
+    /// Scalars have one scope; merged arrays list every contributing scope.
+    pub provenance: BTreeMap<String, Vec<String>>,
+    pub sources: Vec<SettingsSource>,
+}
+
+/// Resolves the effective Claude settings for a project
+/// Files are merged like the CLI does: user < project < local < managed policy.
+/// Objects merge key by key, arrays (e.g. permission rules) are concatenated
+/// without duplicates, and any other value is replaced by the higher scope.
+#[tauri::command]
+pub async fn get_effective_claude_settings(
+    project_path: Option<String>,
+) -> Result<EffectiveClaudeSettings, String> {
+    log::info!("Resolving effective Claude settings (project: {:?})", project_path);
+
+    let mut files = vec![(
+        "user",
+        get_claude_dir().map_err(|e| e.to_string())?.join("settings.json"),
+    )];
+    if let Some(ref project) = project_path {
+        let project_claude_dir = PathBuf::from(project).join(".claude");
+        files.push(("project", project_claude_dir.join("settings.json")));
+        files.push(("local", project_claude_dir.join("settings.local.json")));
+    }
+    if let Some(path) = managed_settings_path() {
+        files.push(("managed", path));
+    }
+
+    let mut settings = Value::Object(Map::new());
+    let mut provenance = BTreeMap::new();
+    let mut sources = Vec::new();
+
+    for (scope, path) in files {
+        let mut source = SettingsSource {
+            scope: scope.to_string(),
+            path: path.to_string_lossy().to_string(),
+            exists: path.exists(),
+            error: None,
+        };
+        if source.exists {
+            let parsed = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read settings: {}", e))
+                .and_then(|content| {
+                    serde_json::from_str::<Value>(&content)
+                        .map_err(|e| format!("Failed to parse settings: {}", e))
+                });
+            match parsed {
+                Ok(layer) if layer.is_object() => {
+                    merge_settings(&mut settings, &layer, scope, "", &mut provenance)
+                }
+                Ok(_) => source.error = Some("Settings file is not a JSON object".to_string()),
+                Err(e) => {
+                    log::warn!("Skipping {} settings {:?}: {}", scope, path, e);
+                    source.error = Some(e);
+                }
+            }
+        }
+        sources.push(source);
+    }
+
+    Ok(EffectiveClaudeSettings {
+        settings,
+        provenance,
+        sources,
+    })
+}
+
+/// Enterprise policy file, which overrides every other scope
+fn managed_settings_path() -> Option<PathBuf> {
+    #[cfg(target_os = "macos")]
+    let path = PathBuf::from("/Library/Application Support/ClaudeCode/managed-settings.json");
+    #[cfg(target_os = "windows")]
+    let path = PathBuf::from(r"C:\ProgramData\ClaudeCode\managed-settings.json");
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    let path = PathBuf::from("/etc/claude-code/managed-settings.json");
+
+    path.exists().then_some(path)
+}
+
+/// Merges `layer` into `base`, recording which scope supplied each leaf value
+fn merge_settings(
+    base: &mut Value,
+    layer: &Value,
+    scope: &str,
+    prefix: &str,
+    provenance: &mut BTreeMap<String, Vec<String>>,
+) {
+    let (base_map, layer_map) = match (base.as_object_mut(), layer.as_object()) {
+        (Some(base_map), Some(layer_map)) => (base_map, layer_map),
+        _ => return,
+    };
+
+    for (key, value) in layer_map {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        match (base_map.get_mut(key), value) {
+            (Some(existing @ Value::Object(_)), Value::Object(_)) => {
+                merge_settings(existing, value, scope, &path, provenance);
+            }
+            (Some(Value::Array(existing)), Value::Array(items)) => {
+                for item in items {
+                    if !existing.contains(item) {
+                        existing.push(item.clone());
+                    }
+                }
+                let scopes = provenance.entry(path).or_default();
+                if !scopes.iter().any(|s| s == scope) {
+                    scopes.push(scope.to_string());
+                }
+            }
+            _ => {
+                // Replacing a whole subtree drops the provenance of its old leaves
+                let nested = format!("{}.", path);
+                provenance.retain(|k, _| !k.starts_with(&nested));
+                if value.is_object() {
+                    let entry = base_map.entry(key.clone()).or_insert(Value::Null);
+                    *entry = Value::Object(Map::new());
+                    merge_settings(entry, value, scope, &path, provenance);
+                } else {
+                    base_map.insert(key.clone(), value.clone());
+                    provenance.insert(path, vec![scope.to_string()]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn merges_layers_with_provenance() {
+        let mut settings = json!({});
+        let mut provenance = BTreeMap::new();
+        let user = json!({
+            "model": "sonnet",
+            "env": { "A": "1", "B": "2" },
+            "permissions": { "allow": ["Bash(ls)"] }
+        });
+        let project = json!({
+            "env": { "B": "3" },
+            "permissions": { "allow": ["Bash(ls)", "Read"] }
+        });
+        merge_settings(&mut settings, &user, "user", "", &mut provenance);
+        merge_settings(&mut settings, &project, "project", "", &mut provenance);
+
+        assert_eq!(settings["model"], "sonnet");
+        assert_eq!(settings["env"], json!({ "A": "1", "B": "3" }));
+        assert_eq!(settings["permissions"]["allow"], json!(["Bash(ls)", "Read"]));
+        assert_eq!(provenance["env.A"], vec!["user"]);
+        assert_eq!(provenance["env.B"], vec!["project"]);
+        assert_eq!(provenance["permissions.allow"], vec!["user", "project"]);
+    }
+}
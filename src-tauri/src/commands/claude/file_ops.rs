@@ -14,12 +14,18 @@ use super::models::FileEntry;
 ///
 /// # Arguments
 /// * `directory_path` - The directory path to list
+/// * `with_child_counts` - Also count the direct children of each subdirectory
+///   (one extra `read_dir` per directory)
 ///
 /// # Returns
 /// * `Ok(Vec<FileEntry>)` - List of directory entries
 /// * `Err(String)` - Error description if the operation fails
 #[tauri::command]
-pub async fn list_directory_contents(directory_path: String) -> Result<Vec<FileEntry>, String> {
+pub async fn list_directory_contents(
+    directory_path: String,
+    with_child_counts: Option<bool>,
+) -> Result<Vec<FileEntry>, String> {
+    let with_child_counts = with_child_counts.unwrap_or(false);
     log::info!("Listing directory contents: '{}'", directory_path);
 
     // Check if path is empty
@@ -75,12 +81,20 @@ pub async fn list_directory_contents(directory_path: String) -> Result<Vec<FileE
             None
         };
 
+        let child_count = if with_child_counts && metadata.is_dir() {
+            // Unreadable directories (permissions) simply get no count
+            fs::read_dir(&entry_path).ok().map(|children| children.count())
+        } else {
+            None
+        };
+
         entries.push(FileEntry {
             name,
             path: entry_path.to_string_lossy().to_string(),
             is_directory: metadata.is_dir(),
             size: metadata.len(),
             extension,
+            child_count,
         });
     }
 
@@ -107,8 +121,13 @@ pub async fn list_directory_contents(directory_path: String) -> Result<Vec<FileE
 /// * `Ok(Vec<FileEntry>)` - List of matching entries
 /// * `Err(String)` - Error description if the operation fails
 #[tauri::command]
-pub async fn search_files(base_path: String, query: String) -> Result<Vec<FileEntry>, String> {
-    log::info!("Searching files in '{}' for: '{}'", base_path, query);
+pub async fn search_files(
+    base_path: String,
+    query: String,
+    fuzzy: Option<bool>,
+) -> Result<Vec<FileEntry>, String> {
+    let fuzzy = fuzzy.unwrap_or(false);
+    log::info!("Searching files in '{}' for: '{}' (fuzzy: {})", base_path, query, fuzzy);
 
     // Check if path is empty
     if base_path.trim().is_empty() {
@@ -133,17 +152,24 @@ pub async fn search_files(base_path: String, query: String) -> Result<Vec<FileEn
     let query_lower = query.to_lowercase();
     let mut results = Vec::new();
 
-    search_files_recursive(&path, &path, &query_lower, &mut results, 0)?;
+    // Fuzzy mode collects a wider candidate pool, since the best matches are only known after scoring
+    let limit = if fuzzy { FUZZY_CANDIDATE_LIMIT } else { 50 };
+    search_files_recursive(&path, &path, &query_lower, fuzzy, limit, &mut results, 0)?;
 
-    // Sort by relevance: exact matches first, then by name
+    // Sort by relevance: exact matches first, then by fuzzy score (if enabled), then by name
     results.sort_by(|a, b| {
-        let a_exact = a.name.to_lowercase() == query_lower;
-        let b_exact = b.name.to_lowercase() == query_lower;
+        let a_name = a.name.to_lowercase();
+        let b_name = b.name.to_lowercase();
+        let a_exact = a_name == query_lower;
+        let b_exact = b_name == query_lower;
 
         match (a_exact, b_exact) {
             (true, false) => std::cmp::Ordering::Less,
             (false, true) => std::cmp::Ordering::Greater,
-            _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            _ if fuzzy => fuzzy_score(&b.name, &query_lower)
+                .cmp(&fuzzy_score(&a.name, &query_lower))
+                .then_with(|| a_name.cmp(&b_name)),
+            _ => a_name.cmp(&b_name),
         }
     });
 
@@ -153,23 +179,73 @@ pub async fn search_files(base_path: String, query: String) -> Result<Vec<FileEn
     Ok(results)
 }
 
+/// Maximum number of fuzzy candidates collected before scoring
+const FUZZY_CANDIDATE_LIMIT: usize = 2000;
+
+/// Scores `name` against a lowercase `query` as an in-order subsequence (fzf-style)
+///
+/// Returns `None` if the query is not a subsequence of the name. Consecutive
+/// matches and matches at word boundaries (after `-`, `_`, `.`, ` `, `/` or at a
+/// camelCase hump) score higher; skipped characters cost a small penalty.
+pub fn fuzzy_score(name: &str, query: &str) -> Option<i64> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut score: i64 = 0;
+    let mut name_idx = 0;
+    let mut prev_match: Option<usize> = None;
+
+    for q in query.chars() {
+        let mut found = None;
+        while name_idx < chars.len() {
+            if chars[name_idx].to_lowercase().eq(q.to_lowercase()) {
+                found = Some(name_idx);
+                name_idx += 1;
+                break;
+            }
+            name_idx += 1;
+        }
+        let idx = found?;
+
+        score += 16;
+        match prev_match {
+            Some(prev) if prev + 1 == idx => score += 15,
+            Some(prev) => score -= (idx - prev - 1) as i64,
+            None => score -= idx as i64,
+        }
+
+        let at_boundary = idx == 0
+            || matches!(chars[idx - 1], '-' | '_' | '.' | ' ' | '/')
+            || (chars[idx].is_uppercase() && chars[idx - 1].is_lowercase());
+        if at_boundary {
+            score += 10;
+        }
+
+        prev_match = Some(idx);
+    }
+
+    Some(score)
+}
+
 /// Recursive helper function for searching files
 ///
 /// # Arguments
 /// * `current_path` - Current directory being searched
 /// * `base_path` - Base search directory (for reference)
 /// * `query` - Search query (lowercase)
+/// * `fuzzy` - Match by subsequence instead of substring
+/// * `limit` - Maximum number of results to collect
 /// * `results` - Mutable reference to results vector
 /// * `depth` - Current recursion depth
 fn search_files_recursive(
     current_path: &PathBuf,
     base_path: &PathBuf,
     query: &str,
+    fuzzy: bool,
+    limit: usize,
     results: &mut Vec<FileEntry>,
     depth: usize,
 ) -> Result<(), String> {
     // Limit recursion depth to prevent excessive searching
-    if depth > 5 || results.len() >= 50 {
+    if depth > 5 || results.len() >= limit {
         return Ok(());
     }
 
@@ -187,7 +263,12 @@ fn search_files_recursive(
             }
 
             // Check if name matches query
-            if name.to_lowercase().contains(query) {
+            let is_match = if fuzzy {
+                fuzzy_score(name, query).is_some()
+            } else {
+                name.to_lowercase().contains(query)
+            };
+            if is_match {
                 let metadata = entry
                     .metadata()
                     .map_err(|e| format!("Failed to read metadata: {}", e))?;
@@ -207,6 +288,7 @@ fn search_files_recursive(
                     is_directory: metadata.is_dir(),
                     size: metadata.len(),
                     extension,
+                    child_count: None,
                 });
             }
         }
@@ -215,35 +297,64 @@ fn search_files_recursive(
         if entry_path.is_dir() {
             // Skip common directories that shouldn't be searched
             if let Some(dir_name) = entry_path.file_name().and_then(|n| n.to_str()) {
-                if matches!(
-                    dir_name,
-                    "node_modules" | "target" | ".git" | "dist" | "build" | ".next" | "__pycache__"
-                ) {
+                if is_skipped_search_dir(dir_name) {
                     continue;
                 }
             }
 
-            search_files_recursive(&entry_path, base_path, query, results, depth + 1)?;
+            search_files_recursive(&entry_path, base_path, query, fuzzy, limit, results, depth + 1)?;
         }
     }
 
     Ok(())
 }
 
+/// Dependency, build output and VCS directories left out of every project walk
+/// (file search, content search, stack detection, fingerprints, file ranking)
+pub(crate) fn is_skipped_search_dir(dir_name: &str) -> bool {
+    matches!(
+        dir_name,
+        "node_modules"
+            | "target"
+            | ".git"
+            | "dist"
+            | "build"
+            | ".next"
+            | "__pycache__"
+            | "vendor"
+            | ".venv"
+            | "venv"
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[tokio::test]
     async fn test_list_directory_invalid_path() {
-        let result = list_directory_contents("".to_string()).await;
+        let result = list_directory_contents("".to_string(), None).await;
         assert!(result.is_err());
     }
 
     #[tokio::test]
     async fn test_search_files_empty_query() {
-        let result = search_files("/tmp".to_string(), "".to_string()).await;
+        let result = search_files("/tmp".to_string(), "".to_string(), None).await;
         assert!(result.is_ok());
         assert_eq!(result.unwrap().len(), 0);
     }
+
+    #[test]
+    fn test_fuzzy_score_ranks_contiguous_and_boundary_matches() {
+        assert!(fuzzy_score("session_export.rs", "sxp").is_some());
+        assert!(fuzzy_score("session.rs", "sxp").is_none());
+
+        let contiguous = fuzzy_score("project_store.rs", "store").unwrap();
+        let scattered = fuzzy_score("session_tracker_ore.rs", "store").unwrap();
+        assert!(contiguous > scattered);
+
+        let boundary = fuzzy_score("ProjectStore.rs", "ps").unwrap();
+        let inner = fuzzy_score("lapsed.rs", "ps").unwrap();
+        assert!(boundary > inner);
+    }
 }
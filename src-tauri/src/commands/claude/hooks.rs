@@ -1,11 +1,31 @@
+use serde::{Deserialize, Serialize};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 
 
 use super::paths::get_claude_dir;
 use super::platform;
 
+/// Shell builtins and keywords that never resolve to a file on disk
+const SHELL_BUILTINS: &[&str] = &[
+    "cd", "echo", "exit", "export", "true", "false", "test", "[", "[[", "printf", "read",
+    "set", "source", ".", "eval", "exec", "if", "for", "while", "case", "command", ":",
+];
+
+/// Validation result for a single configured hook command
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HookValidation {
+    /// "user", "project" or "local"
+    pub scope: String,
+    pub event: String,
+    pub matcher: Option<String>,
+    pub command: String,
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
 #[tauri::command]
 pub async fn get_hooks_config(scope: String, project_path: Option<String>) -> Result<serde_json::Value, String> {
     log::info!("Getting hooks config for scope: {}, project: {:?}", scope, project_path);
@@ -108,6 +128,216 @@ pub async fn update_hooks_config(
 pub async fn validate_hook_command(command: String) -> Result<serde_json::Value, String> {
     log::info!("Validating hook command syntax");
 
+    match check_hook_syntax(&command)? {
+        None => Ok(serde_json::json!({
+            "valid": true,
+            "message": "Command syntax is valid"
+        })),
+        Some(error) => Ok(serde_json::json!({
+            "valid": false,
+            "message": format!("Syntax error: {}", error)
+        })),
+    }
+}
+
+/// Checks every hook command configured in the user, project and local settings
+/// Each command is syntax-checked and its program is resolved on disk or in PATH,
+/// so hooks pointing at deleted scripts are reported instead of failing silently.
+#[tauri::command]
+pub async fn validate_all_hooks(project_path: Option<String>) -> Result<Vec<HookValidation>, String> {
+    log::info!("Validating all configured hooks (project: {:?})", project_path);
+
+    let mut sources = vec![(
+        "user",
+        get_claude_dir().map_err(|e| e.to_string())?.join("settings.json"),
+    )];
+    if let Some(ref project) = project_path {
+        let project_claude_dir = PathBuf::from(project).join(".claude");
+        sources.push(("project", project_claude_dir.join("settings.json")));
+        sources.push(("local", project_claude_dir.join("settings.local.json")));
+    }
+
+    let mut results = Vec::new();
+    for (scope, settings_path) in sources {
+        if !settings_path.exists() {
+            continue;
+        }
+        let content = fs::read_to_string(&settings_path)
+            .map_err(|e| format!("Failed to read settings: {}", e))?;
+        let settings: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse settings: {}", e))?;
+        let events = match settings.get("hooks").and_then(|h| h.as_object()) {
+            Some(events) => events,
+            None => continue,
+        };
+
+        for (event, matchers) in events {
+            for matcher in matchers.as_array().into_iter().flatten() {
+                let matcher_name = matcher["matcher"].as_str().map(|m| m.to_string());
+                for hook in matcher["hooks"].as_array().into_iter().flatten() {
+                    let command = match hook["command"].as_str() {
+                        Some(command) => command.to_string(),
+                        None => continue,
+                    };
+                    let error = check_hook_command(&command, project_path.as_deref());
+                    results.push(HookValidation {
+                        scope: scope.to_string(),
+                        event: event.clone(),
+                        matcher: matcher_name.clone(),
+                        command,
+                        valid: error.is_none(),
+                        error,
+                    });
+                }
+            }
+        }
+    }
+
+    let broken = results.iter().filter(|r| !r.valid).count();
+    if broken > 0 {
+        log::warn!("{} of {} hook commands are broken", broken, results.len());
+    }
+    Ok(results)
+}
+
+/// Returns a description of the problem, or None when the command looks runnable
+fn check_hook_command(command: &str, project_path: Option<&str>) -> Option<String> {
+    match check_hook_syntax(command) {
+        Ok(Some(error)) => return Some(format!("Syntax error: {}", error)),
+        Ok(None) => {}
+        // Without bash the syntax cannot be checked; still resolve the program
+        Err(e) => log::debug!("Skipping hook syntax check: {}", e),
+    }
+
+    let words = hook_words(command);
+    let program = words.first()?;
+    if !SHELL_BUILTINS.contains(&program.as_str()) {
+        if is_path_like(program) {
+            // Paths that can't be fully resolved are not checked
+            if let Some(path) = resolve_hook_path(program, project_path) {
+                if !path.exists() {
+                    return Some(format!("Script not found: {}", path.display()));
+                }
+                #[cfg(unix)]
+                if !is_executable(&path) {
+                    return Some(format!("Script is not executable: {}", path.display()));
+                }
+            }
+        } else if !program.contains('$') && !find_in_path(program) {
+            return Some(format!("Command not found in PATH: {}", program));
+        }
+    }
+
+    // Scripts handed to an interpreter, e.g. `python3 ~/.claude/hooks/check.py`
+    for word in words.iter().skip(1).filter(|word| is_script_path(word)) {
+        if let Some(path) = resolve_hook_path(word, project_path) {
+            if !path.exists() {
+                return Some(format!("Script not found: {}", path.display()));
+            }
+        }
+    }
+    None
+}
+
+/// Words of the first command in a command line, skipping leading `NAME=value` assignments
+/// Quoting follows the shell, so `"$CLAUDE_PROJECT_DIR"/.claude/hooks/x.sh` is one word.
+fn hook_words(command: &str) -> Vec<String> {
+    const OPERATORS: &[&str] = &[";", "|", "||", "&", "&&"];
+
+    let mut words = Vec::new();
+    for word in shlex::split(command).unwrap_or_default() {
+        if OPERATORS.contains(&word.as_str()) {
+            break;
+        }
+        // `a.sh; b` splits into "a.sh;" and "b"
+        if let Some(last) = word.strip_suffix([';', '&', '|']) {
+            words.push(last.to_string());
+            break;
+        }
+        words.push(word);
+    }
+
+    let assignments = words
+        .iter()
+        .take_while(|word| {
+            word.split_once('=')
+                .map(|(name, _)| {
+                    !name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+                })
+                .unwrap_or(false)
+        })
+        .count();
+    words.split_off(assignments)
+}
+
+fn is_path_like(word: &str) -> bool {
+    word.contains('/') || word.contains('\\')
+}
+
+/// A path argument with a script extension
+fn is_script_path(word: &str) -> bool {
+    const SCRIPT_EXTENSIONS: &[&str] = &["sh", "bash", "py", "js", "mjs", "cjs", "ts", "rb", "pl", "ps1"];
+    is_path_like(word)
+        && Path::new(word)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| SCRIPT_EXTENSIONS.contains(&ext))
+            .unwrap_or(false)
+}
+
+/// Resolves a hook path the way Claude Code runs it: `~` and `$CLAUDE_PROJECT_DIR`
+/// are expanded and relative paths resolve against the project, the hook's working
+/// directory. None when a variable or the project directory is unknown.
+fn resolve_hook_path(word: &str, project_path: Option<&str>) -> Option<PathBuf> {
+    let mut expanded = word.to_string();
+    if let Some(project) = project_path {
+        expanded = expanded
+            .replace("${CLAUDE_PROJECT_DIR}", project)
+            .replace("$CLAUDE_PROJECT_DIR", project);
+    }
+    if expanded.contains('$') {
+        return None;
+    }
+
+    let path = match expanded.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir()?.join(rest),
+        None => PathBuf::from(expanded),
+    };
+    if path.is_absolute() {
+        Some(path)
+    } else {
+        project_path.map(|project| Path::new(project).join(path))
+    }
+}
+
+/// Whether `program` is found in one of the PATH directories
+pub(crate) fn find_in_path(program: &str) -> bool {
+    let path_var = match std::env::var_os("PATH") {
+        Some(path_var) => path_var,
+        None => return false,
+    };
+    #[cfg(target_os = "windows")]
+    let extensions = ["", ".exe", ".cmd", ".bat", ".ps1"];
+    #[cfg(not(target_os = "windows"))]
+    let extensions = [""];
+
+    std::env::split_paths(&path_var).any(|dir| {
+        extensions
+            .iter()
+            .any(|ext| dir.join(format!("{}{}", program, ext)).is_file())
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    fs::metadata(path)
+        .map(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+        .unwrap_or(false)
+}
+
+/// Runs `bash -n` on a command; returns the syntax error, if any
+fn check_hook_syntax(command: &str) -> Result<Option<String>, String> {
     // Validate syntax without executing
     let mut cmd = std::process::Command::new("bash");
     cmd.arg("-n") // Syntax check only
@@ -120,16 +350,9 @@ pub async fn validate_hook_command(command: String) -> Result<serde_json::Value,
     match cmd.output() {
         Ok(output) => {
             if output.status.success() {
-                Ok(serde_json::json!({
-                    "valid": true,
-                    "message": "Command syntax is valid"
-                }))
+                Ok(None)
             } else {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                Ok(serde_json::json!({
-                    "valid": false,
-                    "message": format!("Syntax error: {}", stderr)
-                }))
+                Ok(Some(String::from_utf8_lossy(&output.stderr).trim().to_string()))
             }
         }
         Err(e) => Err(format!("Failed to validate command: {}", e))
@@ -1,5 +1,6 @@
 mod cli_runner;
 mod config;
+mod effective_settings;
 mod hooks;
 mod models;
 mod paths;
@@ -11,7 +12,7 @@ mod file_ops;
 pub use models::*;
 pub use paths::*;
 // Export platform utilities for process window hiding
-pub use platform::apply_no_window_async;
+pub use platform::{apply_no_window_async, kill_process_tree};
 pub use self::cli_runner::{
     cancel_claude_execution,
     continue_claude_code,
@@ -33,6 +34,7 @@ pub use self::config::{
     get_codex_system_prompt,
     get_permission_presets,
     get_system_prompt,
+    list_claude_binary_candidates,
     open_new_session,
     read_claude_md_file,
     reset_claude_execution_config,
@@ -45,14 +47,25 @@ pub use self::config::{
     update_claude_permission_config,
     update_thinking_mode,
     validate_permission_config,
+    validate_permission_config_for_version,
+};
+pub use self::effective_settings::{
+    get_effective_claude_settings,
+    EffectiveClaudeSettings,
+    SettingsSource,
 };
 pub use self::hooks::{
     get_hooks_config,
     update_hooks_config,
+    validate_all_hooks,
     validate_hook_command,
+    HookValidation,
 };
-use self::project_store::ProjectStore;
+pub(crate) use self::hooks::find_in_path;
+use self::project_store::{ProjectStore, RenameOutcome};
 pub use file_ops::{list_directory_contents, search_files};
+pub(crate) use file_ops::is_skipped_search_dir;
+pub use session_history::extract_first_user_message;
 // Agent functionality removed
 
  
@@ -166,6 +179,60 @@ pub async fn delete_project_permanently(project_id: String) -> Result<String, St
     Ok(result_msg)
 }
 
+/// Registers an arbitrary directory as a project and picks up any sessions already recorded for it
+#[tauri::command]
+pub async fn add_project(path: String) -> Result<Project, String> {
+    let store = ProjectStore::new()?;
+    let mut project = store.add_project(&path)?;
+
+    let check = crate::commands::path_length::check_path_length(project.path.clone()).await?;
+    if let Some(guidance) = check.guidance {
+        log::warn!("Project path {} may hit MAX_PATH: {}", project.path, guidance);
+        project.path_warning = Some(guidance);
+    }
+    Ok(project)
+}
+
+/// Moves a project's directory on disk and relinks its sessions to the new path
+#[tauri::command]
+pub async fn rename_project_directory(
+    project_id: String,
+    new_path: String,
+) -> Result<Project, String> {
+    let store = ProjectStore::new()?;
+    let RenameOutcome { project, old_path } =
+        store.rename_project_directory(&project_id, &new_path)?;
+
+    // Codex session files are owned by the CLI; only the app-side rewind records are relinked
+    match crate::commands::codex::relink_codex_git_records(&old_path, &project.path) {
+        Ok(count) if count > 0 => log::info!("Relinked {} Codex git record file(s)", count),
+        Ok(_) => {}
+        Err(e) => log::warn!("Failed to relink Codex git records: {}", e),
+    }
+
+    Ok(project)
+}
+
+/// Moves a session into another project, e.g. one started in the wrong directory
+#[tauri::command]
+pub async fn move_session(
+    session_id: String,
+    from_project_id: String,
+    to_project_id: String,
+) -> Result<MovedSession, String> {
+    let store = ProjectStore::new()?;
+    store.move_session(&session_id, &from_project_id, &to_project_id)
+}
+
+/// Returns the canonical form of a project path, as used for all path comparisons
+#[tauri::command]
+pub async fn canonicalize_project_path(path: String) -> Result<String, String> {
+    if path.trim().is_empty() {
+        return Err("Path cannot be empty".to_string());
+    }
+    Ok(canonical_project_path(&path))
+}
+
 /// Lists all hidden projects with intelligent directory existence check
 #[tauri::command]
 pub async fn list_hidden_projects() -> Result<Vec<String>, String> {
@@ -12,6 +12,9 @@ pub struct Project {
     pub sessions: Vec<String>,
     /// Unix timestamp of the latest activity (session modification or project creation)
     pub created_at: u64,
+    /// Set by `add_project` when the path is too long for Windows without long-path support
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path_warning: Option<String>,
 }
 
 /// Represents a session with its metadata
@@ -37,6 +40,19 @@ pub struct Session {
     pub model: Option<String>,
 }
 
+/// The new location of a session moved to another project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MovedSession {
+    /// The session ID (unchanged by the move)
+    pub session_id: String,
+    /// The project ID the session now belongs to
+    pub project_id: String,
+    /// The path of the target project
+    pub project_path: String,
+    /// Full path of the moved JSONL file
+    pub session_file: String,
+}
+
 /// Represents a message entry in the JSONL file
 #[derive(Debug, Deserialize)]
 pub struct JsonlEntry {
@@ -106,4 +122,6 @@ pub struct FileEntry {
     pub size: u64,
     /// File extension (if applicable)
     pub extension: Option<String>,
+    /// Number of direct children (directories only, when requested)
+    pub child_count: Option<usize>,
 }
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Component, Path, PathBuf};
 
 /// Gets the path to the ~/.claude directory
 pub fn get_claude_dir() -> Result<PathBuf> {
@@ -116,3 +117,135 @@ pub fn normalize_path_for_comparison(path: &str) -> String {
 
     normalized
 }
+
+/// Resolves a project path into the one canonical form used across the app
+/// Expands `~`, resolves relative paths against the current directory, maps WSL
+/// `/mnt/<drive>/` paths to Windows paths on Windows, resolves `.`/`..` (and
+/// symlinks when the path exists), and strips `\\?\` prefixes and trailing separators.
+pub fn canonical_project_path(path: &str) -> String {
+    let trimmed = path.trim().trim_matches('"');
+    if trimmed.is_empty() {
+        return String::new();
+    }
+
+    let expanded = match trimmed.strip_prefix('~') {
+        Some(rest) if rest.is_empty() || rest.starts_with('/') || rest.starts_with('\\') => {
+            match dirs::home_dir() {
+                Some(home) => format!("{}{}", home.to_string_lossy(), rest),
+                None => trimmed.to_string(),
+            }
+        }
+        _ => trimmed.to_string(),
+    };
+
+    #[cfg(target_os = "windows")]
+    let expanded = if expanded.starts_with("/mnt/") {
+        crate::commands::wsl_utils::wsl_to_windows_path(&expanded)
+    } else {
+        expanded
+    };
+
+    let absolute = if Path::new(&expanded).is_absolute() || has_drive_prefix(&expanded) {
+        PathBuf::from(&expanded)
+    } else {
+        std::env::current_dir()
+            .map(|dir| dir.join(&expanded))
+            .unwrap_or_else(|_| PathBuf::from(&expanded))
+    };
+    let resolved = absolute
+        .canonicalize()
+        .unwrap_or_else(|_| normalize_components(&absolute));
+
+    let mut result = resolved.to_string_lossy().to_string();
+    if let Some(rest) = result.strip_prefix("\\\\?\\UNC\\") {
+        result = format!("\\\\{}", rest);
+    } else if let Some(rest) = result.strip_prefix("\\\\?\\") {
+        result = rest.to_string();
+    }
+
+    // Keep the separator of a root ("/" or "C:\\")
+    while result.len() > 1 && (result.ends_with('/') || result.ends_with('\\')) {
+        if result.len() == 3 && has_drive_prefix(&result) {
+            break;
+        }
+        result.pop();
+    }
+    result
+}
+
+/// Key under which two project paths compare equal when they refer to the same directory
+pub fn project_path_key(path: &str) -> String {
+    normalize_path_for_comparison(&canonical_project_path(path))
+}
+
+/// Tests paths against one project, canonicalizing the project and each distinct
+/// path only once; session listings repeat the same cwd many times
+pub struct ProjectPathMatcher {
+    key: String,
+    seen: HashMap<String, bool>,
+}
+
+impl ProjectPathMatcher {
+    pub fn new(project_path: &str) -> Self {
+        Self {
+            key: project_path_key(project_path),
+            seen: HashMap::new(),
+        }
+    }
+
+    pub fn matches(&mut self, path: &str) -> bool {
+        if let Some(&matches) = self.seen.get(path) {
+            return matches;
+        }
+        let matches = project_path_key(path) == self.key;
+        self.seen.insert(path.to_string(), matches);
+        matches
+    }
+}
+
+/// "C:" style prefix, recognised on every platform since paths come from Windows sessions too
+fn has_drive_prefix(path: &str) -> bool {
+    let bytes = path.as_bytes();
+    bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+/// Resolves `.` and `..` without touching the filesystem
+fn normalize_components(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(normalized.components().next_back(), Some(Component::Normal(_))) {
+                    normalized.pop();
+                }
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn equivalent_project_paths_share_a_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        let key = project_path_key(&path);
+
+        assert_eq!(project_path_key(&format!("{}/", path)), key);
+        assert_eq!(project_path_key(&format!("{}/sub/..", path)), key);
+        assert_eq!(project_path_key(&format!("\"{}\"", path)), key);
+        assert_ne!(project_path_key(&format!("{}/sub", path)), key);
+
+        let mut matcher = ProjectPathMatcher::new(&path);
+        assert!(matcher.matches(&format!("{}/./", path)));
+        assert!(!matcher.matches("/some/other/project"));
+        // Answers are cached per distinct path
+        assert!(matcher.matches(&format!("{}/./", path)));
+        assert_eq!(matcher.seen.len(), 2);
+    }
+}
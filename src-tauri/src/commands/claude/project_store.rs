@@ -6,12 +6,18 @@ use std::time::SystemTime;
 
 use serde_json::Value;
 
-use super::models::{Project, Session};
-use super::paths::{decode_project_path, get_claude_dir, normalize_path_for_comparison};
+use super::models::{MovedSession, Project, Session};
+use super::paths::{
+    decode_project_path, encode_project_path, get_claude_dir, project_path_key,
+};
 use super::session_history::{
     extract_first_user_message, extract_last_message_timestamp, extract_session_model,
 };
 
+/// Marker written into app-registered project folders so the path survives
+/// before the CLI has recorded any session (directory name decoding is lossy)
+const PROJECT_PATH_MARKER: &str = ".workbench-project-path";
+
 pub struct ProjectStore {
     claude_dir: PathBuf,
 }
@@ -22,6 +28,11 @@ pub struct BatchDeleteOutcome {
     pub errors: Vec<String>,
 }
 
+pub struct RenameOutcome {
+    pub project: Project,
+    pub old_path: String,
+}
+
 impl ProjectStore {
     pub fn new() -> Result<Self, String> {
         let claude_dir = get_claude_dir().map_err(|e| e.to_string())?;
@@ -117,6 +128,7 @@ impl ProjectStore {
                         path: project_path,
                         sessions,
                         created_at: latest_activity,
+                        path_warning: None,
                     });
                 }
             }
@@ -325,7 +337,7 @@ impl ProjectStore {
             actual_project_dir = Some(project_dir);
         } else if let Ok(entries) = fs::read_dir(&projects_dir) {
             let target_normalized_path =
-                normalize_path_for_comparison(&decode_project_path(project_id));
+                project_path_key(&decode_project_path(project_id));
 
             for entry in entries.flatten() {
                 if entry.path().is_dir() {
@@ -336,7 +348,7 @@ impl ProjectStore {
                                 Err(_) => decode_project_path(dir_name),
                             };
 
-                        if normalize_path_for_comparison(&candidate_path)
+                        if project_path_key(&candidate_path)
                             == target_normalized_path
                         {
                             actual_project_dir = Some(entry.path());
@@ -375,6 +387,268 @@ impl ProjectStore {
         Ok(actual_project_id)
     }
 
+    pub fn add_project(&self, path: &str) -> Result<Project, String> {
+        log::info!("Adding project by path: {}", path);
+
+        let canonical = Path::new(path)
+            .canonicalize()
+            .map_err(|e| format!("Failed to resolve project path '{}': {}", path, e))?;
+        if !canonical.is_dir() {
+            return Err(format!("Project path is not a directory: {}", path));
+        }
+        let project_path = {
+            let path_str = canonical.to_string_lossy().to_string();
+            if path_str.starts_with("\\\\?\\") {
+                path_str[4..].to_string()
+            } else {
+                path_str
+            }
+        };
+        let normalized = project_path_key(&project_path);
+
+        // Reuse any folder the CLI already created for this path (its encoding may differ from ours)
+        let projects_dir = self.projects_dir();
+        let mut project_ids = Vec::new();
+        if let Ok(entries) = fs::read_dir(&projects_dir) {
+            for entry in entries.flatten() {
+                if !entry.path().is_dir() {
+                    continue;
+                }
+                if let Some(dir_name) = entry.file_name().to_str() {
+                    let candidate_path = get_project_path_from_sessions(&entry.path())
+                        .unwrap_or_else(|_| decode_project_path(dir_name));
+                    if project_path_key(&candidate_path) == normalized {
+                        project_ids.push(dir_name.to_string());
+                    }
+                }
+            }
+        }
+
+        let project_id = match project_ids.first() {
+            Some(id) => id.clone(),
+            None => {
+                let id = encode_project_path(&project_path);
+                let project_dir = projects_dir.join(&id);
+                fs::create_dir_all(&project_dir)
+                    .map_err(|e| format!("Failed to create project directory: {}", e))?;
+                fs::write(project_dir.join(PROJECT_PATH_MARKER), &project_path)
+                    .map_err(|e| format!("Failed to write project marker: {}", e))?;
+                project_ids.push(id.clone());
+                id
+            }
+        };
+
+        let id_refs: Vec<&str> = project_ids.iter().map(|id| id.as_str()).collect();
+        self.remove_from_hidden_projects(&id_refs)?;
+
+        let mut sessions = Vec::new();
+        let mut latest_activity = 0;
+        for id in &project_ids {
+            for session in self.get_project_sessions(id)? {
+                if session.first_message.is_some() && !sessions.contains(&session.id) {
+                    latest_activity = latest_activity.max(session.created_at);
+                    sessions.push(session.id);
+                }
+            }
+        }
+        if latest_activity == 0 {
+            latest_activity = SystemTime::now()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs();
+        }
+
+        log::info!(
+            "Registered project {} ({}) with {} existing session(s)",
+            project_id,
+            project_path,
+            sessions.len()
+        );
+        Ok(Project {
+            id: project_id,
+            path: project_path,
+            sessions,
+            created_at: latest_activity,
+            path_warning: None,
+        })
+    }
+
+    pub fn rename_project_directory(
+        &self,
+        project_id: &str,
+        new_path: &str,
+    ) -> Result<RenameOutcome, String> {
+        log::info!("Renaming project {} to {}", project_id, new_path);
+
+        let projects_dir = self.projects_dir();
+        let project_dir = projects_dir.join(project_id);
+        if !project_dir.is_dir() {
+            return Err(format!("Project directory not found: {}", project_id));
+        }
+
+        let old_path = get_project_path_from_sessions(&project_dir)
+            .unwrap_or_else(|_| decode_project_path(project_id));
+        let old_dir = Path::new(&old_path);
+        let new_dir = Path::new(new_path);
+
+        if !old_dir.is_dir() {
+            return Err(format!("Project directory does not exist on disk: {}", old_path));
+        }
+        if project_path_key(&old_path) == project_path_key(new_path) {
+            return Err("New path is the same as the current project path".to_string());
+        }
+        if new_dir.exists() {
+            return Err(format!("Target path already exists: {}", new_path));
+        }
+        if new_dir.starts_with(old_dir) {
+            return Err("Cannot move a project into its own subdirectory".to_string());
+        }
+        match new_dir.parent() {
+            Some(parent) if parent.is_dir() => {}
+            _ => return Err(format!("Parent directory of target path does not exist: {}", new_path)),
+        }
+
+        // Relinking rewrites session files, so capture the listing timestamp first
+        let created_at = project_activity_secs(&project_dir);
+
+        // Every completed step is journaled so a failure part-way leaves nothing half-moved
+        let new_id = encode_project_path(new_path);
+        let mut steps = Vec::new();
+        let result = fs::rename(old_dir, new_dir)
+            .map_err(|e| format!("Failed to move project directory: {}", e))
+            .and_then(|_| {
+                steps.push(RenameStep::Moved(old_dir.to_path_buf(), new_dir.to_path_buf()));
+                // Claude CLI looks sessions up by the encoded cwd, so the session folder follows the project
+                self.relink_project_sessions(project_id, &new_id, &old_path, new_path, &mut steps)
+            });
+        if let Err(e) = result {
+            log::error!("Failed to rename project, undoing {} step(s): {}", steps.len(), e);
+            self.undo_rename_steps(steps);
+            return Err(e);
+        }
+
+        let session_dir = projects_dir.join(&new_id);
+        let sessions = fs::read_dir(&session_dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("jsonl"))
+                    .filter_map(|path| path.file_stem().and_then(|s| s.to_str()).map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        log::info!("Renamed project {} -> {} ({})", project_id, new_id, new_path);
+        Ok(RenameOutcome {
+            project: Project {
+                id: new_id,
+                path: new_path.to_string(),
+                sessions,
+                created_at,
+                path_warning: None,
+            },
+            old_path,
+        })
+    }
+
+    /// Moves a session (transcript, side folder and git records) into another project
+    /// The recorded cwd is rewritten so resuming the session runs in the target project.
+    pub fn move_session(
+        &self,
+        session_id: &str,
+        from_project_id: &str,
+        to_project_id: &str,
+    ) -> Result<MovedSession, String> {
+        log::info!(
+            "Moving session {} from project {} to {}",
+            session_id,
+            from_project_id,
+            to_project_id
+        );
+
+        if from_project_id == to_project_id {
+            return Err("Source and target project are the same".to_string());
+        }
+
+        let from_dir = self.projects_dir().join(from_project_id);
+        let to_dir = self.projects_dir().join(to_project_id);
+        if !to_dir.is_dir() {
+            return Err(format!("Target project not found: {}", to_project_id));
+        }
+
+        let session_file_name = format!("{}.jsonl", session_id);
+        let source_file = from_dir.join(&session_file_name);
+        let target_file = to_dir.join(&session_file_name);
+        if !source_file.is_file() {
+            return Err(format!("Session file not found for ID: {}", session_id));
+        }
+        if target_file.exists() {
+            return Err(format!(
+                "Session {} already exists in project {}",
+                session_id, to_project_id
+            ));
+        }
+
+        let from_path = get_project_path_from_sessions(&from_dir)
+            .unwrap_or_else(|_| decode_project_path(from_project_id));
+        let to_path = get_project_path_from_sessions(&to_dir)
+            .unwrap_or_else(|_| decode_project_path(to_project_id));
+
+        // Rewrite cwd before moving so a failure leaves the session where it was
+        let old_field = format!("\"cwd\":{}", serde_json::Value::from(from_path.as_str()));
+        let new_field = format!("\"cwd\":{}", serde_json::Value::from(to_path.as_str()));
+        let content = fs::read_to_string(&source_file)
+            .map_err(|e| format!("Failed to read session file: {}", e))?;
+        fs::write(&target_file, content.replace(&old_field, &new_field))
+            .map_err(|e| format!("Failed to write session file: {}", e))?;
+        if let Err(e) = fs::remove_file(&source_file) {
+            let _ = fs::remove_file(&target_file);
+            return Err(format!("Failed to remove original session file: {}", e));
+        }
+
+        // Per-session side folder (subagent transcripts, tool results)
+        let side_dir = from_dir.join(session_id);
+        if side_dir.is_dir() {
+            if let Err(e) = move_dir_contents(&side_dir, &to_dir.join(session_id)) {
+                log::warn!("Failed to move session folder for {}: {}", session_id, e);
+            }
+        }
+
+        // Git records are keyed by project ID
+        let records_name = format!("{}.git-records.json", session_id);
+        let record_locations = [
+            (
+                from_dir.join("sessions").join(&records_name),
+                to_dir.join("sessions").join(&records_name),
+            ),
+            (
+                self.claude_dir.join("sessions").join(from_project_id).join(&records_name),
+                self.claude_dir.join("sessions").join(to_project_id).join(&records_name),
+            ),
+        ];
+        for (from_records, to_records) in &record_locations {
+            if !from_records.is_file() {
+                continue;
+            }
+            let moved = to_records
+                .parent()
+                .map(fs::create_dir_all)
+                .unwrap_or(Ok(()))
+                .and_then(|_| fs::rename(from_records, to_records));
+            if let Err(e) = moved {
+                log::warn!("Failed to move git records for {}: {}", session_id, e);
+            }
+        }
+
+        Ok(MovedSession {
+            session_id: session_id.to_string(),
+            project_id: to_project_id.to_string(),
+            project_path: to_path,
+            session_file: target_file.to_string_lossy().to_string(),
+        })
+    }
+
     pub fn list_hidden_projects(&self) -> Result<Vec<String>, String> {
         log::info!("Listing hidden projects with directory validation");
 
@@ -383,6 +657,8 @@ impl ProjectStore {
 
         let mut validated_hidden_projects = Vec::new();
         let mut projects_to_remove = Vec::new();
+        // (directory name, path key) of every project directory, computed on first use
+        let mut candidates: Option<Vec<(String, String)>> = None;
 
         for hidden_project_id in &hidden_projects {
             let project_dir = projects_dir.join(hidden_project_id);
@@ -392,37 +668,20 @@ impl ProjectStore {
                 log::debug!("Hidden project directory exists: {}", hidden_project_id);
             } else {
                 let normalized_path =
-                    normalize_path_for_comparison(&decode_project_path(hidden_project_id));
-                let mut found_match = false;
-
-                if let Ok(entries) = fs::read_dir(&projects_dir) {
-                    for entry in entries.flatten() {
-                        if entry.path().is_dir() {
-                            if let Some(dir_name) = entry.file_name().to_str() {
-                                let candidate_path =
-                                    match get_project_path_from_sessions(&entry.path()) {
-                                        Ok(path) => path,
-                                        Err(_) => decode_project_path(dir_name),
-                                    };
-
-                                if normalize_path_for_comparison(&candidate_path)
-                                    == normalized_path
-                                {
-                                    validated_hidden_projects.push(dir_name.to_string());
-                                    log::debug!(
-                                        "Found matching directory for hidden project {} -> {}",
-                                        hidden_project_id,
-                                        dir_name
-                                    );
-                                    found_match = true;
-                                    break;
-                                }
-                            }
-                        }
-                    }
-                }
-
-                if !found_match {
+                    project_path_key(&decode_project_path(hidden_project_id));
+                let candidates =
+                    candidates.get_or_insert_with(|| project_dir_keys(&projects_dir));
+
+                if let Some((dir_name, _)) =
+                    candidates.iter().find(|(_, key)| *key == normalized_path)
+                {
+                    validated_hidden_projects.push(dir_name.clone());
+                    log::debug!(
+                        "Found matching directory for hidden project {} -> {}",
+                        hidden_project_id,
+                        dir_name
+                    );
+                } else {
                     log::warn!(
                         "Hidden project directory not found (will remove from list): {}",
                         hidden_project_id
@@ -444,6 +703,87 @@ impl ProjectStore {
         Ok(validated_hidden_projects)
     }
 
+    /// Moves a project's session folder (and git records) to `new_id` and rewrites
+    /// the recorded cwd in its session files
+    /// Each completed change is pushed to `steps` so the caller can undo it.
+    fn relink_project_sessions(
+        &self,
+        old_id: &str,
+        new_id: &str,
+        old_path: &str,
+        new_path: &str,
+        steps: &mut Vec<RenameStep>,
+    ) -> Result<(), String> {
+        let old_dir = self.projects_dir().join(old_id);
+        let new_dir = self.projects_dir().join(new_id);
+
+        if old_id != new_id {
+            move_dir_contents_journaled(&old_dir, &new_dir, steps)
+                .map_err(|e| format!("Failed to move session folder: {}", e))?;
+
+            let records_dir = self.claude_dir.join("sessions");
+            if records_dir.join(old_id).is_dir() {
+                move_dir_contents_journaled(&records_dir.join(old_id), &records_dir.join(new_id), steps)
+                    .map_err(|e| format!("Failed to move git records folder: {}", e))?;
+            }
+
+            let mut hidden_projects = self.load_hidden_projects()?;
+            if let Some(pos) = hidden_projects.iter().position(|id| id == old_id) {
+                let previous = hidden_projects.clone();
+                hidden_projects[pos] = new_id.to_string();
+                self.save_hidden_projects(&hidden_projects)?;
+                steps.push(RenameStep::HiddenProjects(previous));
+            }
+        }
+
+        let marker = new_dir.join(PROJECT_PATH_MARKER);
+        if marker.exists() {
+            let previous = fs::read_to_string(&marker)
+                .map_err(|e| format!("Failed to read project marker: {}", e))?;
+            fs::write(&marker, new_path)
+                .map_err(|e| format!("Failed to update project marker: {}", e))?;
+            steps.push(RenameStep::Rewrote(marker, previous));
+        }
+
+        // Replace the serialized field in place so the rest of each line stays byte-identical
+        let old_field = format!("\"cwd\":{}", serde_json::Value::from(old_path));
+        let new_field = format!("\"cwd\":{}", serde_json::Value::from(new_path));
+        let entries = fs::read_dir(&new_dir)
+            .map_err(|e| format!("Failed to read session folder: {}", e))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("jsonl") {
+                continue;
+            }
+            let content = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read session file: {}", e))?;
+            if content.contains(&old_field) {
+                fs::write(&path, content.replace(&old_field, &new_field))
+                    .map_err(|e| format!("Failed to update session file: {}", e))?;
+                steps.push(RenameStep::Rewrote(path, content));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Reverts the steps of a failed rename, newest first
+    fn undo_rename_steps(&self, steps: Vec<RenameStep>) {
+        for step in steps.into_iter().rev() {
+            let undone = match &step {
+                RenameStep::Moved(from, to) => fs::rename(to, from).map_err(|e| e.to_string()),
+                RenameStep::RemovedDir(dir) => fs::create_dir_all(dir).map_err(|e| e.to_string()),
+                RenameStep::Rewrote(path, previous) => {
+                    fs::write(path, previous).map_err(|e| e.to_string())
+                }
+                RenameStep::HiddenProjects(previous) => self.save_hidden_projects(previous),
+            };
+            if let Err(e) = undone {
+                log::error!("Failed to undo rename step {:?}: {}", step, e);
+            }
+        }
+    }
+
     fn projects_dir(&self) -> PathBuf {
         self.claude_dir.join("projects")
     }
@@ -485,7 +825,7 @@ impl ProjectStore {
         let mut unique_projects_map: HashMap<String, Project> = HashMap::new();
 
         for project in all_projects {
-            let normalized_path = normalize_path_for_comparison(&project.path);
+            let normalized_path = project_path_key(&project.path);
 
             match unique_projects_map.get_mut(&normalized_path) {
                 Some(existing_project) => {
@@ -574,6 +914,99 @@ impl ProjectStore {
     }
 }
 
+/// Directory name and path key of every project directory
+fn project_dir_keys(projects_dir: &Path) -> Vec<(String, String)> {
+    let entries = match fs::read_dir(projects_dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.path().is_dir())
+        .filter_map(|entry| {
+            let dir_name = entry.file_name().to_str()?.to_string();
+            let path = get_project_path_from_sessions(&entry.path())
+                .unwrap_or_else(|_| decode_project_path(&dir_name));
+            Some((dir_name, project_path_key(&path)))
+        })
+        .collect()
+}
+
+/// A completed change made while renaming a project, kept so it can be undone
+#[derive(Debug)]
+enum RenameStep {
+    /// Renamed from the first path to the second
+    Moved(PathBuf, PathBuf),
+    /// Emptied by moving its entries out, then removed
+    RemovedDir(PathBuf),
+    /// Overwritten; holds the previous content
+    Rewrote(PathBuf, String),
+    /// hidden_projects.json before the update
+    HiddenProjects(Vec<String>),
+}
+
+/// Moves all entries of `from` into `to` (created if missing), then removes `from`
+/// Entries that already exist in `to` are left in place in `from`
+fn move_dir_contents(from: &Path, to: &Path) -> std::io::Result<()> {
+    move_dir_contents_journaled(from, to, &mut Vec::new())
+}
+
+/// Moves a directory, merging into `to` if it exists, recording each move in `steps`
+fn move_dir_contents_journaled(
+    from: &Path,
+    to: &Path,
+    steps: &mut Vec<RenameStep>,
+) -> std::io::Result<()> {
+    if !to.exists() {
+        fs::rename(from, to)?;
+        steps.push(RenameStep::Moved(from.to_path_buf(), to.to_path_buf()));
+        return Ok(());
+    }
+
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if target.exists() {
+            log::warn!("Skipping existing entry while moving: {:?}", target);
+            continue;
+        }
+        fs::rename(entry.path(), &target)?;
+        steps.push(RenameStep::Moved(entry.path(), target));
+    }
+    // Only succeeds if everything was moved
+    if fs::remove_dir(from).is_ok() {
+        steps.push(RenameStep::RemovedDir(from.to_path_buf()));
+    }
+    Ok(())
+}
+
+/// Latest activity of a project folder, computed as `list_projects` does
+fn project_activity_secs(project_dir: &Path) -> u64 {
+    let secs = |time: std::io::Result<SystemTime>| {
+        time.unwrap_or(SystemTime::UNIX_EPOCH)
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    };
+
+    let mut latest = fs::metadata(project_dir)
+        .map(|m| secs(m.created().or_else(|_| m.modified())))
+        .unwrap_or(0);
+    if let Ok(entries) = fs::read_dir(project_dir) {
+        for path in entries.flatten().map(|entry| entry.path()) {
+            if path.is_file()
+                && path.extension().and_then(|s| s.to_str()) == Some("jsonl")
+                && extract_first_user_message(&path).0.is_some()
+            {
+                if let Ok(metadata) = fs::metadata(&path) {
+                    latest = latest.max(secs(metadata.modified()));
+                }
+            }
+        }
+    }
+    latest
+}
+
 fn get_project_path_from_sessions(project_dir: &Path) -> Result<String, String> {
     let entries = fs::read_dir(project_dir)
         .map_err(|e| format!("Failed to read project directory: {}", e))?;
@@ -611,5 +1044,12 @@ fn get_project_path_from_sessions(project_dir: &Path) -> Result<String, String>
         }
     }
 
+    if let Ok(marker) = fs::read_to_string(project_dir.join(PROJECT_PATH_MARKER)) {
+        let marker = marker.trim();
+        if !marker.is_empty() {
+            return Ok(marker.to_string());
+        }
+    }
+
     Err("Could not determine project path from session files".to_string())
 }
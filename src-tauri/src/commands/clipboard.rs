@@ -150,3 +150,35 @@ pub async fn read_from_clipboard() -> Result<String, String> {
 
     Ok(text)
 }
+
+/// 将会话完整记录复制到剪贴板（markdown / text）
+#[command]
+pub async fn copy_session_to_clipboard(
+    session_id: String,
+    tool: String,
+    format: Option<String>,
+) -> Result<(), String> {
+    let format = format.unwrap_or_else(|| "markdown".to_string());
+    log::info!(
+        "Copying {} session {} to clipboard as {}",
+        tool,
+        session_id,
+        format
+    );
+
+    let entries = super::transcript::load_transcript(&tool, &session_id)?;
+    if entries.is_empty() {
+        return Err(format!("Session {} has no messages to copy", session_id));
+    }
+    let text = super::transcript::render_transcript(&entries, &format);
+
+    let mut clipboard =
+        Clipboard::new().map_err(|e| format!("Failed to access clipboard: {}", e))?;
+
+    clipboard
+        .set_text(&text)
+        .map_err(|e| format!("Failed to write to clipboard: {}", e))?;
+
+    log::info!("Copied {} messages ({} chars) to clipboard", entries.len(), text.len());
+    Ok(())
+}
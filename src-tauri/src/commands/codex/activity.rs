@@ -0,0 +1,215 @@
+/**
+ * Codex Session Activity
+ *
+ * Summarizes what a Codex session did on disk by scanning the tool calls in its
+ * JSONL: shell commands run, files written through apply_patch, and files read
+ * by common viewer commands (cat, head, sed -n, ...). Reads are inferred from
+ * command lines, so they are a best-effort view rather than a complete audit.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use super::config::get_codex_sessions_dir;
+use super::session::find_session_file;
+
+/// Programs whose non-flag arguments are files being read
+const READ_PROGRAMS: &[&str] = &["cat", "head", "tail", "less", "more", "nl", "bat", "sed", "wc", "type", "Get-Content"];
+
+/// A shell command run by the agent
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexCommandRun {
+    pub command: String,
+    pub workdir: Option<String>,
+    pub timestamp: Option<String>,
+}
+
+/// Files touched and commands run in a session, in first-seen order
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexSessionActivity {
+    pub files_read: Vec<String>,
+    pub files_written: Vec<String>,
+    pub commands_run: Vec<CodexCommandRun>,
+}
+
+impl CodexSessionActivity {
+    fn add_read(&mut self, seen: &mut HashSet<String>, path: String) {
+        if seen.insert(format!("r:{}", path)) {
+            self.files_read.push(path);
+        }
+    }
+
+    fn add_written(&mut self, seen: &mut HashSet<String>, path: String) {
+        if seen.insert(format!("w:{}", path)) {
+            self.files_written.push(path);
+        }
+    }
+}
+
+/// Summarizes the file and command activity of a Codex session
+#[tauri::command]
+pub async fn get_codex_session_activity(session_id: String) -> Result<CodexSessionActivity, String> {
+    log::info!("[Codex] Summarizing activity of session: {}", session_id);
+
+    let sessions_dir = get_codex_sessions_dir()?;
+    let path = find_session_file(&sessions_dir, &session_id)
+        .ok_or_else(|| format!("Session file not found for ID: {}", session_id))?;
+    let content = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read session file: {}", e))?;
+
+    let mut activity = CodexSessionActivity::default();
+    let mut seen = HashSet::new();
+
+    for line in content.lines() {
+        let event: serde_json::Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if event["type"].as_str() != Some("response_item") {
+            continue;
+        }
+        let payload = &event["payload"];
+        let timestamp = event["timestamp"].as_str().map(String::from);
+
+        let (name, args) = match payload["type"].as_str() {
+            Some("function_call") => (
+                payload["name"].as_str().unwrap_or(""),
+                payload["arguments"]
+                    .as_str()
+                    .and_then(|args| serde_json::from_str(args).ok())
+                    .unwrap_or(serde_json::Value::Null),
+            ),
+            Some("custom_tool_call") => (
+                payload["name"].as_str().unwrap_or(""),
+                serde_json::json!({ "input": payload["input"] }),
+            ),
+            Some("local_shell_call") => ("shell", payload["action"].clone()),
+            _ => continue,
+        };
+
+        if name == "apply_patch" {
+            for file in patch_files(args["input"].as_str().unwrap_or("")) {
+                activity.add_written(&mut seen, file);
+            }
+            continue;
+        }
+
+        let command = match shell_command_text(&args) {
+            Some(command) => command,
+            None => continue,
+        };
+        // apply_patch can also arrive as a shell heredoc
+        if command.contains("*** Begin Patch") {
+            for file in patch_files(&command) {
+                activity.add_written(&mut seen, file);
+            }
+        }
+        for file in read_targets(&command) {
+            activity.add_read(&mut seen, file);
+        }
+        activity.commands_run.push(CodexCommandRun {
+            command,
+            workdir: args["workdir"]
+                .as_str()
+                .or_else(|| args["working_directory"].as_str())
+                .map(String::from),
+            timestamp,
+        });
+    }
+
+    log::info!(
+        "[Codex] Session {} activity: {} read, {} written, {} commands",
+        session_id,
+        activity.files_read.len(),
+        activity.files_written.len(),
+        activity.commands_run.len()
+    );
+    Ok(activity)
+}
+
+/// Command line of a shell tool call, unwrapping `bash -lc "<script>"`
+fn shell_command_text(args: &serde_json::Value) -> Option<String> {
+    let command = args.get("command").or_else(|| args.get("cmd"))?;
+    if let Some(text) = command.as_str() {
+        return Some(text.to_string());
+    }
+
+    let parts: Vec<&str> = command.as_array()?.iter().filter_map(|p| p.as_str()).collect();
+    match parts.as_slice() {
+        [shell, flag, script, ..]
+            if matches!(*flag, "-c" | "-lc" | "-Command" | "/c")
+                && ["bash", "sh", "zsh", "powershell", "pwsh", "cmd"]
+                    .iter()
+                    .any(|s| shell.ends_with(s) || shell.ends_with(&format!("{}.exe", s))) =>
+        {
+            Some(script.to_string())
+        }
+        [] => None,
+        _ => Some(parts.join(" ")),
+    }
+}
+
+/// Files added, updated, deleted or moved by an apply_patch body
+fn patch_files(patch: &str) -> Vec<String> {
+    const MARKERS: &[&str] = &["*** Add File: ", "*** Update File: ", "*** Delete File: ", "*** Move to: "];
+    patch
+        .lines()
+        .filter_map(|line| {
+            MARKERS
+                .iter()
+                .find_map(|marker| line.trim().strip_prefix(marker))
+                .map(|path| path.trim().to_string())
+        })
+        .filter(|path| !path.is_empty())
+        .collect()
+}
+
+/// Files read by viewer commands in a shell script
+fn read_targets(command: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    for segment in command.split(['\n', ';', '|', '&']) {
+        let mut tokens = segment.split_whitespace();
+        let program = match tokens.next() {
+            Some(program) => program.rsplit('/').next().unwrap_or(program),
+            None => continue,
+        };
+        if !READ_PROGRAMS.contains(&program) {
+            continue;
+        }
+
+        // The first non-flag argument of sed is its script
+        let mut skip_script = program == "sed";
+        for token in tokens {
+            let token = token.trim_matches(['\'', '"']);
+            if token.is_empty() || token.starts_with('-') || token.starts_with('>') || token.starts_with('<') {
+                continue;
+            }
+            if skip_script {
+                skip_script = false;
+                continue;
+            }
+            if token.chars().all(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            files.push(token.to_string());
+        }
+    }
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_patch_files_and_read_targets() {
+        let patch = "*** Begin Patch\n*** Update File: src/main.rs\n@@\n-a\n+b\n*** Add File: src/new.rs\n+x\n*** End Patch";
+        assert_eq!(patch_files(patch), vec!["src/main.rs", "src/new.rs"]);
+
+        let args = serde_json::json!({ "command": ["bash", "-lc", "sed -n '1,80p' src/lib.rs && cat -n Cargo.toml | head -20"] });
+        let command = shell_command_text(&args).unwrap();
+        assert_eq!(read_targets(&command), vec!["src/lib.rs", "Cargo.toml"]);
+    }
+}
@@ -9,16 +9,18 @@
  */
 
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::fs;
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 use tokio::process::Command;
 use dirs;
-use rusqlite;
 
 // Import platform-specific utilities for window hiding
 use crate::commands::claude::apply_no_window_async;
 use crate::claude_binary::detect_binary_for_tool;
+use crate::commands::settings_store::{self, SettingKey};
+use crate::commands::config_backup::{redact_json, redact_key_value_lines};
 // Import WSL utilities
 use super::super::wsl_utils;
 
@@ -192,6 +194,209 @@ pub fn get_binary_override(tool: &str) -> Option<String> {
         .map(|s| s.to_string())
 }
 
+/// Tool sections understood in binaries.json
+const BINARIES_JSON_TOOLS: &[&str] = &["claude", "codex"];
+
+/// Result of `validate_binaries_json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinariesJsonStatus {
+    pub path: String,
+    pub exists: bool,
+    /// True when the file is absent or parses with no issues
+    pub valid: bool,
+    /// Tool -> override path, for every readable override
+    pub overrides: HashMap<String, String>,
+    pub issues: Vec<String>,
+}
+
+/// Result of `repair_binaries_json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BinariesJsonRepair {
+    /// Copy of the original file; None when nothing needed repairing
+    pub backup_path: Option<String>,
+    pub overrides: HashMap<String, String>,
+    /// Entries that could not be recovered
+    pub dropped: Vec<String>,
+}
+
+fn binaries_json_path() -> Result<PathBuf, String> {
+    let home = dirs::home_dir().ok_or("Cannot find home directory".to_string())?;
+    Ok(home.join(".claude").join("binaries.json"))
+}
+
+/// Checks ~/.claude/binaries.json and lists the overrides it contains
+#[tauri::command]
+pub async fn validate_binaries_json() -> Result<BinariesJsonStatus, String> {
+    let path = binaries_json_path()?;
+    let mut status = BinariesJsonStatus {
+        path: path.to_string_lossy().to_string(),
+        exists: path.exists(),
+        valid: true,
+        overrides: HashMap::new(),
+        issues: Vec::new(),
+    };
+    if !status.exists {
+        return Ok(status);
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read binaries.json: {}", e))?;
+    match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(json) => {
+            let (sections, issues) = normalize_binaries_json(&json);
+            status.overrides = binary_overrides(&sections);
+            status.issues = issues;
+        }
+        Err(e) => {
+            status.issues.push(format!("Invalid JSON: {}", e));
+            status.overrides = binary_overrides(&recover_binaries_sections(&content));
+        }
+    }
+    status.valid = status.issues.is_empty();
+    Ok(status)
+}
+
+/// Rewrites ~/.claude/binaries.json with only well-formed entries
+/// Overrides are recovered from malformed JSON where possible; the original is
+/// kept as binaries.json.bak-<timestamp>.
+#[tauri::command]
+pub async fn repair_binaries_json() -> Result<BinariesJsonRepair, String> {
+    let path = binaries_json_path()?;
+    if !path.exists() {
+        return Ok(BinariesJsonRepair {
+            backup_path: None,
+            overrides: HashMap::new(),
+            dropped: Vec::new(),
+        });
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read binaries.json: {}", e))?;
+    let (sections, dropped) = match serde_json::from_str::<serde_json::Value>(&content) {
+        Ok(json) => normalize_binaries_json(&json),
+        Err(e) => (
+            recover_binaries_sections(&content),
+            vec![format!("Unparseable content: {}", e)],
+        ),
+    };
+
+    let repaired = serde_json::to_string_pretty(&serde_json::Value::Object(sections.clone()))
+        .map_err(|e| format!("Failed to serialize binaries.json: {}", e))?;
+    let backup_path = if dropped.is_empty() {
+        None
+    } else {
+        let backup = path.with_file_name(format!(
+            "binaries.json.bak-{}",
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        fs::copy(&path, &backup).map_err(|e| format!("Failed to back up binaries.json: {}", e))?;
+        fs::write(&path, repaired).map_err(|e| format!("Failed to write binaries.json: {}", e))?;
+        log::info!("Repaired binaries.json, original kept at {:?}", backup);
+        Some(backup.to_string_lossy().to_string())
+    };
+
+    Ok(BinariesJsonRepair {
+        backup_path,
+        overrides: binary_overrides(&sections),
+        dropped,
+    })
+}
+
+/// Keeps the well-formed parts of a parsed binaries.json and describes what was left out
+fn normalize_binaries_json(
+    json: &serde_json::Value,
+) -> (serde_json::Map<String, serde_json::Value>, Vec<String>) {
+    let mut sections = serde_json::Map::new();
+    let mut issues = Vec::new();
+    let root = match json.as_object() {
+        Some(root) => root,
+        None => return (sections, vec!["Top level is not an object".to_string()]),
+    };
+
+    for (tool, entry) in root {
+        if !BINARIES_JSON_TOOLS.contains(&tool.as_str()) {
+            issues.push(format!("Unknown tool section: {}", tool));
+            continue;
+        }
+        let entry = match entry.as_object() {
+            Some(entry) => entry,
+            None => {
+                issues.push(format!("{}: section is not an object", tool));
+                continue;
+            }
+        };
+
+        let mut section = serde_json::Map::new();
+        for (key, value) in entry {
+            match key.as_str() {
+                "override_path" if value.is_string() || value.is_null() => {
+                    section.insert(key.clone(), value.clone());
+                }
+                "search_paths" => match value.as_array() {
+                    Some(paths) => {
+                        let valid: Vec<serde_json::Value> =
+                            paths.iter().filter(|p| p.is_string()).cloned().collect();
+                        if valid.len() != paths.len() {
+                            issues.push(format!("{}.search_paths: non-string entries", tool));
+                        }
+                        section.insert(key.clone(), serde_json::Value::Array(valid));
+                    }
+                    None => issues.push(format!("{}.search_paths: not an array", tool)),
+                },
+                "override_path" => issues.push(format!("{}.override_path: not a string", tool)),
+                other => issues.push(format!("{}.{}: unknown field", tool, other)),
+            }
+        }
+        sections.insert(tool.clone(), serde_json::Value::Object(section));
+    }
+    (sections, issues)
+}
+
+/// Salvages tool sections from text that is not valid JSON
+fn recover_binaries_sections(content: &str) -> serde_json::Map<String, serde_json::Value> {
+    let section_re = regex::Regex::new(r#""(claude|codex)"\s*:\s*\{([^{}]*)\}"#).unwrap();
+    let override_re =
+        regex::Regex::new(r#""override_path"\s*:\s*"((?:[^"\\]|\\.)*)""#).unwrap();
+    let search_re = regex::Regex::new(r#""search_paths"\s*:\s*\[([^\]]*)\]"#).unwrap();
+    let string_re = regex::Regex::new(r#""((?:[^"\\]|\\.)*)""#).unwrap();
+    let unescape = |raw: &str| {
+        serde_json::from_str::<String>(&format!("\"{}\"", raw)).unwrap_or_else(|_| raw.to_string())
+    };
+
+    let mut sections = serde_json::Map::new();
+    for caps in section_re.captures_iter(content) {
+        let body = &caps[2];
+        let mut section = serde_json::Map::new();
+        if let Some(path) = override_re.captures(body) {
+            section.insert(
+                "override_path".to_string(),
+                serde_json::Value::String(unescape(&path[1])),
+            );
+        }
+        if let Some(list) = search_re.captures(body) {
+            let paths = string_re
+                .captures_iter(&list[1])
+                .map(|s| serde_json::Value::String(unescape(&s[1])))
+                .collect();
+            section.insert("search_paths".to_string(), serde_json::Value::Array(paths));
+        }
+        sections.insert(caps[1].to_string(), serde_json::Value::Object(section));
+    }
+    sections
+}
+
+fn binary_overrides(sections: &serde_json::Map<String, serde_json::Value>) -> HashMap<String, String> {
+    sections
+        .iter()
+        .filter_map(|(tool, section)| {
+            let path = section.get("override_path")?.as_str()?;
+            Some((tool.clone(), path.to_string()))
+        })
+        .collect()
+}
+
 // ============================================================================
 // Sessions Directory
 // ============================================================================
@@ -390,42 +595,17 @@ pub async fn set_custom_codex_path(app: AppHandle, custom_path: String) -> Resul
     }
 
     // Also store in app_settings for compatibility
-    if let Ok(app_data_dir) = app.path().app_data_dir() {
-        let db_path = app_data_dir.join("agents.db");
-        if let Ok(conn) = rusqlite::Connection::open(&db_path) {
-            let _ = conn.execute(
-                "CREATE TABLE IF NOT EXISTS app_settings (
-                    key TEXT PRIMARY KEY,
-                    value TEXT NOT NULL
-                )",
-                [],
-            );
-            let _ = conn.execute(
-                "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
-                rusqlite::params!["codex_binary_path", path_str],
-            );
-        }
+    if let Err(e) = settings_store::set_setting(&app, SettingKey::CodexBinaryPath, &path_str) {
+        log::warn!("[Codex] Failed to store custom path in app_settings: {}", e);
     }
 
     Ok(())
 }
 
 fn read_custom_codex_path_from_db(app: &AppHandle) -> Option<String> {
-    if let Ok(app_data_dir) = app.path().app_data_dir() {
-        let db_path = app_data_dir.join("agents.db");
-        if db_path.exists() {
-            if let Ok(conn) = rusqlite::Connection::open(&db_path) {
-                if let Ok(val) = conn.query_row(
-                    "SELECT value FROM app_settings WHERE key = 'codex_binary_path'",
-                    [],
-                    |row| row.get::<_, String>(0),
-                ) {
-                    return Some(val);
-                }
-            }
-        }
-    }
-    None
+    settings_store::get_setting(app, SettingKey::CodexBinaryPath)
+        .ok()
+        .flatten()
 }
 
 /// Get current Codex path (custom first, then runtime detection)
@@ -449,16 +629,8 @@ pub async fn get_codex_path(app: AppHandle) -> Result<String, String> {
 /// Clear custom Codex path, restore auto detection
 #[tauri::command]
 pub async fn clear_custom_codex_path(app: AppHandle) -> Result<(), String> {
-    if let Ok(app_data_dir) = app.path().app_data_dir() {
-        let db_path = app_data_dir.join("agents.db");
-        if db_path.exists() {
-            if let Ok(conn) = rusqlite::Connection::open(&db_path) {
-                let _ = conn.execute(
-                    "DELETE FROM app_settings WHERE key = 'codex_binary_path'",
-                    [],
-                );
-            }
-        }
+    if let Err(e) = settings_store::delete_setting(&app, SettingKey::CodexBinaryPath) {
+        log::warn!("[Codex] Failed to clear custom path in app_settings: {}", e);
     }
 
     if let Err(e) = clear_binary_override("codex") {
@@ -869,6 +1041,15 @@ fn extract_model_from_config(config: &str) -> Option<String> {
     None
 }
 
+/// Extract top-level model_provider from config.toml text
+fn extract_model_provider_from_config(config: &str) -> Option<String> {
+    let table: toml::Table = toml::from_str(config).ok()?;
+    table
+        .get("model_provider")
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string())
+}
+
 // ============================================================================
 // Provider Management Commands
 // ============================================================================
@@ -933,6 +1114,141 @@ pub async fn get_current_codex_config() -> Result<CurrentCodexConfig, String> {
     })
 }
 
+/// Get the name of the currently active Codex provider
+/// Matches the current config.toml against saved presets by base_url / model_provider.
+/// Returns "Official OpenAI" when no provider is configured, "Custom" when nothing matches.
+#[tauri::command]
+pub async fn get_active_codex_provider() -> Result<String, String> {
+    let name = match find_active_codex_preset().await? {
+        ActiveCodexPreset::Official => "Official OpenAI".to_string(),
+        ActiveCodexPreset::Preset(preset) => preset.name,
+        ActiveCodexPreset::Custom => "Custom".to_string(),
+    };
+    log::info!("[Codex Provider] Active provider: {}", name);
+    Ok(name)
+}
+
+/// What the current config.toml points at
+enum ActiveCodexPreset {
+    /// No provider configured
+    Official,
+    Preset(CodexProviderConfig),
+    /// A provider that matches no saved preset
+    Custom,
+}
+
+/// Finds the saved preset matching the current config.toml
+async fn find_active_codex_preset() -> Result<ActiveCodexPreset, String> {
+    let current = get_current_codex_config().await?;
+    let current_base_url = current
+        .base_url
+        .as_deref()
+        .map(|u| u.trim_end_matches('/').to_string());
+    let current_model_provider = extract_model_provider_from_config(&current.config);
+
+    if current_base_url.is_none() && current_model_provider.is_none() {
+        return Ok(ActiveCodexPreset::Official);
+    }
+
+    let presets = get_codex_provider_presets().await?;
+    let matched = presets.into_iter().find(|preset| {
+        let preset_base_url = extract_base_url_from_config(&preset.config)
+            .map(|u| u.trim_end_matches('/').to_string());
+        let preset_model_provider = extract_model_provider_from_config(&preset.config);
+
+        let base_url_matches = preset_base_url.is_some() && preset_base_url == current_base_url;
+        let provider_matches = match (&preset_model_provider, &current_model_provider) {
+            (Some(a), Some(b)) => a == b,
+            _ => true,
+        };
+
+        if current_base_url.is_some() {
+            base_url_matches && provider_matches
+        } else {
+            preset_model_provider.is_some() && preset_model_provider == current_model_provider
+        }
+    });
+
+    Ok(matched.map_or(ActiveCodexPreset::Custom, ActiveCodexPreset::Preset))
+}
+
+/// Remove duplicate provider presets (same base_url + model + auth)
+/// Keeps the most recently created entry of each group, or the active one if it is in the group.
+/// Returns the IDs of the removed providers.
+#[tauri::command]
+pub async fn dedupe_codex_providers() -> Result<Vec<String>, String> {
+    log::info!("[Codex Provider] Deduplicating provider presets");
+
+    let providers = get_codex_provider_presets().await?;
+    if providers.len() < 2 {
+        return Ok(vec![]);
+    }
+
+    let active_id = match find_active_codex_preset().await? {
+        ActiveCodexPreset::Preset(preset) => Some(preset.id),
+        ActiveCodexPreset::Official | ActiveCodexPreset::Custom => None,
+    };
+
+    // Group by normalized identity; serde_json maps are sorted, so auth serializes canonically
+    let identity = |p: &CodexProviderConfig| {
+        (
+            extract_base_url_from_config(&p.config)
+                .map(|u| u.trim_end_matches('/').to_lowercase())
+                .unwrap_or_default(),
+            extract_model_from_config(&p.config).unwrap_or_default(),
+            p.auth.to_string(),
+        )
+    };
+
+    let mut keepers: HashMap<(String, String, String), usize> = HashMap::new();
+    for (index, provider) in providers.iter().enumerate() {
+        let key = identity(provider);
+        match keepers.get(&key).copied() {
+            None => {
+                keepers.insert(key, index);
+            }
+            Some(kept) => {
+                let kept_provider = &providers[kept];
+                let kept_is_active = active_id.as_ref() == Some(&kept_provider.id);
+                let is_active = active_id.as_ref() == Some(&provider.id);
+                let is_newer = provider.created_at.unwrap_or(0) > kept_provider.created_at.unwrap_or(0);
+                if is_active || (!kept_is_active && is_newer) {
+                    keepers.insert(key, index);
+                }
+            }
+        }
+    }
+
+    let kept_indices: HashSet<usize> = keepers.into_values().collect();
+    let mut removed_ids = Vec::new();
+    let mut remaining = Vec::new();
+    for (index, provider) in providers.into_iter().enumerate() {
+        if kept_indices.contains(&index) {
+            remaining.push(provider);
+        } else {
+            removed_ids.push(provider.id);
+        }
+    }
+
+    if removed_ids.is_empty() {
+        log::info!("[Codex Provider] No duplicate providers found");
+        return Ok(removed_ids);
+    }
+
+    let providers_path = get_codex_providers_path()?;
+    let content = serde_json::to_string_pretty(&remaining)
+        .map_err(|e| format!("Failed to serialize providers: {}", e))?;
+    fs::write(&providers_path, content)
+        .map_err(|e| format!("Failed to write providers.json: {}", e))?;
+
+    log::info!(
+        "[Codex Provider] Removed {} duplicate provider(s): {:?}",
+        removed_ids.len(),
+        removed_ids
+    );
+    Ok(removed_ids)
+}
+
 /// Switch to a Codex provider configuration
 /// Preserves user's custom settings and OAuth tokens
 #[tauri::command]
@@ -949,14 +1265,124 @@ pub async fn switch_codex_provider(config: CodexProviderConfig) -> Result<String
             .map_err(|e| format!("Failed to create .codex directory: {}", e))?;
     }
 
-    // Validate new TOML if not empty
-    let new_config_table: Option<toml::Table> = if !config.config.trim().is_empty() {
-        Some(toml::from_str(&config.config)
-            .map_err(|e| format!("Invalid TOML configuration: {}", e))?)
-    } else {
-        None
+    let final_auth = merge_codex_auth(&auth_path, &config)?;
+    let final_config = merge_codex_config(&config_path, &config)?;
+
+    // Write merged auth.json
+    let auth_content = serde_json::to_string_pretty(&final_auth)
+        .map_err(|e| format!("Failed to serialize auth: {}", e))?;
+    fs::write(&auth_path, auth_content)
+        .map_err(|e| format!("Failed to write auth.json: {}", e))?;
+
+    // Write merged config.toml
+    fs::write(&config_path, &final_config)
+        .map_err(|e| format!("Failed to write config.toml: {}", e))?;
+
+    log::info!("[Codex Provider] Successfully switched to: {}", config.name);
+    Ok(format!("Successfully switched to Codex provider: {}", config.name))
+}
+
+/// Preview of the changes `switch_codex_provider` would make, secrets redacted
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexProviderSwitchPreview {
+    /// Line diff of config.toml ("  " unchanged, "- " removed, "+ " added)
+    pub config_diff: String,
+    pub auth_diff: String,
+    pub config_changed: bool,
+    pub auth_changed: bool,
+}
+
+/// Computes what switching to `config` would write, without touching any file
+#[tauri::command]
+pub async fn preview_codex_provider_switch(
+    config: CodexProviderConfig,
+) -> Result<CodexProviderSwitchPreview, String> {
+    log::info!("[Codex Provider] Previewing switch to provider: {}", config.name);
+
+    let auth_path = get_codex_auth_path()?;
+    let config_path = get_codex_config_path()?;
+
+    let proposed_auth = merge_codex_auth(&auth_path, &config)?;
+    let proposed_config = merge_codex_config(&config_path, &config)?;
+
+    let current_auth = fs::read_to_string(&auth_path)
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok());
+    let current_config = fs::read_to_string(&config_path).unwrap_or_default();
+
+    let render_auth = |auth: Option<&serde_json::Value>| -> String {
+        auth.map(|auth| {
+            let mut auth = auth.clone();
+            redact_json(&mut auth);
+            serde_json::to_string_pretty(&auth).unwrap_or_default()
+        })
+        .unwrap_or_default()
     };
+    let current_auth_text = render_auth(current_auth.as_ref());
+    let proposed_auth_text = render_auth(Some(&proposed_auth));
+    let current_config_text = render_config_for_diff(&current_config);
+    let proposed_config_text = render_config_for_diff(&proposed_config);
+
+    Ok(CodexProviderSwitchPreview {
+        config_changed: current_config_text != proposed_config_text,
+        auth_changed: current_auth.as_ref() != Some(&proposed_auth),
+        config_diff: line_diff(&current_config_text, &proposed_config_text),
+        auth_diff: line_diff(&current_auth_text, &proposed_auth_text),
+    })
+}
+
+/// Re-serializes TOML with secrets redacted, so formatting differences don't show up in the diff
+/// Unparseable content is redacted line by line as is.
+fn render_config_for_diff(content: &str) -> String {
+    let normalized = match toml::from_str::<toml::Table>(content) {
+        Ok(table) => toml::to_string_pretty(&table).unwrap_or_default(),
+        Err(_) => content.to_string(),
+    };
+    redact_key_value_lines(&normalized)
+}
+
+/// Full-context line diff based on the longest common subsequence
+fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = LCS length of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
 
+    let mut out = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            out.push(format!("  {}", old[i]));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            out.push(format!("- {}", old[i]));
+            i += 1;
+        } else {
+            out.push(format!("+ {}", new[j]));
+            j += 1;
+        }
+    }
+    out.join("\n")
+}
+
+/// Merges the provider's auth into the existing auth.json content
+/// Existing OAuth tokens and other credentials are preserved.
+fn merge_codex_auth(
+    auth_path: &std::path::Path,
+    config: &CodexProviderConfig,
+) -> Result<serde_json::Value, String> {
     // Merge auth.json - preserve existing OAuth tokens and other credentials
     // API key related fields that should be cleared when switching to official auth
     let api_key_fields = ["OPENAI_API_KEY", "OPENAI_KEY", "API_KEY"];
@@ -1005,11 +1431,22 @@ pub async fn switch_codex_provider(config: CodexProviderConfig) -> Result<String
             .map_err(|e| format!("Failed to convert auth: {}", e))?
     };
 
-    // Write merged auth.json
-    let auth_content = serde_json::to_string_pretty(&final_auth)
-        .map_err(|e| format!("Failed to serialize auth: {}", e))?;
-    fs::write(&auth_path, auth_content)
-        .map_err(|e| format!("Failed to write auth.json: {}", e))?;
+    Ok(final_auth)
+}
+
+/// Merges the provider's TOML into the existing config.toml content
+/// Provider keys are replaced; the user's other settings are preserved.
+fn merge_codex_config(
+    config_path: &std::path::Path,
+    config: &CodexProviderConfig,
+) -> Result<String, String> {
+    // Validate new TOML if not empty
+    let new_config_table: Option<toml::Table> = if !config.config.trim().is_empty() {
+        Some(toml::from_str(&config.config)
+            .map_err(|e| format!("Invalid TOML configuration: {}", e))?)
+    } else {
+        None
+    };
 
     // Merge config.toml - preserve user's custom settings
     let final_config = if config_path.exists() {
@@ -1055,12 +1492,7 @@ pub async fn switch_codex_provider(config: CodexProviderConfig) -> Result<String
         config.config.clone()
     };
 
-    // Write merged config.toml
-    fs::write(&config_path, &final_config)
-        .map_err(|e| format!("Failed to write config.toml: {}", e))?;
-
-    log::info!("[Codex Provider] Successfully switched to: {}", config.name);
-    Ok(format!("Successfully switched to Codex provider: {}", config.name))
+    Ok(final_config)
 }
 
 /// Add a new Codex provider configuration
@@ -1104,6 +1536,39 @@ pub async fn add_codex_provider_config(config: CodexProviderConfig) -> Result<St
     Ok(format!("Successfully added Codex provider: {}", config.name))
 }
 
+/// Clone a saved provider preset under a new ID and name
+/// The copy keeps auth and config as-is, so a working provider can be tweaked
+/// instead of written from scratch. Returns the new preset.
+#[tauri::command]
+pub async fn clone_codex_provider(
+    source_id: String,
+    new_id: String,
+    new_name: String,
+) -> Result<CodexProviderConfig, String> {
+    log::info!("[Codex Provider] Cloning provider {} as {}", source_id, new_id);
+
+    if new_id.trim().is_empty() || new_name.trim().is_empty() {
+        return Err("Provider ID and name cannot be empty".to_string());
+    }
+
+    let source = get_codex_provider_presets()
+        .await?
+        .into_iter()
+        .find(|p| p.id == source_id)
+        .ok_or_else(|| format!("Provider with ID '{}' not found", source_id))?;
+
+    let clone = CodexProviderConfig {
+        id: new_id.trim().to_string(),
+        name: new_name.trim().to_string(),
+        is_official: Some(false),
+        created_at: Some(chrono::Utc::now().timestamp_millis()),
+        ..source
+    };
+    add_codex_provider_config(clone.clone()).await?;
+
+    Ok(clone)
+}
+
 /// Update an existing Codex provider configuration
 #[tauri::command]
 pub async fn update_codex_provider_config(config: CodexProviderConfig) -> Result<String, String> {
@@ -1194,10 +1659,159 @@ pub async fn clear_codex_provider_config() -> Result<String, String> {
     Ok("Successfully cleared Codex configuration. Now using official OpenAI.".to_string())
 }
 
+/// Contents written when config.toml is missing: no provider, so the CLI uses official OpenAI
+const DEFAULT_CODEX_CONFIG: &str = "# Codex CLI configuration\n# Restored with defaults (official OpenAI). Switch providers to customize.\n";
+
+/// Providers the Codex CLI knows without a [model_providers.*] table
+const BUILTIN_MODEL_PROVIDERS: &[&str] = &["openai", "oss", "ollama", "lmstudio"];
+
+/// Health of one file or directory under ~/.codex
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexConfigFileHealth {
+    pub name: String,
+    pub path: String,
+    pub exists: bool,
+    /// Whether the CLI misbehaves when this file is absent
+    pub required: bool,
+    /// Missing optional files count as valid
+    pub valid: bool,
+    pub error: Option<String>,
+}
+
+/// Health report of the ~/.codex directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexConfigHealth {
+    pub config_dir: String,
+    pub dir_exists: bool,
+    pub files: Vec<CodexConfigFileHealth>,
+    pub healthy: bool,
+}
+
+/// Writes a default config.toml if it is missing, e.g. after a crash or a cleared provider
+/// Returns whether a file was created; an existing config is never touched.
+#[tauri::command]
+pub async fn ensure_codex_config_exists() -> Result<bool, String> {
+    let config_path = get_codex_config_path()?;
+    if config_path.exists() {
+        return Ok(false);
+    }
+
+    let config_dir = get_codex_config_dir()?;
+    fs::create_dir_all(&config_dir)
+        .map_err(|e| format!("Failed to create .codex directory: {}", e))?;
+    fs::write(&config_path, DEFAULT_CODEX_CONFIG)
+        .map_err(|e| format!("Failed to write config.toml: {}", e))?;
+
+    log::info!("[Codex Provider] Created default config.toml at {:?}", config_path);
+    Ok(true)
+}
+
+/// Reports missing or unparsable files across the ~/.codex directory
+#[tauri::command]
+pub async fn get_codex_config_health() -> Result<CodexConfigHealth, String> {
+    let config_dir = get_codex_config_dir()?;
+
+    let config_path = get_codex_config_path()?;
+    let config = check_codex_file("config.toml", &config_path, true, |content| {
+        let table: toml::Table =
+            toml::from_str(content).map_err(|e| format!("Invalid TOML: {}", e))?;
+        match table.get("model_provider").and_then(|v| v.as_str()) {
+            Some(provider)
+                if !BUILTIN_MODEL_PROVIDERS.contains(&provider)
+                    && table
+                        .get("model_providers")
+                        .and_then(|providers| providers.get(provider))
+                        .is_none() =>
+            {
+                Err(format!(
+                    "model_provider \"{}\" has no [model_providers.{}] section",
+                    provider, provider
+                ))
+            }
+            _ => Ok(()),
+        }
+    });
+
+    let auth = check_codex_file("auth.json", &get_codex_auth_path()?, false, |content| {
+        match serde_json::from_str::<serde_json::Value>(content) {
+            Ok(value) if value.is_object() => Ok(()),
+            Ok(_) => Err("Expected a JSON object".to_string()),
+            Err(e) => Err(format!("Invalid JSON: {}", e)),
+        }
+    });
+
+    let providers = check_codex_file("providers.json", &get_codex_providers_path()?, false, |content| {
+        serde_json::from_str::<Vec<CodexProviderConfig>>(content)
+            .map(|_| ())
+            .map_err(|e| format!("Invalid provider presets: {}", e))
+    });
+
+    let sessions_path = config_dir.join("sessions");
+    let sessions_exists = sessions_path.exists();
+    let sessions_error = (sessions_exists && !sessions_path.is_dir())
+        .then(|| "Expected a directory".to_string());
+    let sessions = CodexConfigFileHealth {
+        name: "sessions".to_string(),
+        path: sessions_path.to_string_lossy().to_string(),
+        exists: sessions_exists,
+        required: false,
+        valid: sessions_error.is_none(),
+        error: sessions_error,
+    };
+
+    let files = vec![config, auth, providers, sessions];
+    let problems: Vec<&str> = files
+        .iter()
+        .filter(|f| !f.valid)
+        .map(|f| f.name.as_str())
+        .collect();
+    let healthy = problems.is_empty();
+    if !healthy {
+        log::warn!("[Codex Provider] Config directory has problems: {:?}", problems);
+    }
+
+    Ok(CodexConfigHealth {
+        config_dir: config_dir.to_string_lossy().to_string(),
+        dir_exists: config_dir.is_dir(),
+        files,
+        healthy,
+    })
+}
+
+/// Reads and validates one ~/.codex file
+fn check_codex_file(
+    name: &str,
+    path: &std::path::Path,
+    required: bool,
+    validate: impl FnOnce(&str) -> Result<(), String>,
+) -> CodexConfigFileHealth {
+    let exists = path.exists();
+    let error = if !exists {
+        required.then(|| "File is missing".to_string())
+    } else {
+        fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read file: {}", e))
+            .and_then(|content| validate(&content))
+            .err()
+    };
+
+    CodexConfigFileHealth {
+        name: name.to_string(),
+        path: path.to_string_lossy().to_string(),
+        exists,
+        required,
+        valid: error.is_none(),
+        error,
+    }
+}
+
 /// Test Codex provider connection
 #[tauri::command]
 pub async fn test_codex_provider_connection(base_url: String, api_key: Option<String>) -> Result<String, String> {
     log::info!("[Codex Provider] Testing connection to: {}", base_url);
+    crate::commands::network_mode::ensure_online("Provider connection test")?;
 
     // Simple connectivity test - just try to reach the endpoint
     let client = reqwest::Client::builder()
@@ -1228,3 +1842,25 @@ pub async fn test_codex_provider_connection(base_url: String, api_key: Option<St
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn recovers_overrides_from_malformed_binaries_json() {
+        let broken = r#"{ "claude": { "override_path": "/opt/claude", "search_paths": ["/a", "/b",] }, }"#;
+        assert!(serde_json::from_str::<serde_json::Value>(broken).is_err());
+        let sections = recover_binaries_sections(broken);
+        assert_eq!(binary_overrides(&sections)["claude"], "/opt/claude");
+        assert_eq!(sections["claude"]["search_paths"].as_array().unwrap().len(), 2);
+
+        let json = serde_json::json!({
+            "codex": { "override_path": "/c", "search_paths": ["/a", 3], "extra": 1 },
+            "unknown": {}
+        });
+        let (sections, issues) = normalize_binaries_json(&json);
+        assert_eq!(issues.len(), 3);
+        assert_eq!(sections["codex"]["search_paths"].as_array().unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,128 @@
+/**
+ * Codex Force Kill
+ *
+ * Fallback cancellation for runs whose process is no longer tracked in
+ * `CodexProcessState` (e.g. after the tracking ID got out of sync). Besides the
+ * tracked child, only codex processes whose command line carries the session ID
+ * are killed; other runs in the same project are never touched.
+ */
+
+use std::collections::HashSet;
+use tauri::{AppHandle, Manager};
+use tokio::process::Command;
+
+use crate::commands::claude::{apply_no_window_async, kill_process_tree};
+use super::session::CodexProcessState;
+
+/// A codex process found in the OS process list
+#[derive(Debug, Clone)]
+struct OsProcess {
+    pid: u32,
+    command_line: String,
+}
+
+/// Kills a Codex session even when its process is not tracked
+/// Returns whether at least one process was actually terminated.
+#[tauri::command]
+pub async fn force_kill_codex_session(
+    session_id: String,
+    app_handle: AppHandle,
+) -> Result<bool, String> {
+    log::info!("[Codex] force_kill_codex_session called for session: {}", session_id);
+
+    let state: tauri::State<'_, CodexProcessState> = app_handle.state();
+    let mut terminated = false;
+
+    // 1. The tracked child, if the map still knows it
+    let tracked = state.processes.lock().await.remove(&session_id);
+    if let Some(mut child) = tracked {
+        let killed = match child.id() {
+            Some(pid) => kill_process_tree(pid).is_ok(),
+            None => false,
+        };
+        terminated = killed || child.kill().await.is_ok();
+        log::info!("[Codex] Killed tracked process for session {}: {}", session_id, terminated);
+    }
+
+    // 2. OS-level search by argv (resume runs carry the session ID)
+    let tracked_pids: HashSet<u32> = state
+        .processes
+        .lock()
+        .await
+        .values()
+        .filter_map(|child| child.id())
+        .collect();
+
+    for process in list_codex_processes().await? {
+        // Never touch ourselves or runs that belong to other tracked sessions
+        if process.pid == std::process::id() || tracked_pids.contains(&process.pid) {
+            continue;
+        }
+        if !process.command_line.contains(&session_id) {
+            continue;
+        }
+
+        match kill_process_tree(process.pid) {
+            Ok(()) => {
+                log::info!(
+                    "[Codex] Force-killed untracked process {} ({})",
+                    process.pid,
+                    process.command_line
+                );
+                terminated = true;
+            }
+            Err(e) => log::warn!("[Codex] Failed to kill process {}: {}", process.pid, e),
+        }
+    }
+
+    if !terminated {
+        log::warn!("[Codex] No running process found for session: {}", session_id);
+    }
+    Ok(terminated)
+}
+
+/// Lists running processes whose command line mentions codex
+async fn list_codex_processes() -> Result<Vec<OsProcess>, String> {
+    #[cfg(target_os = "windows")]
+    let mut cmd = {
+        let mut cmd = Command::new("powershell");
+        cmd.args([
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_Process | Where-Object { $_.CommandLine -like '*codex*' } | \
+             ForEach-Object { \"$($_.ProcessId) $($_.CommandLine)\" }",
+        ]);
+        cmd
+    };
+
+    #[cfg(not(target_os = "windows"))]
+    let mut cmd = {
+        let mut cmd = Command::new("ps");
+        cmd.args(["-Ao", "pid=,args="]);
+        cmd
+    };
+
+    apply_no_window_async(&mut cmd);
+    let output = cmd
+        .output()
+        .await
+        .map_err(|e| format!("Failed to list processes: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to list processes: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (pid, command_line) = line.trim().split_once(char::is_whitespace)?;
+            Some(OsProcess {
+                pid: pid.parse().ok()?,
+                command_line: command_line.trim().to_string(),
+            })
+        })
+        .filter(|process| process.command_line.to_lowercase().contains("codex"))
+        .collect())
+}
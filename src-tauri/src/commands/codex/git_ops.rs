@@ -17,6 +17,9 @@ use chrono::Utc;
 use super::super::simple_git;
 // Import rewind helpers/types shared with Claude
 use super::super::prompt_tracker::{RewindMode, RewindCapabilities, PromptRecord as ClaudePromptRecord, load_execution_config};
+use super::super::permission_config::AutoCommitMode;
+use super::super::claude::project_path_key;
+use super::super::session_meta::{ensure_session_unmodified, resolve_session_file};
 // Import WSL utilities
 use super::super::wsl_utils;
 // Import session helpers
@@ -48,6 +51,9 @@ pub struct CodexPromptGitRecord {
     pub commit_before: String,
     pub commit_after: Option<String>,
     pub timestamp: String,
+    /// Commits are hidden snapshot refs (auto_commit_mode = on-rewind-request)
+    #[serde(default)]
+    pub snapshot: bool,
 }
 
 /// Collection of Git records for a Codex session
@@ -59,6 +65,18 @@ pub struct CodexGitRecords {
     pub records: Vec<CodexPromptGitRecord>,
 }
 
+impl CodexGitRecords {
+    /// Index for the next prompt: one past the highest recorded index, which
+    /// stays unique after compaction has removed records
+    pub fn next_prompt_index(&self) -> usize {
+        self.records
+            .iter()
+            .map(|r| r.prompt_index + 1)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
 // ============================================================================
 // Git Records Directory Management
 // ============================================================================
@@ -153,6 +171,91 @@ pub fn truncate_codex_git_records(session_id: &str, prompt_index: usize) -> Resu
     Ok(())
 }
 
+/// Point git records of a moved project at its new path
+/// Returns the number of record files updated
+pub fn relink_codex_git_records(old_path: &str, new_path: &str) -> Result<usize, String> {
+    let records_dir = get_codex_git_records_dir()?;
+    let old_normalized = project_path_key(old_path);
+    let mut updated = 0;
+
+    let entries = fs::read_dir(&records_dir)
+        .map_err(|e| format!("Failed to read git records directory: {}", e))?;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("json") {
+            continue;
+        }
+        let session_id = match path.file_stem().and_then(|s| s.to_str()) {
+            Some(id) => id.to_string(),
+            None => continue,
+        };
+
+        let mut git_records = match load_codex_git_records(&session_id) {
+            Ok(records) => records,
+            Err(e) => {
+                log::warn!("[Codex] Skipping unreadable git records {}: {}", session_id, e);
+                continue;
+            }
+        };
+        if project_path_key(&git_records.project_path) == old_normalized {
+            git_records.project_path = new_path.to_string();
+            save_codex_git_records(&session_id, &git_records)?;
+            updated += 1;
+        }
+    }
+
+    Ok(updated)
+}
+
+/// Keeps the latest record of each prompt index, sorted by index
+fn dedupe_by_prompt_index(mut records: Vec<CodexPromptGitRecord>) -> Vec<CodexPromptGitRecord> {
+    // Stable sort keeps the latest record last among equal indices
+    records.sort_by_key(|r| r.prompt_index);
+    let mut compacted: Vec<CodexPromptGitRecord> = Vec::with_capacity(records.len());
+    for record in records {
+        match compacted.last_mut() {
+            Some(last) if last.prompt_index == record.prompt_index => *last = record,
+            _ => compacted.push(record),
+        }
+    }
+    compacted
+}
+
+/// Drop records whose `commit_before` is gone from the repository (e.g. after a
+/// rebase or gc), keep one record per prompt index in index order, and rewrite the file.
+/// Returns the number of records removed.
+#[tauri::command]
+pub async fn compact_codex_git_records(session_id: String) -> Result<usize, String> {
+    let mut git_records = load_codex_git_records(&session_id)?;
+    if git_records.records.is_empty() {
+        return Ok(0);
+    }
+    if !simple_git::is_git_repo(&git_records.project_path) {
+        return Err(format!(
+            "Project of session {} is not a git repository: {}",
+            session_id, git_records.project_path
+        ));
+    }
+
+    let original_count = git_records.records.len();
+    let project_path = git_records.project_path.clone();
+    git_records
+        .records
+        .retain(|r| simple_git::commit_exists(&project_path, &r.commit_before));
+
+    git_records.records = dedupe_by_prompt_index(std::mem::take(&mut git_records.records));
+
+    let pruned = original_count - git_records.records.len();
+    save_codex_git_records(&session_id, &git_records)?;
+    log::info!(
+        "[Codex Rewind] Compacted git records of session {}: {} pruned, {} kept",
+        session_id,
+        pruned,
+        git_records.records.len()
+    );
+    Ok(pruned)
+}
+
 // ============================================================================
 // Prompt Extraction
 // ============================================================================
@@ -458,6 +561,23 @@ pub fn truncate_codex_session_to_prompt(session_id: &str, prompt_index: usize) -
 // Prompt Recording (for rewind tracking)
 // ============================================================================
 
+/// Hidden ref that keeps a Codex snapshot commit reachable
+fn codex_snapshot_ref(session_id: &str, prompt_index: usize, phase: &str) -> String {
+    format!("refs/claude-workbench/codex/{}/{}-{}", session_id, prompt_index, phase)
+}
+
+/// Restore the working tree to the state before a prompt.
+/// Snapshot records are restored without moving HEAD; commit records use a hard reset.
+fn restore_codex_record(project_path: &str, record: &CodexPromptGitRecord) -> Result<(), String> {
+    if record.snapshot {
+        simple_git::git_restore_snapshot(project_path, &record.commit_before)
+            .map_err(|e| format!("Failed to restore snapshot: {}", e))
+    } else {
+        simple_git::git_reset_hard(project_path, &record.commit_before)
+            .map_err(|e| format!("Failed to reset code: {}", e))
+    }
+}
+
 /// Record a Codex prompt being sent (called before execution)
 #[tauri::command]
 pub async fn record_codex_prompt_sent(
@@ -471,11 +591,13 @@ pub async fn record_codex_prompt_sent(
     let execution_config = load_execution_config()
         .map_err(|e| format!("Failed to load execution config: {}", e))?;
 
-    if execution_config.disable_rewind_git_operations {
+    if execution_config.disable_rewind_git_operations
+        || execution_config.auto_commit_mode == AutoCommitMode::Off
+    {
         log::info!("[Codex Record] Git operations disabled, skipping git record");
         // Still need to return a prompt_index for tracking purposes
         let git_records = load_codex_git_records(&session_id)?;
-        let prompt_index = git_records.records.len();
+        let prompt_index = git_records.next_prompt_index();
         log::info!("[Codex Record] Returning prompt index #{} (no git record)", prompt_index);
         return Ok(prompt_index);
     }
@@ -484,27 +606,36 @@ pub async fn record_codex_prompt_sent(
     simple_git::ensure_git_repo(&project_path)
         .map_err(|e| format!("Failed to ensure Git repo: {}", e))?;
 
-    // Get current commit (state before prompt execution)
-    let commit_before = simple_git::git_current_commit(&project_path)
-        .map_err(|e| format!("Failed to get current commit: {}", e))?;
-
     // Load existing records
     let mut git_records = load_codex_git_records(&session_id)?;
+    let prompt_index = git_records.next_prompt_index();
+    let use_snapshot = execution_config.auto_commit_mode == AutoCommitMode::OnRewindRequest;
+
+    // Get current state before prompt execution (commit, or hidden snapshot ref)
+    let commit_before = if use_snapshot {
+        simple_git::git_snapshot(
+            &project_path,
+            &codex_snapshot_ref(&session_id, prompt_index, "before"),
+            &format!("[Codex] Snapshot before prompt #{}", prompt_index),
+        )
+        .map_err(|e| format!("Failed to snapshot working tree: {}", e))?
+    } else {
+        simple_git::git_current_commit(&project_path)
+            .map_err(|e| format!("Failed to get current commit: {}", e))?
+    };
 
     // Update project path if needed
     if git_records.project_path.is_empty() {
         git_records.project_path = project_path.clone();
     }
 
-    // Calculate prompt index
-    let prompt_index = git_records.records.len();
-
     // Create new record
     let record = CodexPromptGitRecord {
         prompt_index,
         commit_before: commit_before.clone(),
         commit_after: None,
         timestamp: Utc::now().to_rfc3339(),
+        snapshot: use_snapshot,
     };
 
     git_records.records.push(record);
@@ -530,29 +661,41 @@ pub async fn record_codex_prompt_completed(
     let execution_config = load_execution_config()
         .map_err(|e| format!("Failed to load execution config: {}", e))?;
 
-    if execution_config.disable_rewind_git_operations {
+    if execution_config.disable_rewind_git_operations
+        || execution_config.auto_commit_mode == AutoCommitMode::Off
+    {
         log::info!("[Codex Record] Git operations disabled, skipping git commit and record update");
         return Ok(());
     }
 
-    // Auto-commit any changes made by AI
-    let commit_message = format!("[Codex] After prompt #{}", prompt_index);
-    match simple_git::git_commit_changes(&project_path, &commit_message) {
-        Ok(true) => {
-            log::info!("[Codex Record] Auto-committed changes after prompt #{}", prompt_index);
-        }
-        Ok(false) => {
-            log::debug!("[Codex Record] No changes to commit after prompt #{}", prompt_index);
-        }
-        Err(e) => {
-            log::warn!("[Codex Record] Failed to auto-commit: {}", e);
-            // Continue anyway
+    let commit_after = if execution_config.auto_commit_mode == AutoCommitMode::OnRewindRequest {
+        // Snapshot only: keep the user's history free of per-prompt commits
+        simple_git::git_snapshot(
+            &project_path,
+            &codex_snapshot_ref(&session_id, prompt_index, "after"),
+            &format!("[Codex] Snapshot after prompt #{}", prompt_index),
+        )
+        .map_err(|e| format!("Failed to snapshot working tree: {}", e))?
+    } else {
+        // Auto-commit any changes made by AI
+        let commit_message = format!("[Codex] After prompt #{}", prompt_index);
+        match simple_git::git_commit_changes(&project_path, &commit_message) {
+            Ok(true) => {
+                log::info!("[Codex Record] Auto-committed changes after prompt #{}", prompt_index);
+            }
+            Ok(false) => {
+                log::debug!("[Codex Record] No changes to commit after prompt #{}", prompt_index);
+            }
+            Err(e) => {
+                log::warn!("[Codex Record] Failed to auto-commit: {}", e);
+                // Continue anyway
+            }
         }
-    }
 
-    // Get current commit (state after AI completion)
-    let commit_after = simple_git::git_current_commit(&project_path)
-        .map_err(|e| format!("Failed to get current commit: {}", e))?;
+        // Get current commit (state after AI completion)
+        simple_git::git_current_commit(&project_path)
+            .map_err(|e| format!("Failed to get current commit: {}", e))?
+    };
 
     // Update the record
     let mut git_records = load_codex_git_records(&session_id)?;
@@ -575,16 +718,26 @@ pub async fn record_codex_prompt_completed(
 // ============================================================================
 
 /// Revert Codex session to a specific prompt
+/// With `fail_if_dirty`, code rewinds are refused on a dirty tree instead of auto-stashing.
+/// With `expected_mtime`, the rewind is refused if the session file changed since it was loaded.
 #[tauri::command]
 pub async fn revert_codex_to_prompt(
     session_id: String,
     project_path: String,
     prompt_index: usize,
     mode: RewindMode,
+    fail_if_dirty: Option<bool>,
+    expected_mtime: Option<u64>,
 ) -> Result<String, String> {
     log::info!("[Codex Rewind] Reverting session {} to prompt #{} with mode: {:?}",
         session_id, prompt_index, mode);
 
+    // Refuse before touching code or history if another window rewrote the session
+    if expected_mtime.is_some() {
+        let session_file = resolve_session_file(&session_id, "codex", None)?;
+        ensure_session_unmodified(&session_file, expected_mtime)?;
+    }
+
     // Load execution config to check if Git operations are disabled
     let execution_config = load_execution_config()
         .map_err(|e| format!("Failed to load execution config: {}", e))?;
@@ -619,6 +772,17 @@ pub async fn revert_codex_to_prompt(
                     prompt_index
                 ));
             }
+            // Refuse instead of burying in-progress work in an auto-stash
+            if fail_if_dirty.unwrap_or(false) {
+                let dirty_files = simple_git::git_status(&project_path)?;
+                if !dirty_files.is_empty() {
+                    return Err(format!(
+                        "无法回滚代码：工作区有未提交的更改（{} 个文件）：\n{}",
+                        dirty_files.len(),
+                        dirty_files.join("\n")
+                    ));
+                }
+            }
         }
         RewindMode::ConversationOnly => {}
     }
@@ -649,9 +813,8 @@ pub async fn revert_codex_to_prompt(
                 &format!("Auto-stash before Codex code revert to prompt #{}", prompt_index))
                 .map_err(|e| format!("Failed to stash changes: {}", e))?;
 
-            // Reset to commit before this prompt
-            simple_git::git_reset_hard(&project_path, &record.commit_before)
-                .map_err(|e| format!("Failed to reset code: {}", e))?;
+            // Reset to commit (or snapshot) before this prompt
+            restore_codex_record(&project_path, record)?;
 
             log::info!("[Codex Rewind] Successfully reverted code to prompt #{}", prompt_index);
         }
@@ -667,8 +830,7 @@ pub async fn revert_codex_to_prompt(
                 .map_err(|e| format!("Failed to stash changes: {}", e))?;
 
             // Reset code
-            simple_git::git_reset_hard(&project_path, &record.commit_before)
-                .map_err(|e| format!("Failed to reset code: {}", e))?;
+            restore_codex_record(&project_path, record)?;
 
             // Truncate session
             truncate_codex_session_to_prompt(&session_id, prompt_index)?;
@@ -685,3 +847,39 @@ pub async fn revert_codex_to_prompt(
     // Return the prompt text for restoring to input
     Ok(prompt.text.clone())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(prompt_index: usize, commit_before: &str) -> CodexPromptGitRecord {
+        CodexPromptGitRecord {
+            prompt_index,
+            commit_before: commit_before.to_string(),
+            commit_after: None,
+            timestamp: String::new(),
+            snapshot: false,
+        }
+    }
+
+    #[test]
+    fn next_prompt_index_stays_unique_after_compaction() {
+        let mut git_records = CodexGitRecords {
+            records: vec![record(0, "a"), record(1, "b"), record(1, "c"), record(2, "d")],
+            ..Default::default()
+        };
+        // Prompt #0's commit is gone and prompt #1 was recorded twice
+        git_records.records.retain(|r| r.commit_before != "a");
+        git_records.records = dedupe_by_prompt_index(std::mem::take(&mut git_records.records));
+        assert_eq!(git_records.records.len(), 2);
+
+        let prompt_index = git_records.next_prompt_index();
+        assert_eq!(prompt_index, 3);
+        git_records.records.push(record(prompt_index, "e"));
+
+        let found = git_records.records.iter().find(|r| r.prompt_index == 2).unwrap();
+        assert_eq!(found.commit_before, "d");
+        let found = git_records.records.iter().find(|r| r.prompt_index == 3).unwrap();
+        assert_eq!(found.commit_before, "e");
+    }
+}
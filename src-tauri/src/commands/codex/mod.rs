@@ -8,12 +8,24 @@
  * - session.rs: Session lifecycle management (execute, resume, cancel, list, delete)
  * - git_ops.rs: Git operations for rewind functionality (records, truncate, revert)
  * - config.rs: Configuration management (availability, paths, mode, providers)
+ * - terminal.rs: Opening an OS terminal in the project with Codex ready
+ * - run_command.rs: Recording the exact command line of each run
+ * - activity.rs: Summarizing files read/written and commands run in a session
+ * - force_kill.rs: Killing untracked Codex processes found in the OS process list
+ * - output_schema.rs: Checking sample output against an `--output-schema` schema
+ * - storage_migration.rs: Moving sessions from legacy layouts into YYYY/MM/DD folders
  */
 
+pub mod activity;
 pub mod config;
+pub mod force_kill;
 pub mod git_ops;
+pub mod output_schema;
 pub mod session;
+pub mod run_command;
 pub mod session_converter;
+pub mod storage_migration;
+pub mod terminal;
 
 // ============================================================================
 // Re-export Types (allow unused for API compatibility)
@@ -24,8 +36,13 @@ pub mod session_converter;
 pub use session::{
     CodexExecutionMode,
     CodexExecutionOptions,
+    CodexResumeByQuery,
     CodexSession,
+    CodexSessionDay,
+    CodexModelChange,
     CodexProcessState,
+    CodexSandboxOptions,
+    PendingCodexPlan,
 };
 
 // Git operations types
@@ -40,9 +57,14 @@ pub use git_ops::{
 // Config types
 #[allow(unused_imports)]
 pub use config::{
+    BinariesJsonRepair,
+    BinariesJsonStatus,
     CodexAvailability,
+    CodexConfigFileHealth,
+    CodexConfigHealth,
     CodexModeInfo,
     CodexProviderConfig,
+    CodexProviderSwitchPreview,
     CurrentCodexConfig,
 };
 
@@ -61,12 +83,26 @@ pub use session::{
     execute_codex,
     resume_codex,
     resume_last_codex,
+    resume_last_codex_for_project,
+    resume_codex_by_query,
+    can_resume_codex,
     cancel_codex,
+    approve_codex_plan,
     list_codex_sessions,
+    list_codex_sessions_grouped,
     load_codex_session_history,
+    get_codex_session_model_timeline,
+    read_codex_output_file,
+    get_codex_running_output,
     delete_codex_session,
 };
 
+pub use activity::get_codex_session_activity;
+pub use force_kill::force_kill_codex_session;
+pub use output_schema::validate_output_against_schema;
+pub use run_command::get_codex_run_command;
+pub use storage_migration::migrate_session_storage;
+
 // ============================================================================
 // Re-export Tauri Commands - Git Operations / Rewind
 // ============================================================================
@@ -77,6 +113,7 @@ pub use git_ops::{
     record_codex_prompt_sent,
     record_codex_prompt_completed,
     revert_codex_to_prompt,
+    compact_codex_git_records,
 };
 
 // ============================================================================
@@ -90,6 +127,8 @@ pub use config::{
     clear_custom_codex_path,
     get_codex_mode_config,
     set_codex_mode_config,
+    validate_binaries_json,
+    repair_binaries_json,
 };
 
 // ============================================================================
@@ -99,11 +138,17 @@ pub use config::{
 pub use config::{
     get_codex_provider_presets,
     get_current_codex_config,
+    get_active_codex_provider,
+    dedupe_codex_providers,
     switch_codex_provider,
+    preview_codex_provider_switch,
     add_codex_provider_config,
+    clone_codex_provider,
     update_codex_provider_config,
     delete_codex_provider_config,
     clear_codex_provider_config,
+    ensure_codex_config_exists,
+    get_codex_config_health,
     test_codex_provider_connection,
 };
 
@@ -115,8 +160,15 @@ pub use session_converter::{
     convert_session,
     convert_claude_to_codex,
     convert_codex_to_claude,
+    convert_session_between_tools,
 };
 
+// ============================================================================
+// Re-export Tauri Commands - Terminal
+// ============================================================================
+
+pub use terminal::open_codex_terminal;
+
 // ============================================================================
 // Re-export Helper Functions (for internal use by submodules)
 // ============================================================================
@@ -139,6 +191,7 @@ pub use git_ops::{
     load_codex_git_records,
     save_codex_git_records,
     truncate_codex_git_records,
+    relink_codex_git_records,
     extract_codex_prompts,
     truncate_codex_session_to_prompt,
 };
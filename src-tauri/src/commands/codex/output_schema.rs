@@ -0,0 +1,122 @@
+/**
+ * Codex Output Schema Check
+ *
+ * Validates a sample response against the JSON Schema passed to Codex with
+ * `--output-schema`, so schema mistakes show up before a run is spent.
+ * Validation is done by the `jsonschema` crate; a schema it cannot compile is
+ * reported as an error rather than as a violation.
+ */
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+
+/// A place where the sample does not conform to the schema
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaViolation {
+    /// JSON Pointer into the sample, "" for the root value
+    pub path: String,
+    pub message: String,
+}
+
+/// Result of `validate_output_against_schema`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchemaValidationResult {
+    pub valid: bool,
+    pub violations: Vec<SchemaViolation>,
+}
+
+/// Checks `sample_json` against `schema`, given either as inline JSON or as the
+/// path of the schema file (the same value `output_schema` takes)
+#[tauri::command]
+pub async fn validate_output_against_schema(
+    schema: String,
+    sample_json: String,
+) -> Result<SchemaValidationResult, String> {
+    let schema = load_schema(&schema)?;
+    let sample: Value = serde_json::from_str(&sample_json)
+        .map_err(|e| format!("Failed to parse sample JSON: {}", e))?;
+
+    let violations = validate_against_schema(&schema, &sample)?;
+    log::info!(
+        "[Codex] Output schema check found {} violation(s)",
+        violations.len()
+    );
+    Ok(SchemaValidationResult {
+        valid: violations.is_empty(),
+        violations,
+    })
+}
+
+fn load_schema(schema: &str) -> Result<Value, String> {
+    let trimmed = schema.trim();
+    let content = if trimmed.starts_with('{') {
+        trimmed.to_string()
+    } else {
+        fs::read_to_string(trimmed)
+            .map_err(|e| format!("Failed to read schema file {}: {}", trimmed, e))?
+    };
+    let value: Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse schema JSON: {}", e))?;
+    if !value.is_object() {
+        return Err("Schema must be a JSON object".to_string());
+    }
+    Ok(value)
+}
+
+/// Returns every violation of `value` against `schema`
+pub fn validate_against_schema(schema: &Value, value: &Value) -> Result<Vec<SchemaViolation>, String> {
+    let validator =
+        jsonschema::validator_for(schema).map_err(|e| format!("Invalid JSON Schema: {}", e))?;
+    Ok(validator
+        .iter_errors(value)
+        .map(|error| SchemaViolation {
+            path: error.instance_path.to_string(),
+            message: error.to_string(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn reports_violations_with_paths() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "status": { "enum": ["ok", "error"] },
+                "files": { "type": "array", "items": { "$ref": "#/$defs/file" } }
+            },
+            "required": ["status", "files"],
+            "additionalProperties": false,
+            "$defs": {
+                "file": {
+                    "type": "object",
+                    "properties": { "path": { "type": "string", "minLength": 1 } },
+                    "required": ["path"]
+                }
+            }
+        });
+
+        let valid = json!({ "status": "ok", "files": [{ "path": "a.rs" }] });
+        assert!(validate_against_schema(&schema, &valid).unwrap().is_empty());
+
+        let invalid = json!({ "status": "done", "files": [{ "path": "" }, 3], "extra": true });
+        let mut paths: Vec<String> = validate_against_schema(&schema, &invalid)
+            .unwrap()
+            .into_iter()
+            .map(|v| v.path)
+            .collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            vec!["", "/files/0/path", "/files/1", "/status"]
+        );
+
+        assert!(validate_against_schema(&json!({ "type": "nope" }), &json!(1)).is_err());
+    }
+}
@@ -0,0 +1,116 @@
+/**
+ * Codex Run Command Recording
+ *
+ * Records the exact command line each Codex run was started with, so
+ * "works in the terminal but not in the app" reports can be reproduced.
+ * Records live in ~/.codex/run-commands/<session_id>.json, one entry per run
+ * (the initial execution plus every resume). Environment values are redacted.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use tokio::process::Command;
+
+/// Placeholder for environment variable values (they may hold API keys)
+const REDACTED_VALUE: &str = "<redacted>";
+
+/// The command line of a single Codex run
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexRunCommand {
+    pub program: String,
+    pub args: Vec<String>,
+    pub working_dir: Option<String>,
+    /// Environment variables set explicitly for the run, as `NAME=<redacted>`
+    pub env: Vec<String>,
+    /// Shell-quoted command line, ready to paste into a terminal
+    pub command_line: String,
+    /// Whether the prompt was passed on stdin (`-`)
+    pub prompt_via_stdin: bool,
+    pub recorded_at: String,
+}
+
+/// Captures the program, arguments and working directory of a built command
+pub fn describe_codex_command(cmd: &Command) -> CodexRunCommand {
+    let std_cmd = cmd.as_std();
+    let program = std_cmd.get_program().to_string_lossy().to_string();
+    let args: Vec<String> = std_cmd
+        .get_args()
+        .map(|arg| arg.to_string_lossy().to_string())
+        .collect();
+    let env = std_cmd
+        .get_envs()
+        .filter(|(_, value)| value.is_some())
+        .map(|(name, _)| format!("{}={}", name.to_string_lossy(), REDACTED_VALUE))
+        .collect();
+    let command_line = std::iter::once(&program)
+        .chain(args.iter())
+        .map(|part| quote_arg(part))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    CodexRunCommand {
+        prompt_via_stdin: args.last().map(|a| a == "-").unwrap_or(false),
+        working_dir: std_cmd
+            .get_current_dir()
+            .map(|dir| dir.to_string_lossy().to_string()),
+        program,
+        args,
+        env,
+        command_line,
+        recorded_at: chrono::Utc::now().to_rfc3339(),
+    }
+}
+
+/// Appends a run to the session's record file
+pub fn save_codex_run_command(session_id: &str, run: &CodexRunCommand) -> Result<(), String> {
+    let path = get_run_command_path(session_id)?;
+    let mut runs = load_codex_run_commands(session_id)?;
+    runs.push(run.clone());
+
+    let content = serde_json::to_string_pretty(&runs)
+        .map_err(|e| format!("Failed to serialize run commands: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write run commands: {}", e))
+}
+
+fn load_codex_run_commands(session_id: &str) -> Result<Vec<CodexRunCommand>, String> {
+    let path = get_run_command_path(session_id)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read run commands: {}", e))?;
+    serde_json::from_str(&content).map_err(|e| format!("Failed to parse run commands: {}", e))
+}
+
+fn get_run_command_path(session_id: &str) -> Result<PathBuf, String> {
+    let dir = dirs::home_dir()
+        .ok_or_else(|| "Failed to get home directory".to_string())?
+        .join(".codex")
+        .join("run-commands");
+    if !dir.exists() {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Failed to create run commands directory: {}", e))?;
+    }
+    Ok(dir.join(format!("{}.json", session_id)))
+}
+
+fn quote_arg(arg: &str) -> String {
+    let is_plain = !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./:=@,+%".contains(c));
+    if is_plain {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Returns the command lines a Codex session was run with, oldest first
+#[tauri::command]
+pub async fn get_codex_run_command(session_id: String) -> Result<Vec<CodexRunCommand>, String> {
+    log::info!("[Codex] Loading run commands for session: {}", session_id);
+    load_codex_run_commands(&session_id)
+}
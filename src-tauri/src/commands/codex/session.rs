@@ -9,7 +9,9 @@
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::io::{AsyncBufReadExt, BufReader};
@@ -17,12 +19,20 @@ use tokio::process::{Child, Command};
 use tokio::sync::Mutex;
 
 // Import platform-specific utilities for window hiding
-use crate::commands::claude::apply_no_window_async;
-use crate::claude_binary::detect_binary_for_tool;
+use crate::commands::claude::{apply_no_window_async, ProjectPathMatcher};
+use crate::claude_binary::{compare_versions, detect_binary_for_tool};
+use crate::process::OutputBuffer;
 // Import WSL utilities for Windows + WSL Codex support
+use super::super::execution_timeout::spawn_timeout_watchdog;
+use super::super::network_mode::offline_env_vars;
+use super::super::run_error::{record_run_outcome, LastRunErrors, StderrTail};
 use super::super::wsl_utils;
 // Import config module for sessions directory
-use super::config::get_codex_sessions_dir;
+use super::config::{
+    check_codex_availability, get_codex_sessions_dir, get_current_codex_config,
+    test_codex_provider_connection,
+};
+use super::run_command::{describe_codex_command, save_codex_run_command};
 
 // ============================================================================
 // Type Definitions
@@ -46,6 +56,28 @@ impl Default for CodexExecutionMode {
     }
 }
 
+impl CodexExecutionMode {
+    /// The sandbox permissions this preset stands for
+    /// DangerFullAccess additionally runs without any sandbox.
+    pub fn sandbox(&self) -> CodexSandboxOptions {
+        match self {
+            Self::ReadOnly => CodexSandboxOptions { allow_writes: false, allow_network: false },
+            Self::FullAuto => CodexSandboxOptions { allow_writes: true, allow_network: false },
+            Self::DangerFullAccess => CodexSandboxOptions { allow_writes: true, allow_network: true },
+        }
+    }
+}
+
+/// Fine-grained sandbox permissions, as an alternative to the mode presets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexSandboxOptions {
+    /// Allow edits inside the workspace
+    pub allow_writes: bool,
+    /// Allow outbound network access from sandboxed commands (requires writes)
+    pub allow_network: bool,
+}
+
 /// Codex execution options
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -60,6 +92,10 @@ pub struct CodexExecutionOptions {
     #[serde(default)]
     pub mode: CodexExecutionMode,
 
+    /// Sandbox permissions; when set, they take precedence over `mode`
+    /// Unlike DangerFullAccess, writes + network keep the workspace sandbox and approvals.
+    pub sandbox: Option<CodexSandboxOptions>,
+
     /// Model to use (e.g., "gpt-5.1-codex-max")
     pub model: Option<String>,
 
@@ -86,12 +122,77 @@ pub struct CodexExecutionOptions {
     /// Resume last session
     #[serde(default)]
     pub resume_last: bool,
+
+    /// Two-phase mode: plan in read-only first, execute after approval
+    #[serde(default)]
+    pub two_phase: bool,
+
+    /// Directory Codex runs in, if different from the project (git) root
+    /// Relative paths resolve against `project_path`; git records keep tracking `project_path`
+    pub working_dir: Option<String>,
+
+    /// Reasoning effort ("minimal", "low", "medium", "high")
+    /// Only applied to new sessions; ignored on resume
+    pub reasoning_effort: Option<String>,
+
+    /// Named config.toml profile passed as `--profile`
+    /// Only applied to new sessions; ignored on resume
+    pub profile: Option<String>,
+
+    /// Image files attached to the prompt, passed as `--image`
+    /// Relative paths resolve against the working directory; only applied to new sessions
+    pub images: Option<Vec<String>>,
+
+    /// WSL distro for this run (Windows only), overriding the configured distro
+    /// Setting it runs Codex through WSL even when the global mode is native
+    pub wsl_distro: Option<String>,
+
+    /// Convert CRLF/CR in the prompt to LF before writing it to stdin
+    #[serde(default = "default_normalize_line_endings")]
+    pub normalize_line_endings: bool,
+
+    /// Test the active provider's base_url before starting and abort if it is unreachable
+    /// Off by default since it adds a request to every run
+    #[serde(default)]
+    pub check_provider_before_run: bool,
+}
+
+impl CodexExecutionOptions {
+    /// True when the run is sandboxed read-only, the only mode in which the
+    /// sandbox refuses commands and edits on its own
+    pub fn is_read_only_sandbox(&self) -> bool {
+        !self.sandbox.unwrap_or_else(|| self.mode.sandbox()).allow_writes
+    }
 }
 
 fn default_json_mode() -> bool {
     true
 }
 
+fn default_normalize_line_endings() -> bool {
+    true
+}
+
+/// Converts CRLF and lone CR line endings to LF
+/// Some CLIs treat the CR of a Windows line ending as part of the prompt text.
+pub fn normalize_line_endings(text: &str) -> String {
+    text.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// A point in a session where the active model changed
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexModelChange {
+    /// Index of the first prompt that ran on this model
+    pub prompt_index: usize,
+
+    /// Model name
+    pub model: String,
+
+    /// Timestamp of the event that reported the model (ISO string)
+    pub timestamp: Option<String>,
+}
+
 /// Codex session metadata
 #[derive(Debug, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -124,10 +225,49 @@ pub struct CodexSession {
     pub last_message_timestamp: Option<String>,
 }
 
+/// Sessions stored under one sessions/YYYY/MM/DD directory
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexSessionDay {
+    /// "YYYY-MM-DD", taken from the directory names
+    pub date: String,
+    pub sessions: Vec<CodexSession>,
+}
+
+/// Result of `resume_codex_by_query`
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexResumeByQuery {
+    /// Session that was resumed; None when the query was ambiguous
+    pub resumed_session_id: Option<String>,
+    /// Equally good matches to choose from when nothing was resumed
+    pub candidates: Vec<CodexSession>,
+}
+
+/// A plan produced by the read-only phase of a two-phase run, awaiting approval
+#[derive(Debug, Clone)]
+pub struct PendingCodexPlan {
+    /// Options for the execution phase (mode already elevated)
+    pub options: CodexExecutionOptions,
+
+    /// Codex thread ID to resume (from thread.started)
+    pub thread_id: String,
+
+    /// Plan text (last agent message of the planning phase)
+    pub plan: String,
+}
+
 /// Global state to track Codex processes
 pub struct CodexProcessState {
     pub processes: Arc<Mutex<HashMap<String, Child>>>,
     pub last_session_id: Arc<Mutex<Option<String>>>,
+    /// Two-phase plans awaiting approval, keyed by process session ID
+    pub pending_plans: Arc<Mutex<HashMap<String, PendingCodexPlan>>>,
+    /// Output lines emitted so far by each running process, keyed by process session ID
+    /// Lets a reloaded frontend rehydrate an in-progress transcript. Capped, oldest lines dropped first.
+    pub output_buffers: Arc<Mutex<HashMap<String, OutputBuffer>>>,
+    /// Exit code and stderr tail of each process session's last failed run
+    pub last_errors: LastRunErrors,
 }
 
 impl Default for CodexProcessState {
@@ -135,6 +275,9 @@ impl Default for CodexProcessState {
         Self {
             processes: Arc::new(Mutex::new(HashMap::new())),
             last_session_id: Arc::new(Mutex::new(None)),
+            pending_plans: Arc::new(Mutex::new(HashMap::new())),
+            output_buffers: Arc::new(Mutex::new(HashMap::new())),
+            last_errors: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
@@ -143,6 +286,24 @@ impl Default for CodexProcessState {
 // Core Execution Methods
 // ============================================================================
 
+/// Pre-flight for `check_provider_before_run`: tests the base_url of the active
+/// config.toml; runs without a custom provider pass through
+async fn check_codex_provider_reachable() -> Result<(), String> {
+    let current = get_current_codex_config().await?;
+    let base_url = match current.base_url {
+        Some(url) => url,
+        None => {
+            log::info!("[Codex] No custom provider base_url, skipping pre-flight check");
+            return Ok(());
+        }
+    };
+
+    test_codex_provider_connection(base_url.clone(), current.api_key)
+        .await
+        .map(|result| log::info!("[Codex] Provider pre-flight: {}", result))
+        .map_err(|e| format!("Provider {} is unreachable, run not started: {}", base_url, e))
+}
+
 /// Executes a Codex task in non-interactive mode with streaming output
 #[tauri::command]
 pub async fn execute_codex(
@@ -151,11 +312,95 @@ pub async fn execute_codex(
 ) -> Result<(), String> {
     log::info!("execute_codex called with options: {:?}", options);
 
+    if options.check_provider_before_run {
+        check_codex_provider_reachable().await?;
+    }
+
+    if options.two_phase {
+        // Phase 1: plan in read-only mode, keep the requested options for phase 2
+        let mut plan_options = options.clone();
+        plan_options.mode = CodexExecutionMode::ReadOnly;
+        plan_options.sandbox = None;
+
+        let mut execute_options = options.clone();
+        let requested = execute_options.sandbox.unwrap_or_else(|| execute_options.mode.sandbox());
+        if !requested.allow_writes {
+            execute_options.mode = CodexExecutionMode::FullAuto;
+            execute_options.sandbox = None;
+        }
+
+        let (cmd, prompt) = build_codex_command(&plan_options, false, None)?;
+        return execute_codex_process(
+            cmd,
+            prompt,
+            options.project_path.clone(),
+            Some(execute_options),
+            None,
+            plan_options.is_read_only_sandbox(),
+            app_handle,
+        )
+        .await;
+    }
+
     // Build codex exec command
     let (cmd, prompt) = build_codex_command(&options, false, None)?;
 
+    // The -o file is read back on completion so the UI can show the structured result
+    let output_file = options
+        .output_file
+        .as_deref()
+        .map(|file| {
+            resolve_codex_output_path(file, &codex_working_dir(&options), options.wsl_distro.as_deref())
+        });
+
     // Execute and stream output
-    execute_codex_process(cmd, prompt, options.project_path.clone(), app_handle).await
+    execute_codex_process(
+        cmd,
+        prompt,
+        options.project_path.clone(),
+        None,
+        output_file,
+        options.is_read_only_sandbox(),
+        app_handle,
+    ).await
+}
+
+/// Approves the plan of a two-phase run and resumes the same Codex thread
+/// with the elevated execution mode
+#[tauri::command]
+pub async fn approve_codex_plan(
+    session_id: String,
+    app_handle: AppHandle,
+) -> Result<(), String> {
+    log::info!("approve_codex_plan called for session: {}", session_id);
+
+    let state: tauri::State<'_, CodexProcessState> = app_handle.state();
+    let pending = state
+        .pending_plans
+        .lock()
+        .await
+        .remove(&session_id)
+        .ok_or_else(|| format!("No pending plan found for session: {}", session_id))?;
+
+    let mut options = pending.options;
+    options.prompt = "The plan is approved. Proceed to implement it now.".to_string();
+
+    log::info!(
+        "[Codex] Executing approved plan for thread {} with mode {:?}",
+        pending.thread_id,
+        options.mode
+    );
+
+    let (cmd, prompt) = build_codex_command(&options, true, Some(&pending.thread_id))?;
+    execute_codex_process(
+        cmd,
+        prompt,
+        options.project_path.clone(),
+        None,
+        None,
+        options.is_read_only_sandbox(),
+        app_handle,
+    ).await
 }
 
 /// Resumes a previous Codex session
@@ -171,7 +416,15 @@ pub async fn resume_codex(
     let (cmd, prompt) = build_codex_command(&options, true, Some(&session_id))?;
 
     // Execute and stream output
-    execute_codex_process(cmd, prompt, options.project_path.clone(), app_handle).await
+    execute_codex_process(
+        cmd,
+        prompt,
+        options.project_path.clone(),
+        None,
+        None,
+        options.is_read_only_sandbox(),
+        app_handle,
+    ).await
 }
 
 /// Resumes the last Codex session
@@ -186,7 +439,111 @@ pub async fn resume_last_codex(
     let (cmd, prompt) = build_codex_command(&options, true, Some("--last"))?;
 
     // Execute and stream output
-    execute_codex_process(cmd, prompt, options.project_path.clone(), app_handle).await
+    execute_codex_process(
+        cmd,
+        prompt,
+        options.project_path.clone(),
+        None,
+        None,
+        options.is_read_only_sandbox(),
+        app_handle,
+    ).await
+}
+
+/// Resumes the most recently updated session of `options.project_path`
+/// Unlike `resume_last_codex`, sessions of other projects are never picked.
+/// Returns the ID of the resumed session.
+#[tauri::command]
+pub async fn resume_last_codex_for_project(
+    options: CodexExecutionOptions,
+    app_handle: AppHandle,
+) -> Result<String, String> {
+    log::info!("resume_last_codex_for_project called: {}", options.project_path);
+    if options.project_path.trim().is_empty() {
+        return Err("Project path cannot be empty".to_string());
+    }
+
+    let sessions = list_codex_sessions(Some(options.project_path.clone())).await?;
+    let session_id = match sessions.into_iter().max_by_key(|session| session.updated_at) {
+        Some(session) => session.id,
+        None => {
+            return Err(format!(
+                "No Codex session found for project {}",
+                options.project_path
+            ))
+        }
+    };
+
+    log::info!(
+        "[Codex] Resuming latest session {} of {}",
+        session_id,
+        options.project_path
+    );
+    resume_codex(session_id.clone(), options, app_handle).await?;
+    Ok(session_id)
+}
+
+/// Resumes the session whose first message best matches `query`
+/// Only sessions of `options.project_path` are searched. When several sessions
+/// match equally well, nothing is resumed and they are returned as candidates.
+#[tauri::command]
+pub async fn resume_codex_by_query(
+    query: String,
+    options: CodexExecutionOptions,
+    app_handle: AppHandle,
+) -> Result<CodexResumeByQuery, String> {
+    log::info!("resume_codex_by_query called: {}", query);
+    if query.trim().is_empty() {
+        return Err("Query cannot be empty".to_string());
+    }
+
+    let sessions = list_codex_sessions(Some(options.project_path.clone())).await?;
+    let mut scored: Vec<(u8, CodexSession)> = sessions
+        .into_iter()
+        .filter_map(|session| {
+            let score = first_message_match_score(session.first_message.as_deref()?, &query)?;
+            Some((score, session))
+        })
+        .collect();
+    let best = match scored.iter().map(|(score, _)| *score).max() {
+        Some(best) => best,
+        None => return Err(format!("No Codex session matches \"{}\"", query)),
+    };
+    scored.retain(|(score, _)| *score == best);
+
+    if scored.len() > 1 {
+        log::info!("[Codex] {} sessions match \"{}\", asking to disambiguate", scored.len(), query);
+        return Ok(CodexResumeByQuery {
+            resumed_session_id: None,
+            candidates: scored.into_iter().map(|(_, session)| session).collect(),
+        });
+    }
+
+    let session_id = scored.remove(0).1.id;
+    log::info!("[Codex] Resuming session {} matched by query", session_id);
+    resume_codex(session_id.clone(), options, app_handle).await?;
+    Ok(CodexResumeByQuery {
+        resumed_session_id: Some(session_id),
+        candidates: Vec::new(),
+    })
+}
+
+/// How well a first message matches a query, higher is better; None for no match
+/// 3 = same text, 2 = starts with the query, 1 = contains it, 0 = contains every word.
+fn first_message_match_score(first_message: &str, query: &str) -> Option<u8> {
+    let message = first_message.trim().to_lowercase();
+    let query = query.trim().to_lowercase();
+    if message == query {
+        Some(3)
+    } else if message.starts_with(&query) {
+        Some(2)
+    } else if message.contains(&query) {
+        Some(1)
+    } else if query.split_whitespace().all(|word| message.contains(word)) {
+        Some(0)
+    } else {
+        None
+    }
 }
 
 /// Cancels a running Codex execution
@@ -228,9 +585,14 @@ pub async fn cancel_codex(
 
 /// Lists all Codex sessions by reading ~/.codex/sessions directory
 /// On Windows with WSL mode, reads from WSL filesystem via UNC path
+/// When `project_path` is given, only sessions whose cwd is that project are returned.
 #[tauri::command]
-pub async fn list_codex_sessions() -> Result<Vec<CodexSession>, String> {
-    log::info!("list_codex_sessions called");
+pub async fn list_codex_sessions(project_path: Option<String>) -> Result<Vec<CodexSession>, String> {
+    log::info!("list_codex_sessions called (project filter: {:?})", project_path);
+    let mut project_matcher = project_path
+        .as_deref()
+        .filter(|path| !path.trim().is_empty())
+        .map(ProjectPathMatcher::new);
 
     // Use unified sessions directory function (supports WSL)
     let sessions_dir = get_codex_sessions_dir()?;
@@ -260,7 +622,13 @@ pub async fn list_codex_sessions() -> Result<Vec<CodexSession>, String> {
                                                 Some(session) => {
                                                     log::debug!("Found session: {} ({})",
                                                         session.id, session.project_path);
-                                                    sessions.push(session);
+                                                    let in_project = match &mut project_matcher {
+                                                        Some(matcher) => matcher.matches(&session.project_path),
+                                                        None => true,
+                                                    };
+                                                    if in_project {
+                                                        sessions.push(session);
+                                                    }
                                                 }
                                                 None => {
                                                     log::debug!("Failed to parse: {:?}", path);
@@ -284,6 +652,94 @@ pub async fn list_codex_sessions() -> Result<Vec<CodexSession>, String> {
     Ok(sessions)
 }
 
+/// Lists Codex sessions grouped by their YYYY/MM/DD storage directory, newest day first
+#[tauri::command]
+pub async fn list_codex_sessions_grouped(
+    project_path: Option<String>,
+) -> Result<Vec<CodexSessionDay>, String> {
+    let mut project_matcher = project_path
+        .as_deref()
+        .filter(|path| !path.trim().is_empty())
+        .map(ProjectPathMatcher::new);
+
+    let sessions_dir = get_codex_sessions_dir()?;
+    if !sessions_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    // Directory names sort chronologically, so no timestamps need to be compared across days
+    let sorted_dirs = |dir: &std::path::Path| -> Vec<(String, PathBuf)> {
+        let mut dirs: Vec<(String, PathBuf)> = std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|entry| entry.path().is_dir())
+                    .map(|entry| (entry.file_name().to_string_lossy().to_string(), entry.path()))
+                    .collect()
+            })
+            .unwrap_or_default();
+        dirs.sort_by(|a, b| b.0.cmp(&a.0));
+        dirs
+    };
+
+    let mut days = Vec::new();
+    for (year, year_path) in sorted_dirs(&sessions_dir) {
+        for (month, month_path) in sorted_dirs(&year_path) {
+            for (day, day_path) in sorted_dirs(&month_path) {
+                let mut sessions: Vec<CodexSession> = std::fs::read_dir(&day_path)
+                    .map(|entries| {
+                        entries
+                            .flatten()
+                            .map(|entry| entry.path())
+                            .filter(|path| path.extension().and_then(|s| s.to_str()) == Some("jsonl"))
+                            .filter_map(|path| parse_codex_session_file(&path))
+                            .filter(|session| match &mut project_matcher {
+                                Some(matcher) => matcher.matches(&session.project_path),
+                                None => true,
+                            })
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                if sessions.is_empty() {
+                    continue;
+                }
+                sessions.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+                days.push(CodexSessionDay {
+                    date: format!("{}-{}-{}", year, month, day),
+                    sessions,
+                });
+            }
+        }
+    }
+
+    log::info!("[Codex] Grouped sessions into {} day(s)", days.len());
+    Ok(days)
+}
+
+/// Number of leading lines searched for the session_meta event
+const SESSION_META_SCAN_LINES: usize = 10;
+
+/// Finds the session_meta event among the first lines of a session file.
+/// Blank lines and non-meta lines (e.g. stray log output) before it are skipped.
+/// The iterator is left positioned right after the meta line.
+fn find_session_meta<I>(lines: &mut I) -> Option<serde_json::Value>
+where
+    I: Iterator<Item = std::io::Result<String>>,
+{
+    for line in lines.take(SESSION_META_SCAN_LINES) {
+        let line = line.ok()?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) {
+            if event["type"].as_str() == Some("session_meta") {
+                return Some(event);
+            }
+        }
+    }
+    None
+}
+
 /// Parses a Codex session JSONL file to extract metadata
 pub fn parse_codex_session_file(path: &std::path::Path) -> Option<CodexSession> {
     use std::io::{BufRead, BufReader};
@@ -292,13 +748,8 @@ pub fn parse_codex_session_file(path: &std::path::Path) -> Option<CodexSession>
     let reader = BufReader::new(file);
     let mut lines = reader.lines();
 
-    // Read first line (session_meta)
-    let first_line = lines.next()?.ok()?;
-    let meta: serde_json::Value = serde_json::from_str(&first_line).ok()?;
-
-    if meta["type"].as_str()? != "session_meta" {
-        return None;
-    }
+    // Locate the session_meta event (normally line one, but tolerate leading noise)
+    let meta = find_session_meta(&mut lines)?;
 
     let payload = &meta["payload"];
     let session_id = payload["id"].as_str()?.to_string();
@@ -448,6 +899,139 @@ pub async fn load_codex_session_history(session_id: String) -> Result<Vec<serde_
     Ok(events)
 }
 
+/// Returns the ordered list of model switches within a Codex session
+/// Models are read from session_meta, turn_context and response_item events;
+/// prompt indices match the ones used by the rewind prompt list
+#[tauri::command]
+pub async fn get_codex_session_model_timeline(session_id: String) -> Result<Vec<CodexModelChange>, String> {
+    log::info!("get_codex_session_model_timeline called for: {}", session_id);
+
+    let sessions_dir = get_codex_sessions_dir()?;
+    let session_file = find_session_file(&sessions_dir, &session_id)
+        .ok_or_else(|| format!("Session file not found for ID: {}", session_id))?;
+    let content = std::fs::read_to_string(&session_file)
+        .map_err(|e| format!("Failed to read session file: {}", e))?;
+
+    let mut timeline: Vec<CodexModelChange> = Vec::new();
+    // Number of real user prompts seen so far
+    let mut prompt_count = 0usize;
+
+    for line in content.lines() {
+        let event: serde_json::Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        let payload = &event["payload"];
+
+        if event["type"].as_str() == Some("response_item")
+            && payload["role"].as_str() == Some("user")
+            && is_user_prompt(payload)
+        {
+            prompt_count += 1;
+            continue;
+        }
+
+        let model = match payload["model"].as_str() {
+            Some(m) if !m.is_empty() => m,
+            _ => continue,
+        };
+        if timeline.last().map(|c| c.model.as_str()) == Some(model) {
+            continue;
+        }
+
+        // Codex records the turn context right after the prompt it applies to
+        let prompt_index = prompt_count.saturating_sub(1);
+        if let Some(last) = timeline.last_mut() {
+            // Several reports for the same prompt (e.g. session_meta then turn_context): latest wins
+            if last.prompt_index == prompt_index {
+                last.model = model.to_string();
+                last.timestamp = event["timestamp"].as_str().map(String::from);
+                continue;
+            }
+        }
+        timeline.push(CodexModelChange {
+            prompt_index,
+            model: model.to_string(),
+            timestamp: event["timestamp"].as_str().map(String::from),
+        });
+    }
+
+    log::info!("Found {} model change(s) in Codex session {}", timeline.len(), session_id);
+    Ok(timeline)
+}
+
+/// Returns true if a user response_item carries typed text rather than context injections
+pub(crate) fn is_user_prompt(payload: &serde_json::Value) -> bool {
+    payload["content"]
+        .as_array()
+        .map(|items| {
+            items.iter().any(|item| {
+                item["type"].as_str() == Some("input_text")
+                    && item["text"]
+                        .as_str()
+                        .map(|text| {
+                            !text.contains("<environment_context>")
+                                && !text.contains("# AGENTS.md instructions")
+                                && !text.trim().is_empty()
+                        })
+                        .unwrap_or(false)
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Returns the output lines a running Codex process has emitted so far
+/// A reloaded frontend replays these before subscribing to live `codex-output` events.
+#[tauri::command]
+pub async fn get_codex_running_output(
+    session_id: String,
+    state: tauri::State<'_, CodexProcessState>,
+) -> Result<Vec<String>, String> {
+    state
+        .output_buffers
+        .lock()
+        .await
+        .get(&session_id)
+        .map(OutputBuffer::lines)
+        .ok_or_else(|| format!("No running Codex session: {}", session_id))
+}
+
+/// Reads the file written by `codex exec -o` (relative paths resolve against the project)
+#[tauri::command]
+pub async fn read_codex_output_file(path: String, project_path: Option<String>) -> Result<String, String> {
+    let resolved = resolve_codex_output_path(&path, project_path.as_deref().unwrap_or(""), None);
+    log::info!("read_codex_output_file: {:?}", resolved);
+    std::fs::read_to_string(&resolved)
+        .map_err(|e| format!("Failed to read output file {}: {}", resolved.display(), e))
+}
+
+/// Maps an output file path as passed to the CLI to a path readable by the app
+/// In WSL mode, Linux paths are mapped to the Windows drive or the distro's UNC share
+/// (`wsl_distro` overrides the configured distro, as with `CodexExecutionOptions::wsl_distro`)
+pub fn resolve_codex_output_path(path: &str, project_path: &str, wsl_distro: Option<&str>) -> PathBuf {
+    #[cfg(target_os = "windows")]
+    {
+        let wsl_config = wsl_utils::get_wsl_config();
+        if (wsl_config.enabled || wsl_distro.is_some()) && path.starts_with('/') {
+            if path.starts_with("/mnt/") {
+                return PathBuf::from(wsl_utils::wsl_to_windows_path(path));
+            }
+            if let Some(distro) = wsl_distro.or(wsl_config.distro.as_deref()) {
+                return wsl_utils::build_wsl_unc_path(path, distro);
+            }
+        }
+    }
+    #[cfg(not(target_os = "windows"))]
+    let _ = wsl_distro;
+
+    let file = PathBuf::from(path);
+    if file.is_absolute() || project_path.is_empty() {
+        file
+    } else {
+        PathBuf::from(project_path).join(file)
+    }
+}
+
 /// Finds the JSONL file for a given session ID
 pub fn find_session_file(sessions_dir: &std::path::Path, session_id: &str) -> Option<std::path::PathBuf> {
     use walkdir::WalkDir;
@@ -457,17 +1041,13 @@ pub fn find_session_file(sessions_dir: &std::path::Path, session_id: &str) -> Op
         if entry.path().extension().and_then(|s| s.to_str()) == Some("jsonl") {
             // Read the first line to check session_id
             if let Ok(file) = std::fs::File::open(entry.path()) {
-                let reader = BufReader::new(file);
-                if let Some(Ok(first_line)) = reader.lines().next() {
-                    if let Ok(meta) = serde_json::from_str::<serde_json::Value>(&first_line) {
-                        // Check if this is a session_meta event with matching ID
-                        if meta["type"].as_str() == Some("session_meta") {
-                            if let Some(id) = meta["payload"]["id"].as_str() {
-                                if id == session_id {
-                                    log::info!("Found session file: {:?} for session_id: {}", entry.path(), session_id);
-                                    return Some(entry.path().to_path_buf());
-                                }
-                            }
+                let mut lines = BufReader::new(file).lines();
+                // Check if the session_meta event has a matching ID
+                if let Some(meta) = find_session_meta(&mut lines) {
+                    if let Some(id) = meta["payload"]["id"].as_str() {
+                        if id == session_id {
+                            log::info!("Found session file: {:?} for session_id: {}", entry.path(), session_id);
+                            return Some(entry.path().to_path_buf());
                         }
                     }
                 }
@@ -479,6 +1059,84 @@ pub fn find_session_file(sessions_dir: &std::path::Path, session_id: &str) -> Op
     None
 }
 
+/// Whether a Codex session can be resumed, and why not
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CodexResumeCheck {
+    pub resumable: bool,
+    pub reason: Option<String>,
+}
+
+impl CodexResumeCheck {
+    fn blocked(reason: impl Into<String>) -> Self {
+        Self {
+            resumable: false,
+            reason: Some(reason.into()),
+        }
+    }
+}
+
+/// Checks that a session can be resumed before the user tries to
+/// The session file must exist with a valid session_meta header, the CLI must be
+/// available, and the installed CLI must not be older than the one that recorded it.
+#[tauri::command]
+pub async fn can_resume_codex(session_id: String) -> Result<CodexResumeCheck, String> {
+    use std::io::{BufRead, BufReader};
+
+    let sessions_dir = get_codex_sessions_dir()?;
+    let session_file = match find_session_file(&sessions_dir, &session_id) {
+        Some(path) => path,
+        None => return Ok(CodexResumeCheck::blocked("Session file no longer exists")),
+    };
+
+    let file = match std::fs::File::open(&session_file) {
+        Ok(file) => file,
+        Err(e) => {
+            return Ok(CodexResumeCheck::blocked(format!("Session file is not readable: {}", e)))
+        }
+    };
+    let meta = match find_session_meta(&mut BufReader::new(file).lines()) {
+        Some(meta) => meta,
+        None => return Ok(CodexResumeCheck::blocked("Session file has no session_meta header")),
+    };
+    if meta["payload"]["cwd"].as_str().is_none() {
+        return Ok(CodexResumeCheck::blocked("Session metadata has no working directory"));
+    }
+
+    let availability = check_codex_availability().await?;
+    if !availability.available {
+        return Ok(CodexResumeCheck::blocked(
+            availability
+                .error
+                .unwrap_or_else(|| "Codex CLI is not available".to_string()),
+        ));
+    }
+
+    // A session recorded by a newer CLI may use a format the installed one cannot read
+    let recorded = meta["payload"]["cli_version"].as_str().and_then(version_number);
+    let installed = availability.version.as_deref().and_then(version_number);
+    if let (Some(recorded), Some(installed)) = (recorded, installed) {
+        if compare_versions(&recorded, &installed) == std::cmp::Ordering::Greater {
+            return Ok(CodexResumeCheck::blocked(format!(
+                "Session was recorded by Codex CLI {} but {} is installed; please upgrade",
+                recorded, installed
+            )));
+        }
+    }
+
+    Ok(CodexResumeCheck {
+        resumable: true,
+        reason: None,
+    })
+}
+
+/// Extracts "x.y.z" from version output such as "codex-cli 0.58.0"
+fn version_number(text: &str) -> Option<String> {
+    text.split(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .find(|part| part.contains('.') && part.starts_with(|c: char| c.is_ascii_digit()))
+        .map(|part| part.trim_end_matches('.').to_string())
+}
+
 /// Deletes a Codex session
 /// On Windows with WSL mode, deletes from WSL filesystem via UNC path
 #[tauri::command]
@@ -504,6 +1162,118 @@ pub async fn delete_codex_session(session_id: String) -> Result<String, String>
 // Helper Functions
 // ============================================================================
 
+/// Returns the directory Codex should run in (`working_dir`, falling back to `project_path`)
+fn codex_working_dir(options: &CodexExecutionOptions) -> String {
+    match options.working_dir.as_deref().map(str::trim) {
+        Some(dir) if !dir.is_empty() => {
+            let dir_path = PathBuf::from(dir);
+            if dir_path.is_absolute() {
+                dir.to_string()
+            } else {
+                PathBuf::from(&options.project_path)
+                    .join(dir_path)
+                    .to_string_lossy()
+                    .to_string()
+            }
+        }
+        _ => options.project_path.clone(),
+    }
+}
+
+/// Reasoning effort levels accepted by `model_reasoning_effort`
+const CODEX_REASONING_EFFORTS: &[&str] = &["minimal", "low", "medium", "high"];
+
+/// Returns the config override for the requested reasoning effort, validating the value
+fn codex_reasoning_effort_args(options: &CodexExecutionOptions) -> Result<Vec<String>, String> {
+    match options.reasoning_effort.as_deref() {
+        None => Ok(Vec::new()),
+        Some(effort) => {
+            let effort = effort.trim().to_lowercase();
+            if !CODEX_REASONING_EFFORTS.contains(&effort.as_str()) {
+                return Err(format!(
+                    "Invalid reasoning effort '{}'. Expected one of: {}",
+                    effort,
+                    CODEX_REASONING_EFFORTS.join(", ")
+                ));
+            }
+            Ok(vec!["-c".to_string(), format!("model_reasoning_effort=\"{}\"", effort)])
+        }
+    }
+}
+
+/// Returns the `--profile` flag for the requested config profile, rejecting blank names
+fn codex_profile_args(options: &CodexExecutionOptions) -> Result<Vec<String>, String> {
+    match options.profile.as_deref() {
+        None => Ok(Vec::new()),
+        Some(profile) if profile.trim().is_empty() => {
+            Err("Codex profile name must not be empty".to_string())
+        }
+        Some(profile) => Ok(vec!["--profile".to_string(), profile.trim().to_string()]),
+    }
+}
+
+/// File extensions Codex accepts as image input
+const CODEX_IMAGE_EXTENSIONS: &[&str] = &["png", "jpg", "jpeg", "gif", "webp", "bmp"];
+
+/// Returns the absolute paths of the attached images, checking each is an existing image file
+fn codex_image_paths(options: &CodexExecutionOptions) -> Result<Vec<String>, String> {
+    let images = match options.images.as_deref() {
+        Some(images) => images,
+        None => return Ok(Vec::new()),
+    };
+    let working_dir = PathBuf::from(codex_working_dir(options));
+
+    images
+        .iter()
+        .map(|image| {
+            let path = PathBuf::from(image.trim());
+            let path = if path.is_absolute() { path } else { working_dir.join(path) };
+            if !path.is_file() {
+                return Err(format!("Image file does not exist: {}", path.display()));
+            }
+            let extension = path
+                .extension()
+                .map(|ext| ext.to_string_lossy().to_lowercase())
+                .unwrap_or_default();
+            if !CODEX_IMAGE_EXTENSIONS.contains(&extension.as_str()) {
+                return Err(format!(
+                    "Unsupported image type '{}'. Expected one of: {}",
+                    path.display(),
+                    CODEX_IMAGE_EXTENSIONS.join(", ")
+                ));
+            }
+            Ok(path.to_string_lossy().to_string())
+        })
+        .collect()
+}
+
+/// Returns the sandbox flags for a run (read-only is the CLI default)
+/// Explicit sandbox options win over the mode preset. No flag is passed for
+/// read-only, so a sandbox default set in the user's config.toml still applies.
+fn codex_sandbox_args(options: &CodexExecutionOptions) -> Result<Vec<String>, String> {
+    let args: &[&str] = match options.sandbox {
+        Some(CodexSandboxOptions { allow_writes: false, allow_network: false }) => &[],
+        Some(CodexSandboxOptions { allow_writes: true, allow_network: false }) => &["--full-auto"],
+        Some(CodexSandboxOptions { allow_writes: true, allow_network: true }) => &[
+            "--full-auto",
+            "-c",
+            "sandbox_workspace_write.network_access=true",
+        ],
+        Some(CodexSandboxOptions { allow_writes: false, allow_network: true }) => {
+            return Err(
+                "allowNetwork requires allowWrites: Codex's read-only sandbox has no network access"
+                    .to_string(),
+            )
+        }
+        None => match options.mode {
+            CodexExecutionMode::FullAuto => &["--full-auto"],
+            CodexExecutionMode::DangerFullAccess => &["--sandbox", "danger-full-access"],
+            CodexExecutionMode::ReadOnly => &[],
+        },
+    };
+    Ok(args.iter().map(|a| a.to_string()).collect())
+}
+
 /// Builds a Codex command with the given options
 /// Returns (Command, Option<String>) where the String is the prompt to be passed via stdin
 /// Supports both native execution and WSL mode on Windows
@@ -516,12 +1286,20 @@ fn build_codex_command(
     #[cfg(target_os = "windows")]
     {
         let wsl_config = wsl_utils::get_wsl_config();
-        if wsl_config.enabled {
-            log::info!("[Codex] Using WSL mode (distro: {:?})", wsl_config.distro);
-            return build_wsl_codex_command(options, is_resume, session_id, &wsl_config);
+        if wsl_config.enabled || options.wsl_distro.is_some() {
+            let mut run_config = wsl_config.clone();
+            if let Some(ref distro) = options.wsl_distro {
+                run_config.distro = Some(validate_wsl_distro(distro)?);
+            }
+            log::info!("[Codex] Using WSL mode (distro: {:?})", run_config.distro);
+            return build_wsl_codex_command(options, is_resume, session_id, &run_config);
         }
     }
 
+    if let Some(ref distro) = options.wsl_distro {
+        log::warn!("[Codex] Ignoring wsl_distro '{}': WSL is only available on Windows", distro);
+    }
+
     // Native mode: Use system-installed Codex
     let (_env_info, detected) = detect_binary_for_tool("codex", "CODEX_PATH", "codex");
     let codex_cmd = if let Some(inst) = detected {
@@ -538,6 +1316,7 @@ fn build_codex_command(
     };
 
     let mut cmd = Command::new(&codex_cmd);
+    cmd.envs(offline_env_vars().iter().copied());
     cmd.arg("exec");
 
     // CRITICAL: --json MUST come before 'resume' (if used)
@@ -549,6 +1328,12 @@ fn build_codex_command(
         cmd.arg("--json");
     }
 
+    // Two-phase execution resumes the planning thread with an elevated sandbox,
+    // so the mode flags have to precede 'resume' like --json does
+    if is_resume && options.two_phase {
+        cmd.args(codex_sandbox_args(options)?);
+    }
+
     if is_resume {
         // Add 'resume' after --json
         cmd.arg("resume");
@@ -564,24 +1349,21 @@ fn build_codex_command(
         // For new sessions: add other options
         // (--json already added above)
 
-        match options.mode {
-            CodexExecutionMode::FullAuto => {
-                cmd.arg("--full-auto");
-            }
-            CodexExecutionMode::DangerFullAccess => {
-                cmd.arg("--sandbox");
-                cmd.arg("danger-full-access");
-            }
-            CodexExecutionMode::ReadOnly => {
-                // Read-only is default
-            }
-        }
+        cmd.args(codex_sandbox_args(options)?);
 
         if let Some(ref model) = options.model {
             cmd.arg("--model");
             cmd.arg(model);
         }
 
+        cmd.args(codex_reasoning_effort_args(options)?);
+        cmd.args(codex_profile_args(options)?);
+
+        // `--image` takes several values; the `=` form keeps it from swallowing the trailing "-"
+        for image in codex_image_paths(options)? {
+            cmd.arg(format!("--image={}", image));
+        }
+
         if let Some(ref schema) = options.output_schema {
             cmd.arg("--output-schema");
             cmd.arg(schema);
@@ -597,8 +1379,12 @@ fn build_codex_command(
         }
     }
 
-    // Set working directory
-    cmd.current_dir(&options.project_path);
+    // Set working directory (may be a subdirectory of the git-tracked project root)
+    let working_dir = codex_working_dir(options);
+    if !std::path::Path::new(&working_dir).is_dir() {
+        return Err(format!("Working directory does not exist: {}", working_dir));
+    }
+    cmd.current_dir(&working_dir);
 
     // Set API key environment variable if provided
     if let Some(ref api_key) = options.api_key {
@@ -614,17 +1400,40 @@ fn build_codex_command(
     // Add "-" to indicate reading from stdin (common CLI convention)
     cmd.arg("-");
 
+    let prompt = if options.normalize_line_endings {
+        normalize_line_endings(&options.prompt)
+    } else {
+        options.prompt.clone()
+    };
     let prompt_for_stdin = if is_resume {
         // For resume mode, prompt is still needed but passed via stdin
-        Some(options.prompt.clone())
+        Some(prompt)
     } else {
         // For new sessions, pass prompt via stdin
-        Some(options.prompt.clone())
+        Some(prompt)
     };
 
     Ok((cmd, prompt_for_stdin))
 }
 
+/// Checks a per-run distro against the installed WSL distros
+/// Returns the distro name as reported by WSL (matching is case-insensitive)
+#[cfg(target_os = "windows")]
+fn validate_wsl_distro(distro: &str) -> Result<String, String> {
+    let distros = wsl_utils::get_wsl_distros();
+    distros
+        .iter()
+        .find(|d| d.eq_ignore_ascii_case(distro.trim()))
+        .cloned()
+        .ok_or_else(|| {
+            format!(
+                "WSL distro '{}' not found. Available distros: {}",
+                distro,
+                if distros.is_empty() { "none".to_string() } else { distros.join(", ") }
+            )
+        })
+}
+
 /// Builds a Codex command for WSL mode
 /// This is used when Codex is installed in WSL and we're running on Windows
 #[cfg(target_os = "windows")]
@@ -642,28 +1451,30 @@ fn build_wsl_codex_command(
         args.push("--json".to_string());
     }
 
+    if is_resume && options.two_phase {
+        args.extend(codex_sandbox_args(options)?);
+    }
+
     if is_resume {
         args.push("resume".to_string());
         if let Some(sid) = session_id {
             args.push(sid.to_string());
         }
     } else {
-        match options.mode {
-            CodexExecutionMode::FullAuto => {
-                args.push("--full-auto".to_string());
-            }
-            CodexExecutionMode::DangerFullAccess => {
-                args.push("--sandbox".to_string());
-                args.push("danger-full-access".to_string());
-            }
-            CodexExecutionMode::ReadOnly => {}
-        }
+        args.extend(codex_sandbox_args(options)?);
 
         if let Some(ref model) = options.model {
             args.push("--model".to_string());
             args.push(model.clone());
         }
 
+        args.extend(codex_reasoning_effort_args(options)?);
+        args.extend(codex_profile_args(options)?);
+
+        for image in codex_image_paths(options)? {
+            args.push(format!("--image={}", wsl_utils::windows_to_wsl_path(&image)));
+        }
+
         if let Some(ref schema) = options.output_schema {
             args.push("--output-schema".to_string());
             args.push(schema.clone());
@@ -684,11 +1495,12 @@ fn build_wsl_codex_command(
     args.push("-".to_string());
 
     // Build WSL command with path conversion
-    // project_path is Windows format (C:\...), will be converted to WSL format (/mnt/c/...)
+    // working_dir is Windows format (C:\...), will be converted to WSL format (/mnt/c/...)
+    let working_dir = codex_working_dir(options);
     let mut cmd = wsl_utils::build_wsl_command_async(
         "codex",
         &args,
-        Some(&options.project_path),
+        Some(&working_dir),
         wsl_config.distro.as_deref(),
     );
 
@@ -701,18 +1513,34 @@ fn build_wsl_codex_command(
     log::info!(
         "[Codex WSL] Command built: wsl -d {:?} --cd {} -- codex {:?}",
         wsl_config.distro,
-        wsl_utils::windows_to_wsl_path(&options.project_path),
+        wsl_utils::windows_to_wsl_path(&working_dir),
         args
     );
 
-    Ok((cmd, Some(options.prompt.clone())))
+    let prompt = if options.normalize_line_endings {
+        normalize_line_endings(&options.prompt)
+    } else {
+        options.prompt.clone()
+    };
+    Ok((cmd, Some(prompt)))
 }
 
+/// Interval between `codex-heartbeat` events while a process runs
+const CODEX_HEARTBEAT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
 /// Executes a Codex process and streams output to frontend
+/// When `two_phase` carries the execution-phase options, the run is treated as the
+/// planning phase: its final agent message is emitted as `codex-plan` and parked
+/// in `pending_plans` until `approve_codex_plan` is called.
+/// When `output_file` is set, its contents are emitted as `codex-output-file` after a successful run.
+/// Sandbox denials are only reported when `detect_denials` is set (read-only runs).
 async fn execute_codex_process(
     mut cmd: Command,
     prompt: Option<String>,
     _project_path: String,
+    two_phase: Option<CodexExecutionOptions>,
+    output_file: Option<PathBuf>,
+    detect_denials: bool,
     app_handle: AppHandle,
 ) -> Result<(), String> {
     // Setup stdio
@@ -724,6 +1552,9 @@ async fn execute_codex_process(
     // This prevents the terminal window from flashing when starting Codex sessions
     apply_no_window_async(&mut cmd);
 
+    // Recorded against the Codex thread ID once the CLI reports it
+    let mut pending_run_command = Some(describe_codex_command(&cmd));
+
     // Spawn process
     let mut child = cmd
         .spawn()
@@ -761,6 +1592,7 @@ async fn execute_codex_process(
     let session_id = format!("codex-{}", uuid::Uuid::new_v4());
 
     // Store process in state
+    let child_pid = child.id();
     let state: tauri::State<'_, CodexProcessState> = app_handle.state();
     {
         let mut processes = state.processes.lock().await;
@@ -768,7 +1600,27 @@ async fn execute_codex_process(
 
         let mut last_session = state.last_session_id.lock().await;
         *last_session = Some(session_id.clone());
+
+        state.output_buffers.lock().await.insert(session_id.clone(), OutputBuffer::default());
     }
+    let output_buffers = state.output_buffers.clone();
+
+    // Kill the run once the configured Codex timeout elapses
+    let finished = Arc::new(AtomicBool::new(false));
+    if let Some(pid) = child_pid {
+        let session_id_timeout = session_id.clone();
+        spawn_timeout_watchdog(app_handle.clone(), "codex", pid, finished.clone(), move || {
+            Some(session_id_timeout.clone())
+        });
+    }
+
+    // Liveness counters shared with the heartbeat task
+    let started_at = std::time::Instant::now();
+    let bytes_received = Arc::new(AtomicU64::new(0));
+    let last_output_ms = Arc::new(AtomicU64::new(0));
+    let bytes_received_stdout = bytes_received.clone();
+    let last_output_ms_stdout = last_output_ms.clone();
+    let bytes_received_stderr = bytes_received.clone();
 
     // Clone handles for async tasks
     let app_handle_stdout = app_handle.clone();
@@ -777,6 +1629,12 @@ async fn execute_codex_process(
     let session_id_stdout = session_id.clone();  // Clone for stdout task
     let session_id_complete = session_id.clone();
 
+    // Two-phase planning: (thread_id, last agent message) captured from stdout
+    let plan_capture: Arc<Mutex<(Option<String>, Option<String>)>> =
+        Arc::new(Mutex::new((None, None)));
+    let plan_capture_stdout = plan_capture.clone();
+    let capture_plan = two_phase.is_some();
+
     // FIX: Emit session init event immediately so frontend can subscribe to the correct channel
     // This event is sent on the global channel, frontend will use this to switch to session-specific listeners
     let init_payload = serde_json::json!({
@@ -790,11 +1648,43 @@ async fn execute_codex_process(
 
     // Spawn task to read stdout (JSONL events)
     // FIX: Emit to both session-specific and global channels for proper multi-tab isolation
-    tokio::spawn(async move {
+    let stdout_task = tokio::spawn(async move {
         let mut reader = BufReader::new(stdout).lines();
         while let Ok(Some(line)) = reader.next_line().await {
+            bytes_received_stdout.fetch_add(line.len() as u64 + 1, Ordering::Relaxed);
+            last_output_ms_stdout.store(started_at.elapsed().as_millis() as u64, Ordering::Relaxed);
             if !line.trim().is_empty() {
                 log::debug!("Codex output: {}", line);
+                if let Some(buffer) = output_buffers.lock().await.get_mut(&session_id_stdout) {
+                    buffer.push(line.clone());
+                }
+                if capture_plan {
+                    capture_plan_event(&line, &plan_capture_stdout).await;
+                }
+                if pending_run_command.is_some() {
+                    if let Some(thread_id) = thread_started_id(&line) {
+                        if let Some(run) = pending_run_command.take() {
+                            if let Err(e) = save_codex_run_command(&thread_id, &run) {
+                                log::warn!("[Codex] Failed to record run command: {}", e);
+                            }
+                        }
+                    }
+                }
+                if let Some(denial) = detect_denials.then(|| detect_sandbox_denial(&line)).flatten() {
+                    log::warn!("[Codex] Sandbox denied {}: {}", denial["operation"], denial["detail"]);
+                    let payload = serde_json::json!({
+                        "session_id": session_id_stdout,
+                        "operation": denial["operation"],
+                        "detail": denial["detail"],
+                        "reason": denial["reason"],
+                    });
+                    if let Err(e) = app_handle_stdout.emit(&format!("codex-sandbox-denied:{}", session_id_stdout), &payload) {
+                        log::error!("Failed to emit codex-sandbox-denied (session-specific): {}", e);
+                    }
+                    if let Err(e) = app_handle_stdout.emit("codex-sandbox-denied", &payload) {
+                        log::error!("Failed to emit codex-sandbox-denied (global): {}", e);
+                    }
+                }
                 // Emit to session-specific channel first (for multi-tab isolation)
                 if let Err(e) = app_handle_stdout.emit(&format!("codex-output:{}", session_id_stdout), &line) {
                     log::error!("Failed to emit codex-output (session-specific): {}", e);
@@ -808,12 +1698,46 @@ async fn execute_codex_process(
     });
 
     // Spawn task to read stderr (log errors, suppress debug output)
-    tokio::spawn(async move {
+    let stderr_tail = Arc::new(std::sync::Mutex::new(StderrTail::default()));
+    let stderr_tail_reader = stderr_tail.clone();
+    let stderr_task = tokio::spawn(async move {
         let mut reader = BufReader::new(stderr).lines();
         while let Ok(Some(line)) = reader.next_line().await {
+            bytes_received_stderr.fetch_add(line.len() as u64 + 1, Ordering::Relaxed);
             // Log error messages for debugging
             if !line.trim().is_empty() {
                 log::warn!("Codex stderr: {}", line);
+                if let Ok(mut tail) = stderr_tail_reader.lock() {
+                    tail.push(&line);
+                }
+            }
+        }
+    });
+
+    // Spawn heartbeat task: a liveness signal for long runs without output
+    // Stops once the process is no longer tracked (completed or cancelled)
+    let app_handle_heartbeat = app_handle.clone();
+    let session_id_heartbeat = session_id.clone();
+    tokio::spawn(async move {
+        let state: tauri::State<'_, CodexProcessState> = app_handle_heartbeat.state();
+        loop {
+            tokio::time::sleep(CODEX_HEARTBEAT_INTERVAL).await;
+            if !state.processes.lock().await.contains_key(&session_id_heartbeat) {
+                break;
+            }
+
+            let elapsed_ms = started_at.elapsed().as_millis() as u64;
+            let payload = serde_json::json!({
+                "session_id": session_id_heartbeat,
+                "elapsed_secs": elapsed_ms / 1000,
+                "bytes_received": bytes_received.load(Ordering::Relaxed),
+                "idle_secs": elapsed_ms.saturating_sub(last_output_ms.load(Ordering::Relaxed)) / 1000,
+            });
+            if let Err(e) = app_handle_heartbeat.emit(&format!("codex-heartbeat:{}", session_id_heartbeat), &payload) {
+                log::error!("Failed to emit codex-heartbeat (session-specific): {}", e);
+            }
+            if let Err(e) = app_handle_heartbeat.emit("codex-heartbeat", &payload) {
+                log::error!("Failed to emit codex-heartbeat (global): {}", e);
             }
         }
     });
@@ -854,10 +1778,83 @@ async fn execute_codex_process(
             }
         };
 
+        finished.store(true, Ordering::SeqCst);
         if let Some(status) = exit_status {
             log::info!("Codex process exited with status: {}", status);
         }
 
+        // Make sure all output has been forwarded before signalling completion
+        let _ = stdout_task.await;
+        let _ = stderr_task.await;
+
+        // Cancelled runs (no exit status) are not failures
+        if let Some(status) = exit_status {
+            let tail = stderr_tail.lock().map(|t| t.text()).unwrap_or_default();
+            record_run_outcome(&state.last_errors, &session_id_complete, status.success(), status.code(), tail)
+                .await;
+        }
+
+        if let Some(execute_options) = two_phase {
+            let succeeded = exit_status.map(|s| s.success()).unwrap_or(false);
+            let (thread_id, plan) = plan_capture.lock().await.clone();
+            match (succeeded, thread_id) {
+                (true, Some(thread_id)) => {
+                    let plan = plan.unwrap_or_default();
+                    let payload = serde_json::json!({
+                        "session_id": session_id_complete,
+                        "thread_id": thread_id,
+                        "plan": plan,
+                    });
+                    state.pending_plans.lock().await.insert(
+                        session_id_complete.clone(),
+                        PendingCodexPlan {
+                            options: execute_options,
+                            thread_id,
+                            plan,
+                        },
+                    );
+                    if let Err(e) = app_handle_complete.emit(&format!("codex-plan:{}", session_id_complete), &payload) {
+                        log::error!("Failed to emit codex-plan (session-specific): {}", e);
+                    }
+                    if let Err(e) = app_handle_complete.emit("codex-plan", &payload) {
+                        log::error!("Failed to emit codex-plan (global): {}", e);
+                    }
+                    log::info!("[Codex] Plan ready for approval: {}", session_id_complete);
+                }
+                _ => {
+                    log::warn!(
+                        "[Codex] Planning phase for {} did not produce a resumable plan",
+                        session_id_complete
+                    );
+                }
+            }
+        }
+
+        if let Some(path) = output_file {
+            if exit_status.map(|s| s.success()).unwrap_or(false) {
+                match std::fs::read_to_string(&path) {
+                    Ok(content) => {
+                        let payload = serde_json::json!({
+                            "session_id": session_id_complete,
+                            "path": path.to_string_lossy(),
+                            "json": serde_json::from_str::<serde_json::Value>(&content).ok(),
+                            "content": content,
+                        });
+                        if let Err(e) = app_handle_complete.emit(&format!("codex-output-file:{}", session_id_complete), &payload) {
+                            log::error!("Failed to emit codex-output-file (session-specific): {}", e);
+                        }
+                        if let Err(e) = app_handle_complete.emit("codex-output-file", &payload) {
+                            log::error!("Failed to emit codex-output-file (global): {}", e);
+                        }
+                    }
+                    Err(e) => log::warn!("[Codex] Failed to read output file {:?}: {}", path, e),
+                }
+            }
+        }
+
+        // The session file now holds the full transcript
+        state.output_buffers.lock().await.remove(&session_id_complete);
+
         // Emit completion event
         // FIX: Emit to both session-specific and global channels for proper multi-tab isolation
         if let Err(e) = app_handle_complete.emit(&format!("codex-complete:{}", session_id_complete), true) {
@@ -871,3 +1868,212 @@ async fn execute_codex_process(
 
     Ok(())
 }
+
+/// Messages Codex and the OS sandboxes (Seatbelt, Landlock) produce when they block
+/// a command; plain EACCES ("permission denied") is left out as ordinary failures raise it
+const SANDBOX_DENIAL_MARKERS: &[&str] = &[
+    "read-only file system",
+    "operation not permitted",
+    "failed in sandbox",
+    "sandbox denied",
+];
+
+/// Detects operations the sandbox refused, returning `{operation, detail, reason}`
+/// Failed file changes are always sandbox denials; failed commands only when their
+/// output carries a denial marker, so ordinary non-zero exits are not reported.
+fn detect_sandbox_denial(line: &str) -> Option<serde_json::Value> {
+    let event: serde_json::Value = serde_json::from_str(line).ok()?;
+    if event["type"].as_str() != Some("item.completed") {
+        return None;
+    }
+
+    let item = &event["item"];
+    let status = item["status"].as_str().unwrap_or("");
+    if status != "failed" && status != "declined" {
+        return None;
+    }
+
+    match item["type"].as_str()? {
+        "file_change" => {
+            let paths: Vec<&str> = item["changes"]
+                .as_array()
+                .map(|changes| changes.iter().filter_map(|c| c["path"].as_str()).collect())
+                .unwrap_or_default();
+            Some(serde_json::json!({
+                "operation": "file_change",
+                "detail": paths.join(", "),
+                "reason": status,
+            }))
+        }
+        "command_execution" => {
+            let output = item["aggregated_output"].as_str().unwrap_or("");
+            let lower = output.to_lowercase();
+            if status == "failed" && !SANDBOX_DENIAL_MARKERS.iter().any(|m| lower.contains(m)) {
+                return None;
+            }
+            let reason: String = output.lines().last().unwrap_or(status).chars().take(500).collect();
+            Some(serde_json::json!({
+                "operation": "command",
+                "detail": item["command"].as_str().unwrap_or(""),
+                "reason": reason,
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// Returns the thread ID of a `thread.started` event
+fn thread_started_id(line: &str) -> Option<String> {
+    let event: serde_json::Value = serde_json::from_str(line).ok()?;
+    if event["type"].as_str() != Some("thread.started") {
+        return None;
+    }
+    event["thread_id"].as_str().map(|id| id.to_string())
+}
+
+/// Records the thread ID and latest agent message of a planning-phase run
+async fn capture_plan_event(line: &str, capture: &Mutex<(Option<String>, Option<String>)>) {
+    let event: serde_json::Value = match serde_json::from_str(line) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    match event["type"].as_str() {
+        Some("thread.started") => {
+            if let Some(thread_id) = event["thread_id"].as_str() {
+                capture.lock().await.0 = Some(thread_id.to_string());
+            }
+        }
+        Some("item.completed") => {
+            if event["item"]["type"].as_str() == Some("agent_message") {
+                if let Some(text) = event["item"]["text"].as_str() {
+                    capture.lock().await.1 = Some(text.to_string());
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const META_LINE: &str = r#"{"type":"session_meta","timestamp":"2025-11-23T10:00:00Z","payload":{"id":"abc-123","timestamp":"2025-11-23T10:00:00Z","cwd":"/tmp/project"}}"#;
+    const USER_LINE: &str = r#"{"type":"response_item","timestamp":"2025-11-23T10:00:05Z","payload":{"type":"message","role":"user","content":[{"type":"input_text","text":"hello codex"}]}}"#;
+
+    fn write_session(content: &str) -> tempfile::NamedTempFile {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(content.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_first_message_match_score() {
+        assert_eq!(first_message_match_score("Fix the login bug", "fix the login bug"), Some(3));
+        assert_eq!(first_message_match_score("Fix the login bug", "fix the"), Some(2));
+        assert_eq!(first_message_match_score("Fix the login bug", "login"), Some(1));
+        assert_eq!(first_message_match_score("Fix the login bug", "bug login"), Some(0));
+        assert_eq!(first_message_match_score("Fix the login bug", "signup"), None);
+    }
+
+    #[test]
+    fn test_parse_session_with_meta_on_first_line() {
+        let file = write_session(&format!("{}\n{}\n", META_LINE, USER_LINE));
+        let session = parse_codex_session_file(file.path()).expect("session should parse");
+        assert_eq!(session.id, "abc-123");
+        assert_eq!(session.first_message.as_deref(), Some("hello codex"));
+    }
+
+    #[test]
+    fn test_parse_session_with_blank_line_before_meta() {
+        let file = write_session(&format!("\n{}\n{}\n", META_LINE, USER_LINE));
+        let session = parse_codex_session_file(file.path()).expect("session should parse");
+        assert_eq!(session.id, "abc-123");
+        assert_eq!(session.project_path, "/tmp/project");
+        assert_eq!(session.first_message.as_deref(), Some("hello codex"));
+    }
+
+    #[test]
+    fn test_parse_session_with_log_line_before_meta() {
+        let file = write_session(&format!("WARN something happened\n{}\n", META_LINE));
+        let session = parse_codex_session_file(file.path()).expect("session should parse");
+        assert_eq!(session.id, "abc-123");
+    }
+
+    #[test]
+    fn test_parse_session_without_meta() {
+        let file = write_session(&format!("{}\n", USER_LINE));
+        assert!(parse_codex_session_file(file.path()).is_none());
+    }
+
+    #[test]
+    fn test_detect_sandbox_denial() {
+        let denied = r#"{"type":"item.completed","item":{"type":"command_execution","command":"touch a.txt","aggregated_output":"touch: cannot touch 'a.txt': Read-only file system","status":"failed"}}"#;
+        let denial = detect_sandbox_denial(denied).unwrap();
+        assert_eq!(denial["operation"], "command");
+        assert_eq!(denial["detail"], "touch a.txt");
+
+        let ordinary_failure = r#"{"type":"item.completed","item":{"type":"command_execution","command":"cargo test","aggregated_output":"test result: FAILED","status":"failed"}}"#;
+        assert!(detect_sandbox_denial(ordinary_failure).is_none());
+
+        let chmod = r#"{"type":"item.completed","item":{"type":"command_execution","command":"chmod +x /etc/hosts","aggregated_output":"chmod: changing permissions of '/etc/hosts': Permission denied","status":"failed"}}"#;
+        assert!(detect_sandbox_denial(chmod).is_none());
+        let grep = r#"{"type":"item.completed","item":{"type":"command_execution","command":"grep -r sandbox src","aggregated_output":"src/lib.rs: // sandbox setup","status":"failed"}}"#;
+        assert!(detect_sandbox_denial(grep).is_none());
+
+        let patch = r#"{"type":"item.completed","item":{"type":"file_change","changes":[{"path":"src/main.rs","kind":"update"}],"status":"failed"}}"#;
+        assert_eq!(detect_sandbox_denial(patch).unwrap()["detail"], "src/main.rs");
+    }
+
+    #[test]
+    fn test_normalize_line_endings() {
+        assert_eq!(normalize_line_endings("line1\r\nline2\rline3\n"), "line1\nline2\nline3\n");
+
+        let options: CodexExecutionOptions = serde_json::from_value(serde_json::json!({
+            "projectPath": "/tmp/project",
+            "prompt": "fix\r\nthe bug\r\n"
+        }))
+        .unwrap();
+        assert!(options.normalize_line_endings);
+        let (_, prompt) = build_codex_command(&options, false, None).unwrap();
+        assert_eq!(prompt.as_deref(), Some("fix\nthe bug\n"));
+    }
+
+    #[test]
+    fn test_sandbox_options_override_mode() {
+        let options = |extra: serde_json::Value| -> CodexExecutionOptions {
+            let mut value = serde_json::json!({ "projectPath": "/tmp/project", "prompt": "hi" });
+            value.as_object_mut().unwrap().extend(extra.as_object().unwrap().clone());
+            serde_json::from_value(value).unwrap()
+        };
+
+        let preset = options(serde_json::json!({ "mode": "danger-full-access" }));
+        assert_eq!(codex_sandbox_args(&preset).unwrap(), ["--sandbox", "danger-full-access"]);
+
+        let networked = options(serde_json::json!({
+            "mode": "read-only",
+            "sandbox": { "allowWrites": true, "allowNetwork": true }
+        }));
+        assert_eq!(
+            codex_sandbox_args(&networked).unwrap(),
+            ["--full-auto", "-c", "sandbox_workspace_write.network_access=true"]
+        );
+
+        let full_auto = options(serde_json::json!({ "mode": "full-auto" }));
+        assert_eq!(codex_sandbox_args(&full_auto).unwrap(), ["--full-auto"]);
+
+        let read_only = options(serde_json::json!({ "mode": "read-only" }));
+        assert!(codex_sandbox_args(&read_only).unwrap().is_empty());
+
+        // Denials are only looked for when the sandbox is read-only
+        assert!(read_only.is_read_only_sandbox());
+        assert!(!full_auto.is_read_only_sandbox());
+        assert!(!preset.is_read_only_sandbox());
+        assert!(!networked.is_read_only_sandbox());
+
+        let invalid = options(serde_json::json!({ "sandbox": { "allowWrites": false, "allowNetwork": true } }));
+        assert!(codex_sandbox_args(&invalid).is_err());
+    }
+}
@@ -5,6 +5,7 @@
  * 支持：
  * - Claude → Codex：将 Claude session 转换为 Codex 可执行的 session
  * - Codex → Claude：将 Codex session 转换为 Claude 可加载的历史记录
+ * - Gemini → Claude：将 Gemini 聊天记录转换为 Claude 可恢复的 session
  *
  * 核心特性：
  * - 自动识别引擎类型（UUID vs rollout-前缀）
@@ -20,6 +21,9 @@ use std::collections::HashMap;
 use std::io::{BufRead, BufReader, Write};
 use once_cell::sync::Lazy;
 
+use crate::commands::gemini::config::read_session_detail;
+use crate::commands::gemini::parser::convert_history_message_to_unified;
+
 // ================================
 // 数据结构定义
 // ================================
@@ -28,7 +32,7 @@ use once_cell::sync::Lazy;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ConversionSource {
-    /// 源引擎类型: "claude" | "codex"
+    /// 源引擎类型: "claude" | "codex" | "gemini"
     pub engine: String,
     /// 源 Session ID
     pub session_id: String,
@@ -270,6 +274,22 @@ pub static CLAUDE_TO_CODEX_TOOL_MAP: Lazy<HashMap<&'static str, &'static str>> =
     m
 });
 
+/// Gemini → Claude 工具名称映射
+pub static GEMINI_TO_CLAUDE_TOOL_MAP: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
+    let mut m = HashMap::new();
+    m.insert("run_shell_command", "bash");
+    m.insert("read_file", "read");
+    m.insert("read_many_files", "read");
+    m.insert("write_file", "write");
+    m.insert("replace", "edit");
+    m.insert("search_file_content", "grep");
+    m.insert("glob", "glob");
+    m.insert("list_directory", "ls");
+    m.insert("web_fetch", "webfetch");
+    m.insert("google_web_search", "websearch");
+    m
+});
+
 /// 映射 Codex 工具名到 Claude 工具名
 /// MCP 工具 (mcp__ 前缀) 不进行映射
 pub fn map_codex_to_claude_tool(codex_name: &str) -> String {
@@ -283,6 +303,18 @@ pub fn map_codex_to_claude_tool(codex_name: &str) -> String {
         .unwrap_or_else(|| codex_name.to_string())
 }
 
+/// 映射 Gemini 工具名到 Claude 工具名
+/// MCP 工具 (mcp__ 前缀) 不进行映射
+pub fn map_gemini_to_claude_tool(gemini_name: &str) -> String {
+    if gemini_name.starts_with("mcp__") {
+        return gemini_name.to_string();
+    }
+    GEMINI_TO_CLAUDE_TOOL_MAP
+        .get(gemini_name.to_lowercase().as_str())
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| gemini_name.to_string())
+}
+
 /// 映射 Claude 工具名到 Codex 工具名
 /// MCP 工具 (mcp__ 前缀) 不进行映射
 pub fn map_claude_to_codex_tool(claude_name: &str) -> String {
@@ -790,29 +822,15 @@ impl CodexToClaudeConverter {
         // 将 content 数组转换为简化格式
         let simplified_content = self.simplify_content(content);
 
-        ClaudeMessage {
-            message_type: message_type.to_string(),
-            message: Some(ClaudeMessageContent {
-                role: role.to_string(),
-                content: simplified_content,
-                usage: None,
-            }),
-            timestamp: Some(timestamp.to_string()),
-            uuid: Some(uuid::Uuid::new_v4().to_string()),
-            parent_uuid: None,
-            session_id: Some(self.new_session_id.clone()),
-            cwd: Some(self.project_path.clone()),
-            version: Some("2.0.55".to_string()), // 使用真实版本号，避免被识别为特殊模式
-            git_branch: None,
-            user_type: if role == "user" { Some("external".to_string()) } else { None },
-            is_sidechain: Some(false),
-            subtype: None,
-            received_at: if role != "user" { Some(timestamp.to_string()) } else { None },
-            sent_at: if role == "user" { Some(timestamp.to_string()) } else { None },
+        build_claude_message(
+            &self.new_session_id,
+            &self.project_path,
+            message_type,
+            role,
+            simplified_content,
+            timestamp,
             model,
-            conversion_source: None,
-            extra: HashMap::new(),
-        }
+        )
     }
 
     pub fn convert(&self) -> Result<ConversionResult, String> {
@@ -835,37 +853,7 @@ impl CodexToClaudeConverter {
             .first()
             .and_then(|e| e.timestamp.clone())
             .unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
-        let snapshot_uuid = uuid::Uuid::new_v4().to_string();
-
-        claude_messages.push(ClaudeMessage {
-            message_type: "file-history-snapshot".to_string(),
-            message: None,
-            timestamp: Some(first_timestamp.clone()),
-            uuid: Some(snapshot_uuid.clone()),
-            parent_uuid: None,
-            session_id: None,
-            cwd: None,
-            version: None,
-            git_branch: None,
-            user_type: None,
-            is_sidechain: None,
-            subtype: None,
-            received_at: None,
-            sent_at: None,
-            model: None,
-            conversion_source: None,
-            extra: {
-                let mut map = HashMap::new();
-                map.insert("messageId".to_string(), Value::String(snapshot_uuid.clone()));
-                map.insert("snapshot".to_string(), serde_json::json!({
-                    "messageId": snapshot_uuid,
-                    "trackedFileBackups": {},
-                    "timestamp": first_timestamp
-                }));
-                map.insert("isSnapshotUpdate".to_string(), Value::Bool(false));
-                map
-            },
-        });
+        claude_messages.push(file_history_snapshot(&first_timestamp));
 
         // 3b. 转换 Codex 事件
         for event in &codex_events {
@@ -944,7 +932,7 @@ impl CodexToClaudeConverter {
     }
 
     /// 转换单个 Codex 事件为 Claude 消息
-    fn convert_codex_event(&self, event: &CodexEvent) -> Option<ClaudeMessage> {
+    pub fn convert_codex_event(&self, event: &CodexEvent) -> Option<ClaudeMessage> {
         let timestamp = event
             .timestamp
             .clone()
@@ -1147,40 +1135,244 @@ impl CodexToClaudeConverter {
 
     /// 写入 Claude session 文件
     fn write_claude_session(&self, messages: &[ClaudeMessage]) -> Result<String, String> {
-        let claude_dir = super::super::claude::get_claude_dir()
-            .map_err(|e| format!("Failed to get Claude directory: {}", e))?;
+        write_claude_session_file(&self.project_id, &self.new_session_id, messages)
+    }
+}
 
-        // 直接使用 project_id（实际的目录名）
-        let project_dir = claude_dir.join("projects").join(&self.project_id);
+// ================================
+// Claude session 写入（Codex / Gemini → Claude 共用）
+// ================================
+
+/// 创建标准 Claude 消息
+fn build_claude_message(
+    session_id: &str,
+    project_path: &str,
+    message_type: &str,
+    role: &str,
+    content: Option<Value>,
+    timestamp: &str,
+    model: Option<String>,
+) -> ClaudeMessage {
+    ClaudeMessage {
+        message_type: message_type.to_string(),
+        message: Some(ClaudeMessageContent {
+            role: role.to_string(),
+            content,
+            usage: None,
+        }),
+        timestamp: Some(timestamp.to_string()),
+        uuid: Some(uuid::Uuid::new_v4().to_string()),
+        parent_uuid: None,
+        session_id: Some(session_id.to_string()),
+        cwd: Some(project_path.to_string()),
+        version: Some("2.0.55".to_string()), // 使用真实版本号，避免被识别为特殊模式
+        git_branch: None,
+        user_type: if role == "user" { Some("external".to_string()) } else { None },
+        is_sidechain: Some(false),
+        subtype: None,
+        received_at: if role != "user" { Some(timestamp.to_string()) } else { None },
+        sent_at: if role == "user" { Some(timestamp.to_string()) } else { None },
+        model,
+        conversion_source: None,
+        extra: HashMap::new(),
+    }
+}
 
-        std::fs::create_dir_all(&project_dir)
-            .map_err(|e| format!("Failed to create project directory: {}", e))?;
+/// file-history-snapshot 消息（Claude session 的第一条消息，必需！）
+fn file_history_snapshot(timestamp: &str) -> ClaudeMessage {
+    let snapshot_uuid = uuid::Uuid::new_v4().to_string();
+    ClaudeMessage {
+        message_type: "file-history-snapshot".to_string(),
+        message: None,
+        timestamp: Some(timestamp.to_string()),
+        uuid: Some(snapshot_uuid.clone()),
+        parent_uuid: None,
+        session_id: None,
+        cwd: None,
+        version: None,
+        git_branch: None,
+        user_type: None,
+        is_sidechain: None,
+        subtype: None,
+        received_at: None,
+        sent_at: None,
+        model: None,
+        conversion_source: None,
+        extra: {
+            let mut map = HashMap::new();
+            map.insert("messageId".to_string(), Value::String(snapshot_uuid.clone()));
+            map.insert("snapshot".to_string(), serde_json::json!({
+                "messageId": snapshot_uuid,
+                "trackedFileBackups": {},
+                "timestamp": timestamp
+            }));
+            map.insert("isSnapshotUpdate".to_string(), Value::Bool(false));
+            map
+        },
+    }
+}
 
-        let file_path = project_dir.join(format!("{}.jsonl", self.new_session_id));
+/// 写入 Claude session 文件，返回文件路径
+fn write_claude_session_file(
+    project_id: &str,
+    session_id: &str,
+    messages: &[ClaudeMessage],
+) -> Result<String, String> {
+    let claude_dir = super::super::claude::get_claude_dir()
+        .map_err(|e| format!("Failed to get Claude directory: {}", e))?;
 
-        let mut file = std::fs::File::create(&file_path)
-            .map_err(|e| format!("Failed to create session file: {}", e))?;
+    // 直接使用 project_id（实际的目录名）
+    let project_dir = claude_dir.join("projects").join(project_id);
 
-        // 建立 parentUuid 消息链
-        let mut prev_uuid: Option<String> = None;
-        let mut linked_messages = messages.to_vec();
+    std::fs::create_dir_all(&project_dir)
+        .map_err(|e| format!("Failed to create project directory: {}", e))?;
 
-        for msg in &mut linked_messages {
-            // 设置 parent_uuid 指向前一条消息
-            msg.parent_uuid = prev_uuid.clone();
-            // 更新 prev_uuid 为当前消息的 uuid
-            prev_uuid = msg.uuid.clone();
+    let file_path = project_dir.join(format!("{}.jsonl", session_id));
+
+    let mut file = std::fs::File::create(&file_path)
+        .map_err(|e| format!("Failed to create session file: {}", e))?;
+
+    // 建立 parentUuid 消息链
+    let mut prev_uuid: Option<String> = None;
+    let mut linked_messages = messages.to_vec();
+
+    for msg in &mut linked_messages {
+        // 设置 parent_uuid 指向前一条消息
+        msg.parent_uuid = prev_uuid.clone();
+        // 更新 prev_uuid 为当前消息的 uuid
+        prev_uuid = msg.uuid.clone();
+    }
+
+    // 写入文件
+    for msg in &linked_messages {
+        let line = serde_json::to_string(msg)
+            .map_err(|e| format!("Failed to serialize message: {}", e))?;
+        writeln!(file, "{}", line)
+            .map_err(|e| format!("Failed to write message: {}", e))?;
+    }
+
+    Ok(file_path.to_string_lossy().to_string())
+}
+
+// ================================
+// Gemini → Claude 转换器
+// ================================
+
+/// Gemini Session → Claude Session 转换器
+/// 基于 Gemini 历史记录的统一消息格式（`convert_history_message_to_unified`）
+pub struct GeminiToClaudeConverter {
+    source_session_id: String,
+    project_id: String,     // Claude 项目目录名（由项目路径编码）
+    project_path: String,   // 原始项目路径（Gemini 按其哈希存储会话）
+    new_session_id: String, // UUID 格式
+}
+
+impl GeminiToClaudeConverter {
+    pub fn new(source_session_id: String, project_path: String) -> Self {
+        Self {
+            source_session_id,
+            project_id: super::super::claude::encode_project_path(&project_path),
+            project_path,
+            new_session_id: uuid::Uuid::new_v4().to_string(),
         }
+    }
 
-        // 写入文件
-        for msg in &linked_messages {
-            let line = serde_json::to_string(msg)
-                .map_err(|e| format!("Failed to serialize message: {}", e))?;
-            writeln!(file, "{}", line)
-                .map_err(|e| format!("Failed to write message: {}", e))?;
+    pub fn convert(&self) -> Result<ConversionResult, String> {
+        log::info!(
+            "Converting Gemini session {} to Claude",
+            self.source_session_id
+        );
+
+        // 1. 读取源 Gemini 聊天记录
+        let detail = read_session_detail(&self.project_path, &self.source_session_id)?;
+        if detail.messages.is_empty() {
+            return Err(format!("Gemini session {} has no messages", self.source_session_id));
         }
 
-        Ok(file_path.to_string_lossy().to_string())
+        // 2. 转换为 Claude 消息
+        let mut claude_messages = vec![file_history_snapshot(&detail.start_time)];
+        for message in &detail.messages {
+            for unified in convert_history_message_to_unified(message) {
+                if let Some(msg) = self.convert_unified_message(&unified, &detail.start_time) {
+                    claude_messages.push(msg);
+                }
+            }
+        }
+
+        // 3. 写入目标文件
+        let target_path =
+            write_claude_session_file(&self.project_id, &self.new_session_id, &claude_messages)?;
+
+        log::info!(
+            "Successfully converted {} Gemini messages to Claude session {}",
+            detail.messages.len(),
+            self.new_session_id
+        );
+
+        Ok(ConversionResult {
+            success: true,
+            new_session_id: self.new_session_id.clone(),
+            target_engine: "claude".to_string(),
+            message_count: claude_messages.len(),
+            source: ConversionSource {
+                engine: "gemini".to_string(),
+                session_id: self.source_session_id.clone(),
+                converted_at: chrono::Utc::now().to_rfc3339(),
+                source_project_path: self.project_path.clone(),
+            },
+            target_path,
+            error: None,
+        })
+    }
+
+    /// 将一条统一格式消息转换为 Claude 消息；空消息返回 None
+    fn convert_unified_message(&self, unified: &Value, fallback_timestamp: &str) -> Option<ClaudeMessage> {
+        let message_type = unified.get("type")?.as_str()?;
+        let blocks = unified["message"]["content"].as_array()?;
+
+        let content: Vec<Value> = blocks
+            .iter()
+            .filter_map(|block| match block.get("type").and_then(|t| t.as_str()) {
+                Some("text") if block["text"].as_str().unwrap_or("").is_empty() => None,
+                // Claude 要求 tool_use / tool_result 带 ID
+                Some("tool_use") => {
+                    let id = block["id"].as_str()?;
+                    let name = block["name"].as_str()?;
+                    Some(serde_json::json!({
+                        "type": "tool_use",
+                        "id": id,
+                        "name": map_gemini_to_claude_tool(name),
+                        "input": block["input"]
+                    }))
+                }
+                Some("tool_result") => {
+                    block["tool_use_id"].as_str()?;
+                    Some(block.clone())
+                }
+                _ => Some(block.clone()),
+            })
+            .collect();
+        if content.is_empty() {
+            return None;
+        }
+
+        let timestamp = unified
+            .get("timestamp")
+            .and_then(|t| t.as_str())
+            .unwrap_or(fallback_timestamp);
+        let model = unified["geminiMetadata"]["model"]
+            .as_str()
+            .map(|m| m.to_string());
+
+        Some(build_claude_message(
+            &self.new_session_id,
+            &self.project_path,
+            message_type,
+            message_type,
+            Some(Value::Array(content)),
+            timestamp,
+            model,
+        ))
     }
 }
 
@@ -1250,6 +1442,43 @@ pub async fn convert_session(
     }
 }
 
+/// 跨工具转换接口：按源 / 目标工具转换 session
+/// 目前支持 Gemini → Claude，以及 Claude ↔ Codex（委托给 `convert_session`）。
+/// Gemini 按项目路径存储会话，因此需要 `project_path`。
+#[tauri::command]
+pub async fn convert_session_between_tools(
+    source_session_id: String,
+    source_tool: String,
+    target_tool: String,
+    project_path: String,
+) -> Result<ConversionResult, String> {
+    log::info!(
+        "Converting {} session {} to {}, project_path: {}",
+        source_tool,
+        source_session_id,
+        target_tool,
+        project_path
+    );
+
+    match (source_tool.as_str(), target_tool.as_str()) {
+        ("gemini", "claude") => {
+            GeminiToClaudeConverter::new(source_session_id, project_path).convert()
+        }
+        ("claude", "codex") | ("codex", "claude") => {
+            let project_id = super::super::claude::encode_project_path(&project_path);
+            convert_session(source_session_id, target_tool, project_id, project_path).await
+        }
+        _ if source_tool == target_tool => Err(format!(
+            "Session {} is already a {} session",
+            source_session_id, target_tool
+        )),
+        _ => Err(format!(
+            "Converting {} sessions to {} is not supported",
+            source_tool, target_tool
+        )),
+    }
+}
+
 /// 便捷接口：Claude → Codex
 #[tauri::command]
 pub async fn convert_claude_to_codex(
@@ -0,0 +1,203 @@
+/**
+ * Codex Session Storage Migration
+ *
+ * Codex keeps sessions at ~/.codex/sessions/YYYY/MM/DD/rollout-*.jsonl, and
+ * `list_codex_sessions` only looks at that depth. Sessions written by older
+ * versions (flat files, month-only folders) are invisible there; this moves
+ * them into the date-nested layout after copying the originals to a backup.
+ */
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::config::get_codex_sessions_dir;
+
+/// One relocated session file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MigratedSession {
+    pub session_id: String,
+    pub from: String,
+    pub to: String,
+}
+
+/// Outcome of `migrate_session_storage`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMigrationReport {
+    /// Session files found outside the YYYY/MM/DD layout
+    pub legacy_found: usize,
+    pub migrated: Vec<MigratedSession>,
+    /// Where the originals were copied before moving; None when nothing moved
+    pub backup_dir: Option<String>,
+    pub errors: Vec<String>,
+}
+
+/// Moves Codex sessions stored in legacy layouts into YYYY/MM/DD folders
+/// Originals are copied to ~/.codex/sessions-backup/<timestamp>/ first.
+#[tauri::command]
+pub async fn migrate_session_storage() -> Result<SessionMigrationReport, String> {
+    let sessions_dir = get_codex_sessions_dir()?;
+    log::info!("[Codex] Checking session storage layout in {:?}", sessions_dir);
+
+    let mut report = SessionMigrationReport::default();
+    if !sessions_dir.exists() {
+        return Ok(report);
+    }
+
+    let legacy: Vec<PathBuf> = WalkDir::new(&sessions_dir)
+        .into_iter()
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| entry.path().extension().and_then(|s| s.to_str()) == Some("jsonl"))
+        .filter(|entry| !is_date_nested(&sessions_dir, entry.path()))
+        .map(|entry| entry.into_path())
+        .collect();
+    report.legacy_found = legacy.len();
+    if legacy.is_empty() {
+        return Ok(report);
+    }
+
+    let backup_dir = sessions_dir
+        .parent()
+        .unwrap_or(&sessions_dir)
+        .join("sessions-backup")
+        .join(chrono::Local::now().format("%Y%m%d-%H%M%S").to_string());
+
+    for path in legacy {
+        let relative = path.strip_prefix(&sessions_dir).unwrap_or(&path).to_path_buf();
+        let (session_id, timestamp) = match read_session_meta(&path) {
+            Some(meta) => meta,
+            None => {
+                report
+                    .errors
+                    .push(format!("{}: no session_meta header", relative.display()));
+                continue;
+            }
+        };
+
+        let target = sessions_dir.join(target_relative_path(&timestamp, &path, &session_id));
+        if target.exists() {
+            report
+                .errors
+                .push(format!("{}: {} already exists", relative.display(), target.display()));
+            continue;
+        }
+
+        let moved = backup_file(&backup_dir, &relative, &path).and_then(|_| {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create directory: {}", e))?;
+            }
+            fs::rename(&path, &target).map_err(|e| format!("Failed to move file: {}", e))
+        });
+
+        match moved {
+            Ok(()) => report.migrated.push(MigratedSession {
+                session_id,
+                from: path.to_string_lossy().to_string(),
+                to: target.to_string_lossy().to_string(),
+            }),
+            Err(e) => report.errors.push(format!("{}: {}", relative.display(), e)),
+        }
+    }
+
+    if !report.migrated.is_empty() {
+        report.backup_dir = Some(backup_dir.to_string_lossy().to_string());
+    }
+    log::info!(
+        "[Codex] Migrated {} of {} legacy session file(s), {} error(s)",
+        report.migrated.len(),
+        report.legacy_found,
+        report.errors.len()
+    );
+    Ok(report)
+}
+
+/// Whether `path` sits at sessions/YYYY/MM/DD/<file>
+fn is_date_nested(sessions_dir: &Path, path: &Path) -> bool {
+    let relative = match path.strip_prefix(sessions_dir) {
+        Ok(relative) => relative,
+        Err(_) => return false,
+    };
+    let parts: Vec<&str> = relative.iter().filter_map(|p| p.to_str()).collect();
+    match parts.as_slice() {
+        [year, month, day, _file] => {
+            [(year, 4), (month, 2), (day, 2)]
+                .iter()
+                .all(|(part, len)| part.len() == *len && part.chars().all(|c| c.is_ascii_digit()))
+        }
+        _ => false,
+    }
+}
+
+/// Destination below the sessions dir, dated by the session's local start day
+fn target_relative_path(timestamp: &str, path: &Path, session_id: &str) -> PathBuf {
+    let started = chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|dt| dt.with_timezone(&chrono::Local))
+        .unwrap_or_else(|_| chrono::Local::now());
+
+    let file_name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .filter(|name| name.starts_with("rollout-"))
+        .map(String::from)
+        .unwrap_or_else(|| {
+            format!("rollout-{}-{}.jsonl", started.format("%Y-%m-%dT%H-%M-%S"), session_id)
+        });
+
+    PathBuf::from(started.format("%Y").to_string())
+        .join(started.format("%m").to_string())
+        .join(started.format("%d").to_string())
+        .join(file_name)
+}
+
+/// Session ID and start timestamp from the session_meta header
+fn read_session_meta(path: &Path) -> Option<(String, String)> {
+    let file = fs::File::open(path).ok()?;
+    let meta = BufReader::new(file)
+        .lines()
+        .take(10)
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(&line).ok())
+        .find(|event| event["type"].as_str() == Some("session_meta"))?;
+    let payload = &meta["payload"];
+    let id = payload["id"].as_str()?.to_string();
+    let timestamp = payload["timestamp"]
+        .as_str()
+        .or_else(|| meta["timestamp"].as_str())
+        .unwrap_or_default()
+        .to_string();
+    Some((id, timestamp))
+}
+
+fn backup_file(backup_dir: &Path, relative: &Path, path: &Path) -> Result<(), String> {
+    let backup = backup_dir.join(relative);
+    if let Some(parent) = backup.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create backup directory: {}", e))?;
+    }
+    fs::copy(path, &backup)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to back up file: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_legacy_layouts() {
+        let root = Path::new("/home/u/.codex/sessions");
+        assert!(is_date_nested(root, &root.join("2025/11/23/rollout-a.jsonl")));
+        assert!(!is_date_nested(root, &root.join("rollout-a.jsonl")));
+        assert!(!is_date_nested(root, &root.join("2025/11/rollout-a.jsonl")));
+        assert!(!is_date_nested(root, &root.join("old/sub/dir/rollout-a.jsonl")));
+
+        let target = target_relative_path("2025-03-04T10:00:00+00:00", Path::new("x/abc.jsonl"), "id-1");
+        assert_eq!(target.iter().count(), 4);
+        assert!(target.to_string_lossy().ends_with("-id-1.jsonl"));
+    }
+}
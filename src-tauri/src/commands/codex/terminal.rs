@@ -0,0 +1,176 @@
+/**
+ * Codex Terminal Module
+ *
+ * Opens the OS terminal in a project directory, optionally with the interactive
+ * Codex CLI already running, for users who want to drop down to the CLI.
+ */
+
+use std::path::Path;
+use std::process::Command as StdCommand;
+
+use crate::claude_binary::detect_binary_for_tool;
+#[cfg(target_os = "windows")]
+use super::super::wsl_utils;
+
+/// Opens a terminal in `project_path`, starting `codex` in it unless `launch_codex` is false
+/// In WSL mode the terminal opens a WSL shell in the converted project path.
+/// Returns the name of the terminal that was launched.
+#[tauri::command]
+pub async fn open_codex_terminal(
+    project_path: String,
+    launch_codex: Option<bool>,
+) -> Result<String, String> {
+    if !Path::new(&project_path).is_dir() {
+        return Err(format!("Project path does not exist: {}", project_path));
+    }
+    let launch_codex = launch_codex.unwrap_or(true);
+    log::info!(
+        "[Codex] Opening terminal in {} (launch codex: {})",
+        project_path,
+        launch_codex
+    );
+
+    #[cfg(target_os = "windows")]
+    {
+        let wsl_config = wsl_utils::get_wsl_config();
+        if wsl_config.enabled {
+            return open_wsl_terminal(&project_path, launch_codex, wsl_config);
+        }
+    }
+
+    let codex_cmd = detect_binary_for_tool("codex", "CODEX_PATH", "codex")
+        .1
+        .map(|inst| inst.path)
+        .unwrap_or_else(|| "codex".to_string());
+
+    open_native_terminal(&project_path, launch_codex.then_some(codex_cmd.as_str()))
+}
+
+#[cfg(target_os = "windows")]
+fn open_wsl_terminal(
+    project_path: &str,
+    launch_codex: bool,
+    wsl_config: &wsl_utils::WslConfig,
+) -> Result<String, String> {
+    let mut wsl_args: Vec<String> = Vec::new();
+    if let Some(ref distro) = wsl_config.distro {
+        wsl_args.push("-d".to_string());
+        wsl_args.push(distro.clone());
+    }
+    wsl_args.push("--cd".to_string());
+    wsl_args.push(wsl_utils::windows_to_wsl_path(project_path));
+
+    if launch_codex {
+        let codex = wsl_config
+            .codex_path_in_wsl
+            .clone()
+            .unwrap_or_else(|| "codex".to_string());
+        // Keep the shell open after Codex exits
+        wsl_args.extend([
+            "--".to_string(),
+            "bash".to_string(),
+            "-lic".to_string(),
+            format!("{}; exec bash", shell_quote(&codex)),
+        ]);
+    }
+
+    let mut args = vec!["wsl.exe".to_string()];
+    args.extend(wsl_args);
+    spawn_windows_terminal(project_path, &args)
+}
+
+#[cfg(target_os = "windows")]
+fn open_native_terminal(project_path: &str, codex_cmd: Option<&str>) -> Result<String, String> {
+    let mut args = vec!["cmd.exe".to_string()];
+    if let Some(codex) = codex_cmd {
+        args.push("/K".to_string());
+        // std quotes arguments containing spaces on Windows
+        args.push(codex.to_string());
+    }
+    spawn_windows_terminal(project_path, &args)
+}
+
+/// Runs `args` in Windows Terminal, falling back to a plain console window
+#[cfg(target_os = "windows")]
+fn spawn_windows_terminal(project_path: &str, args: &[String]) -> Result<String, String> {
+    let wt = StdCommand::new("wt.exe")
+        .arg("-d")
+        .arg(project_path)
+        .args(args)
+        .spawn();
+    if wt.is_ok() {
+        return Ok("Windows Terminal".to_string());
+    }
+
+    StdCommand::new("cmd.exe")
+        .args(["/C", "start", ""])
+        .args(args)
+        .current_dir(project_path)
+        .spawn()
+        .map_err(|e| format!("Failed to open terminal: {}", e))?;
+    Ok("Command Prompt".to_string())
+}
+
+#[cfg(target_os = "macos")]
+fn open_native_terminal(project_path: &str, codex_cmd: Option<&str>) -> Result<String, String> {
+    match codex_cmd {
+        Some(codex) => {
+            let script = format!("cd {} && {}", shell_quote(project_path), shell_quote(codex));
+            let apple_script = format!(
+                "tell application \"Terminal\"\nactivate\ndo script \"{}\"\nend tell",
+                script.replace('\\', "\\\\").replace('"', "\\\"")
+            );
+            StdCommand::new("osascript")
+                .arg("-e")
+                .arg(apple_script)
+                .spawn()
+                .map_err(|e| format!("Failed to open terminal: {}", e))?;
+        }
+        None => {
+            StdCommand::new("open")
+                .args(["-a", "Terminal"])
+                .arg(project_path)
+                .spawn()
+                .map_err(|e| format!("Failed to open terminal: {}", e))?;
+        }
+    }
+    Ok("Terminal".to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn open_native_terminal(project_path: &str, codex_cmd: Option<&str>) -> Result<String, String> {
+    // Keep the shell open after Codex exits
+    let shell_cmd = codex_cmd.map(|codex| format!("{}; exec bash", shell_quote(codex)));
+
+    // (terminal, arguments that run `bash -c <cmd>`)
+    let terminals: &[(&str, &[&str])] = &[
+        ("x-terminal-emulator", &["-e", "bash", "-c"]),
+        ("gnome-terminal", &["--", "bash", "-c"]),
+        ("konsole", &["-e", "bash", "-c"]),
+        ("xfce4-terminal", &["-x", "bash", "-c"]),
+        ("alacritty", &["-e", "bash", "-c"]),
+        ("kitty", &["bash", "-c"]),
+        ("wezterm", &["start", "--", "bash", "-c"]),
+        ("xterm", &["-e", "bash", "-c"]),
+    ];
+
+    for (terminal, run_args) in terminals {
+        let mut cmd = StdCommand::new(terminal);
+        cmd.current_dir(project_path);
+        if let Some(ref shell_cmd) = shell_cmd {
+            cmd.args(*run_args).arg(shell_cmd);
+        }
+        match cmd.spawn() {
+            Ok(_) => return Ok(terminal.to_string()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(format!("Failed to open {}: {}", terminal, e)),
+        }
+    }
+
+    Err("No supported terminal emulator found".to_string())
+}
+
+/// Quotes a string for POSIX shells (also used for the bash command run inside WSL)
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
@@ -0,0 +1,278 @@
+//! Configuration backup and restore
+//!
+//! Bundles the configuration files of Claude, Codex, Gemini and acemcp into a
+//! single JSON archive so a setup can be carried over to a reinstall or another
+//! machine. Session transcripts are not included.
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Archive format version, bumped when the layout changes incompatibly
+const ARCHIVE_VERSION: u32 = 1;
+
+/// Replacement for secret values in a redacted archive
+const REDACTED_PLACEHOLDER: &str = "<redacted>";
+
+/// Files included in the archive, relative to the home directory
+const CONFIG_FILES: &[&str] = &[
+    // Claude
+    ".claude/settings.json",
+    ".claude/settings.local.json",
+    ".claude/CLAUDE.md",
+    ".claude/providers.json",
+    ".claude/execution_config.json",
+    ".claude/hidden_projects.json",
+    ".claude/bookmarks.json",
+    ".claude/binaries.json",
+    ".claude/translation_config.json",
+    // Codex
+    ".codex/auth.json",
+    ".codex/config.toml",
+    ".codex/providers.json",
+    ".codex/workbench_config.json",
+    // Gemini
+    ".gemini/settings.json",
+    ".gemini/.env",
+    ".gemini/GEMINI.md",
+    ".anycode/gemini.json",
+    ".anycode/gemini_providers.json",
+    // acemcp (settings.toml is the pre-migration name of config.toml)
+    ".acemcp/config.toml",
+    ".acemcp/settings.toml",
+];
+
+/// A single file stored in the archive
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArchivedFile {
+    /// Path relative to the home directory, always with forward slashes
+    path: String,
+    content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigArchive {
+    version: u32,
+    created_at: String,
+    redacted: bool,
+    files: Vec<ArchivedFile>,
+}
+
+/// Result of `export_all_config`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigExportResult {
+    pub path: String,
+    pub files: Vec<String>,
+    pub redacted: bool,
+}
+
+/// Result of `import_all_config`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ConfigImportResult {
+    pub restored: Vec<String>,
+    /// Redacted files that were not restored because a local copy with real secrets exists
+    pub skipped: Vec<String>,
+}
+
+/// Writes all known configuration files into a single archive at `target_path`
+/// With `redact_secrets`, values of keys that look like API keys, tokens or
+/// passwords are replaced with a placeholder.
+#[tauri::command]
+pub async fn export_all_config(
+    target_path: String,
+    redact_secrets: Option<bool>,
+) -> Result<ConfigExportResult, String> {
+    let redact = redact_secrets.unwrap_or(false);
+    log::info!("Exporting configuration to {} (redacted: {})", target_path, redact);
+
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    let mut files = Vec::new();
+
+    for relative in CONFIG_FILES {
+        let path = home.join(relative);
+        if !path.is_file() {
+            continue;
+        }
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let content = if redact {
+            redact_file_content(relative, &content)
+        } else {
+            content
+        };
+        files.push(ArchivedFile {
+            path: relative.to_string(),
+            content,
+        });
+    }
+
+    if files.is_empty() {
+        return Err("No configuration files found to export".to_string());
+    }
+
+    let archive = ConfigArchive {
+        version: ARCHIVE_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        redacted: redact,
+        files,
+    };
+    let json = serde_json::to_string_pretty(&archive)
+        .map_err(|e| format!("Failed to serialize archive: {}", e))?;
+    fs::write(&target_path, json).map_err(|e| format!("Failed to write archive: {}", e))?;
+
+    log::info!("Exported {} configuration files", archive.files.len());
+    Ok(ConfigExportResult {
+        path: target_path,
+        files: archive.files.into_iter().map(|f| f.path).collect(),
+        redacted: redact,
+    })
+}
+
+/// Restores the files of an archive created by `export_all_config`
+/// Existing files are backed up as `<name>.bak` before being overwritten.
+#[tauri::command]
+pub async fn import_all_config(archive_path: String) -> Result<ConfigImportResult, String> {
+    log::info!("Importing configuration from {}", archive_path);
+
+    let content =
+        fs::read_to_string(&archive_path).map_err(|e| format!("Failed to read archive: {}", e))?;
+    let archive: ConfigArchive =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse archive: {}", e))?;
+    if archive.version > ARCHIVE_VERSION {
+        return Err(format!(
+            "Unsupported archive version {} (expected {} or lower)",
+            archive.version, ARCHIVE_VERSION
+        ));
+    }
+
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+
+    // Validate every entry before touching the filesystem
+    let mut targets = Vec::with_capacity(archive.files.len());
+    for file in &archive.files {
+        targets.push(resolve_archive_path(&home, &file.path)?);
+    }
+
+    let mut restored = Vec::new();
+    let mut skipped = Vec::new();
+
+    for (file, target) in archive.files.iter().zip(targets) {
+        if target.exists() {
+            // Restoring a redacted file would replace real secrets with the placeholder
+            if archive.redacted && file.content.contains(REDACTED_PLACEHOLDER) {
+                skipped.push(file.path.clone());
+                continue;
+            }
+            let mut backup = target.clone().into_os_string();
+            backup.push(".bak");
+            fs::copy(&target, &backup)
+                .map_err(|e| format!("Failed to back up {}: {}", target.display(), e))?;
+        } else if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        fs::write(&target, &file.content)
+            .map_err(|e| format!("Failed to write {}: {}", target.display(), e))?;
+        restored.push(file.path.clone());
+    }
+
+    log::info!(
+        "Restored {} configuration files, skipped {}",
+        restored.len(),
+        skipped.len()
+    );
+    Ok(ConfigImportResult { restored, skipped })
+}
+
+/// Redacted contents of every existing config file, keyed by home-relative path
+/// Unreadable files are skipped; used for diagnostics bundles.
+pub(crate) fn read_redacted_config() -> Result<Vec<(String, String)>, String> {
+    let home = dirs::home_dir().ok_or("Cannot find home directory")?;
+    Ok(CONFIG_FILES
+        .iter()
+        .filter_map(|relative| {
+            let content = fs::read_to_string(home.join(relative)).ok()?;
+            Some((relative.to_string(), redact_file_content(relative, &content)))
+        })
+        .collect())
+}
+
+/// Maps an archive entry to its location under `home`, rejecting anything that could escape it
+fn resolve_archive_path(home: &Path, relative: &str) -> Result<PathBuf, String> {
+    let path = Path::new(relative);
+    let is_safe = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_)));
+    if !is_safe || relative.is_empty() {
+        return Err(format!("Invalid path in archive: {}", relative));
+    }
+    Ok(home.join(path))
+}
+
+/// Whether a config key name refers to a credential
+/// Codex's `env_key` only names the environment variable holding the key.
+pub(crate) fn is_secret_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    ["key", "token", "secret", "password"]
+        .iter()
+        .any(|marker| key.contains(marker))
+        && key != "env_key"
+}
+
+fn redact_file_content(relative: &str, content: &str) -> String {
+    if relative.ends_with(".json") {
+        if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(content) {
+            redact_json(&mut value);
+            if let Ok(redacted) = serde_json::to_string_pretty(&value) {
+                return redacted;
+            }
+        }
+    }
+    redact_key_value_lines(content)
+}
+
+pub(crate) fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if child.is_string() && is_secret_key(key) {
+                    *child = serde_json::Value::String(REDACTED_PLACEHOLDER.to_string());
+                } else {
+                    redact_json(child);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+/// Redacts `key = value` (TOML) and `KEY=value` (.env) lines with secret-looking keys
+pub(crate) fn redact_key_value_lines(content: &str) -> String {
+    let line_re = Regex::new(r#"^(\s*(?:export\s+)?["']?([\w.-]+)["']?\s*=\s*)(.+)$"#).unwrap();
+    let mut redacted = content
+        .lines()
+        .map(|line| match line_re.captures(line) {
+            Some(caps) if is_secret_key(&caps[2]) => {
+                let quoted = caps[3].trim_start().starts_with('"');
+                if quoted {
+                    format!("{}\"{}\"", &caps[1], REDACTED_PLACEHOLDER)
+                } else {
+                    format!("{}{}", &caps[1], REDACTED_PLACEHOLDER)
+                }
+            }
+            _ => line.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if content.ends_with('\n') {
+        redacted.push('\n');
+    }
+    redacted
+}
@@ -0,0 +1,294 @@
+//! Streaming content search
+//!
+//! Greps the files under a directory for a plain-text query and emits every
+//! matching line as a `content-search-hit` event as soon as it is found, then a
+//! single `content-search-done` event. Searches run on a blocking thread and
+//! check a cancel flag between files, so `cancel_content_search` stops them early.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+
+use super::claude::is_skipped_search_dir;
+
+/// Hits emitted before a search stops on its own
+const DEFAULT_MAX_RESULTS: usize = 1000;
+
+/// Files larger than this are not searched
+const MAX_FILE_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Bytes inspected for NUL to tell binary files apart
+const BINARY_SNIFF_BYTES: usize = 8192;
+
+/// Longest line text sent in a hit, in characters
+const MAX_LINE_CHARS: usize = 500;
+
+/// Cancel flags of running searches keyed by search ID
+#[derive(Default)]
+pub struct ContentSearchState {
+    searches: Arc<Mutex<HashMap<String, Arc<AtomicBool>>>>,
+}
+
+/// Payload of a `content-search-hit` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentSearchHit {
+    pub search_id: String,
+    /// Path relative to the search base, `/`-separated
+    pub path: String,
+    /// 1-based
+    pub line_number: usize,
+    pub line: String,
+}
+
+/// Payload of the `content-search-done` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContentSearchDone {
+    pub search_id: String,
+    pub hits: usize,
+    pub files_searched: usize,
+    pub cancelled: bool,
+    /// True when the search stopped at `max_results`
+    pub truncated: bool,
+}
+
+/// Starts searching the files under `base_path` for `query` and returns the search ID
+/// Hits arrive as `content-search-hit` events; `content-search-done` ends the search.
+#[tauri::command]
+pub async fn start_content_search(
+    base_path: String,
+    query: String,
+    case_sensitive: Option<bool>,
+    max_results: Option<usize>,
+    app: AppHandle,
+    state: State<'_, ContentSearchState>,
+) -> Result<String, String> {
+    let base = PathBuf::from(&base_path);
+    if !base.is_dir() {
+        return Err(format!("Search path is not a directory: {}", base_path));
+    }
+    if query.is_empty() {
+        return Err("Search query is empty".to_string());
+    }
+
+    let search_id = uuid::Uuid::new_v4().to_string();
+    let cancel = Arc::new(AtomicBool::new(false));
+    state
+        .searches
+        .lock()
+        .await
+        .insert(search_id.clone(), cancel.clone());
+    log::info!(
+        "Starting content search {} for {:?} in {}",
+        search_id,
+        query,
+        base_path
+    );
+
+    let searches = state.searches.clone();
+    let id = search_id.clone();
+    tokio::spawn(async move {
+        let search = ContentSearch {
+            search_id: id.clone(),
+            base: base.clone(),
+            query,
+            case_sensitive: case_sensitive.unwrap_or(false),
+            max_results: max_results.unwrap_or(DEFAULT_MAX_RESULTS),
+            cancel,
+            app: app.clone(),
+        };
+        let done = tokio::task::spawn_blocking(move || search.run())
+            .await
+            .unwrap_or_else(|e| {
+                log::error!("Content search {} panicked: {}", id, e);
+                ContentSearchDone {
+                    search_id: id.clone(),
+                    hits: 0,
+                    files_searched: 0,
+                    cancelled: false,
+                    truncated: false,
+                }
+            });
+
+        searches.lock().await.remove(&id);
+        log::info!(
+            "Content search {} finished: {} hit(s) in {} file(s){}",
+            id,
+            done.hits,
+            done.files_searched,
+            if done.cancelled { " (cancelled)" } else { "" }
+        );
+        let _ = app.emit("content-search-done", &done);
+    });
+
+    Ok(search_id)
+}
+
+/// Stops a running search; it still emits `content-search-done` with `cancelled` set
+/// Returns false if no search with that ID is running.
+#[tauri::command]
+pub async fn cancel_content_search(
+    search_id: String,
+    state: State<'_, ContentSearchState>,
+) -> Result<bool, String> {
+    match state.searches.lock().await.get(&search_id) {
+        Some(cancel) => {
+            cancel.store(true, Ordering::Relaxed);
+            log::info!("Cancelling content search {}", search_id);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+struct ContentSearch {
+    search_id: String,
+    base: PathBuf,
+    query: String,
+    case_sensitive: bool,
+    max_results: usize,
+    cancel: Arc<AtomicBool>,
+    app: AppHandle,
+}
+
+impl ContentSearch {
+    fn run(&self) -> ContentSearchDone {
+        let mut done = ContentSearchDone {
+            search_id: self.search_id.clone(),
+            hits: 0,
+            files_searched: 0,
+            cancelled: false,
+            truncated: false,
+        };
+        let needle = if self.case_sensitive {
+            self.query.clone()
+        } else {
+            self.query.to_lowercase()
+        };
+
+        let mut pending = vec![self.base.clone()];
+        while let Some(dir) = pending.pop() {
+            let entries = match fs::read_dir(&dir) {
+                Ok(entries) => entries,
+                Err(e) => {
+                    log::debug!("Skipping unreadable directory {:?}: {}", dir, e);
+                    continue;
+                }
+            };
+
+            for entry in entries.flatten() {
+                if self.cancel.load(Ordering::Relaxed) {
+                    done.cancelled = true;
+                    return done;
+                }
+
+                let path = entry.path();
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with('.') {
+                    continue;
+                }
+                let file_type = match entry.file_type() {
+                    Ok(file_type) => file_type,
+                    Err(_) => continue,
+                };
+                if file_type.is_dir() {
+                    if !is_skipped_search_dir(&name) {
+                        pending.push(path);
+                    }
+                    continue;
+                }
+
+                let content = match read_text_file(&path) {
+                    Some(content) => content,
+                    None => continue,
+                };
+                done.files_searched += 1;
+
+                for (line_number, line) in
+                    find_matching_lines(&content, &needle, self.case_sensitive)
+                {
+                    let _ = self.app.emit(
+                        "content-search-hit",
+                        &ContentSearchHit {
+                            search_id: self.search_id.clone(),
+                            path: relative_path(&self.base, &path),
+                            line_number,
+                            line,
+                        },
+                    );
+                    done.hits += 1;
+                    if done.hits >= self.max_results {
+                        done.truncated = true;
+                        return done;
+                    }
+                }
+            }
+        }
+        done
+    }
+}
+
+/// Reads a file as text, or None for large, binary or unreadable files
+fn read_text_file(path: &Path) -> Option<String> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_FILE_BYTES {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(metadata.len() as usize);
+    fs::File::open(path).ok()?.read_to_end(&mut bytes).ok()?;
+    if bytes[..bytes.len().min(BINARY_SNIFF_BYTES)].contains(&0) {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+fn relative_path(base: &Path, path: &Path) -> String {
+    path.strip_prefix(base)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// Lines containing `needle` as (1-based line number, trimmed line)
+/// `needle` must already be lowercased for case-insensitive searches.
+fn find_matching_lines(content: &str, needle: &str, case_sensitive: bool) -> Vec<(usize, String)> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            if case_sensitive {
+                line.contains(needle)
+            } else {
+                line.to_lowercase().contains(needle)
+            }
+        })
+        .map(|(i, line)| (i + 1, line.trim().chars().take(MAX_LINE_CHARS).collect()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_lines_with_and_without_case() {
+        let content = "fn main() {\n    let Config = load();\n    config.run();\n}";
+        let insensitive = find_matching_lines(content, "config", false);
+        assert_eq!(
+            insensitive,
+            vec![
+                (2, "let Config = load();".to_string()),
+                (3, "config.run();".to_string())
+            ]
+        );
+        assert_eq!(find_matching_lines(content, "Config", true).len(), 1);
+    }
+}
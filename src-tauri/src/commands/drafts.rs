@@ -0,0 +1,89 @@
+//! Composer drafts
+//!
+//! The composer autosaves its unsent prompt so it survives a crash or restart.
+//! Each project/tool pair has one draft file under ~/.claude/drafts/<tool>/,
+//! written through a temp file so a crash mid-write never leaves a torn draft.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::PathBuf;
+
+use super::claude::get_claude_dir;
+
+/// A saved composer draft
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Draft {
+    pub project_id: String,
+    pub tool: String,
+    pub text: String,
+    pub updated_at: i64,
+}
+
+fn get_draft_path(project_id: &str, tool: &str) -> Result<PathBuf, String> {
+    if !matches!(tool, "claude" | "codex" | "gemini") {
+        return Err(format!("Unsupported tool: {}", tool));
+    }
+    if project_id.trim().is_empty() {
+        return Err("Project ID is empty".to_string());
+    }
+
+    // Project IDs may be paths; hash them so the file name is portable and
+    // distinct IDs never map to the same draft
+    let file_stem = format!("{:x}", Sha256::digest(project_id.as_bytes()));
+    Ok(get_claude_dir()
+        .map_err(|e| e.to_string())?
+        .join("drafts")
+        .join(tool)
+        .join(format!("{}.json", file_stem)))
+}
+
+/// Saves the composer draft of a project; an empty text deletes the draft
+#[tauri::command]
+pub async fn save_draft(project_id: String, tool: String, text: String) -> Result<(), String> {
+    let path = get_draft_path(&project_id, &tool)?;
+
+    if text.trim().is_empty() {
+        if path.exists() {
+            fs::remove_file(&path).map_err(|e| format!("Failed to delete draft: {}", e))?;
+        }
+        return Ok(());
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create drafts directory: {}", e))?;
+    }
+    let draft = Draft {
+        project_id,
+        tool,
+        text,
+        updated_at: chrono::Utc::now().timestamp(),
+    };
+    let content =
+        serde_json::to_string(&draft).map_err(|e| format!("Failed to serialize draft: {}", e))?;
+
+    let temp = path.with_extension("json.tmp");
+    fs::write(&temp, content).map_err(|e| format!("Failed to write draft: {}", e))?;
+    fs::rename(&temp, &path).map_err(|e| format!("Failed to write draft: {}", e))
+}
+
+/// Returns the saved draft of a project, if any
+#[tauri::command]
+pub async fn get_draft(project_id: String, tool: String) -> Result<Option<Draft>, String> {
+    let path = get_draft_path(&project_id, &tool)?;
+    if !path.exists() {
+        return Ok(None);
+    }
+
+    let content = fs::read_to_string(&path).map_err(|e| format!("Failed to read draft: {}", e))?;
+    let draft: Draft =
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse draft: {}", e))?;
+
+    // Guards against a corrupted or hand-edited file; never restore another project's text
+    if draft.project_id != project_id {
+        return Ok(None);
+    }
+    Ok(Some(draft))
+}
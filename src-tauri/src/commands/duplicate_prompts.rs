@@ -0,0 +1,186 @@
+//! Duplicate prompt detection
+//!
+//! Finds prompts that were sent more than once in a session (typically re-sent by
+//! accident after resuming) so the UI can flag redundant turns before a fork or export.
+//! Prompts match when they are identical after whitespace/case normalization, or
+//! when their character-bigram similarity reaches `NEAR_DUPLICATE_THRESHOLD`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::codex::extract_codex_prompts;
+use super::prompt_tracker::extract_prompts_from_jsonl;
+use super::transcript::{find_claude_session_file, load_transcript};
+
+/// Dice similarity at or above which two prompts count as near-identical
+pub(super) const NEAR_DUPLICATE_THRESHOLD: f64 = 0.9;
+
+/// Maximum number of characters of the prompt preview returned per group
+const PREVIEW_MAX_CHARS: usize = 120;
+
+/// A set of prompts that repeat each other
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicatePromptGroup {
+    /// Prompt indices, ascending (same numbering as the prompt list / rewind)
+    pub indices: Vec<usize>,
+    pub preview: String,
+    /// True when every prompt in the group is identical after normalization
+    pub exact: bool,
+    /// Lowest similarity between the first prompt and any other member
+    pub similarity: f64,
+}
+
+/// Groups identical or near-identical prompts of a session ("claude", "codex" or "gemini")
+#[tauri::command]
+pub async fn find_duplicate_prompts(
+    session_id: String,
+    tool: String,
+) -> Result<Vec<DuplicatePromptGroup>, String> {
+    log::info!("Finding duplicate prompts in {} session {}", tool, session_id);
+
+    let prompts: Vec<(usize, String)> = match tool.as_str() {
+        "claude" => {
+            let session_file = find_claude_session_file(&session_id)?;
+            let project_id = session_file
+                .parent()
+                .and_then(|dir| dir.file_name())
+                .map(|name| name.to_string_lossy().to_string())
+                .ok_or_else(|| "Failed to determine project of session".to_string())?;
+            extract_prompts_from_jsonl(&session_id, &project_id)
+                .map_err(|e| format!("Failed to extract prompts: {}", e))?
+                .into_iter()
+                .map(|p| (p.index, p.text))
+                .collect()
+        }
+        "codex" => extract_codex_prompts(&session_id)?
+            .into_iter()
+            .map(|p| (p.index, p.text))
+            .collect(),
+        "gemini" => load_transcript("gemini", &session_id)?
+            .into_iter()
+            .filter(|entry| entry.role == "user")
+            .map(|entry| entry.text)
+            .enumerate()
+            .collect(),
+        other => return Err(format!("Unsupported tool: {}", other)),
+    };
+
+    let groups = group_duplicates(&prompts, NEAR_DUPLICATE_THRESHOLD);
+    log::info!("Found {} duplicate prompt group(s)", groups.len());
+    Ok(groups)
+}
+
+/// Greedily groups each prompt with the later prompts that match it
+fn group_duplicates(prompts: &[(usize, String)], threshold: f64) -> Vec<DuplicatePromptGroup> {
+    let normalized: Vec<String> = prompts.iter().map(|(_, text)| normalize(text)).collect();
+    let bigrams: Vec<HashMap<(char, char), usize>> =
+        normalized.iter().map(|text| char_bigrams(text)).collect();
+    let mut grouped = vec![false; prompts.len()];
+    let mut groups = Vec::new();
+
+    for i in 0..prompts.len() {
+        if grouped[i] || normalized[i].is_empty() {
+            continue;
+        }
+        let mut indices = vec![prompts[i].0];
+        let mut exact = true;
+        let mut similarity: f64 = 1.0;
+
+        for j in (i + 1)..prompts.len() {
+            if grouped[j] {
+                continue;
+            }
+            let score = if normalized[i] == normalized[j] {
+                1.0
+            } else {
+                dice_similarity(&bigrams[i], &bigrams[j])
+            };
+            if score >= threshold {
+                grouped[j] = true;
+                indices.push(prompts[j].0);
+                exact &= normalized[i] == normalized[j];
+                similarity = similarity.min(score);
+            }
+        }
+
+        if indices.len() > 1 {
+            indices.sort_unstable();
+            groups.push(DuplicatePromptGroup {
+                indices,
+                preview: preview(&prompts[i].1),
+                exact,
+                similarity,
+            });
+        }
+    }
+
+    groups
+}
+
+/// Lowercased, whitespace-collapsed form without trailing punctuation
+pub(super) fn normalize(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .trim_end_matches(|c: char| c.is_ascii_punctuation() || "。！？，".contains(c))
+        .to_string()
+}
+
+pub(super) fn char_bigrams(text: &str) -> HashMap<(char, char), usize> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut bigrams = HashMap::new();
+    for pair in chars.windows(2) {
+        *bigrams.entry((pair[0], pair[1])).or_insert(0) += 1;
+    }
+    bigrams
+}
+
+/// Sørensen–Dice coefficient over bigram multisets
+pub(super) fn dice_similarity(a: &HashMap<(char, char), usize>, b: &HashMap<(char, char), usize>) -> f64 {
+    let total: usize = a.values().sum::<usize>() + b.values().sum::<usize>();
+    if total == 0 {
+        return 0.0;
+    }
+    let shared: usize = a
+        .iter()
+        .map(|(bigram, count)| (*count).min(*b.get(bigram).unwrap_or(&0)))
+        .sum();
+    2.0 * shared as f64 / total as f64
+}
+
+fn preview(text: &str) -> String {
+    let text = text.trim();
+    match text.char_indices().nth(PREVIEW_MAX_CHARS) {
+        Some((idx, _)) => format!("{}...", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn groups_exact_and_near_duplicates() {
+        let prompts: Vec<(usize, String)> = [
+            "Fix the login bug in auth.rs",
+            "Add tests",
+            "fix the  login bug in auth.rs.",
+            "Fix the login bugs in auth.rs",
+            "修复登录页面的样式问题",
+            "Write docs",
+        ]
+        .iter()
+        .enumerate()
+        .map(|(i, text)| (i, text.to_string()))
+        .collect();
+
+        let groups = group_duplicates(&prompts, NEAR_DUPLICATE_THRESHOLD);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].indices, vec![0, 2, 3]);
+        assert!(!groups[0].exact);
+        assert!(groups[0].similarity >= NEAR_DUPLICATE_THRESHOLD);
+    }
+}
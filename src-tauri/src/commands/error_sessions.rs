@@ -0,0 +1,383 @@
+//! Sessions that hit errors
+//!
+//! Scans stored session files for the first API error or user interruption so
+//! failed conversations can be found and retried. Failed tool calls are opt-in
+//! and only count when the turn ended on them: an agent routinely recovers from
+//! a grep without matches or a missing file.
+//! Claude sessions are read from ~/.claude/projects, Codex rollouts from the
+//! Codex sessions directory. Gemini chats are not scanned: they only record a
+//! hash of the project path. The backend derives no run status for stored
+//! sessions, so errors are classified from the events here.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use super::claude::{get_claude_dir, list_projects};
+use super::codex::config::get_codex_sessions_dir;
+use super::codex::session::{is_user_prompt, parse_codex_session_file};
+use super::transcript::is_claude_prompt;
+
+/// Longest error description returned, in characters
+const MAX_DESCRIPTION_CHARS: usize = 200;
+
+/// The first error found in a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionError {
+    /// "api-error", "tool-failure" or "interrupted"
+    pub kind: String,
+    pub description: String,
+}
+
+/// What one session line means for error detection
+#[derive(Debug, Clone, PartialEq)]
+enum EntryOutcome {
+    Error(SessionError),
+    ToolFailed(SessionError),
+    ToolSucceeded,
+    TurnStarted,
+    Other,
+}
+
+/// A session containing at least one error
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorSession {
+    pub tool: String,
+    pub session_id: String,
+    pub project_path: String,
+    pub kind: String,
+    pub description: String,
+    /// Unix timestamp of the session file's last modification
+    pub updated_at: u64,
+}
+
+/// Lists the sessions of a tool ("claude" or "codex") that contain an error,
+/// most recently updated first
+/// With `include_tool_failures`, a turn that ended on a failed tool call counts too.
+#[tauri::command]
+pub async fn list_error_sessions(
+    tool: String,
+    include_tool_failures: Option<bool>,
+) -> Result<Vec<ErrorSession>, String> {
+    let include_tool_failures = include_tool_failures.unwrap_or(false);
+    let mut sessions = match tool.as_str() {
+        "claude" => list_claude_error_sessions(include_tool_failures).await?,
+        "codex" => list_codex_error_sessions(include_tool_failures)?,
+        _ => return Err(format!("Unsupported tool: {}", tool)),
+    };
+    sessions.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+
+    log::info!("Found {} {} session(s) with errors", sessions.len(), tool);
+    Ok(sessions)
+}
+
+async fn list_claude_error_sessions(
+    include_tool_failures: bool,
+) -> Result<Vec<ErrorSession>, String> {
+    let projects_dir = get_claude_dir()
+        .map_err(|e| e.to_string())?
+        .join("projects");
+
+    let mut sessions = Vec::new();
+    for project in list_projects().await? {
+        for session_id in &project.sessions {
+            let path = projects_dir
+                .join(&project.id)
+                .join(format!("{}.jsonl", session_id));
+            if let Some(error) =
+                first_error_in_file(&path, claude_entry_outcome, include_tool_failures)
+            {
+                sessions.push(ErrorSession {
+                    tool: "claude".to_string(),
+                    session_id: session_id.clone(),
+                    project_path: project.path.clone(),
+                    kind: error.kind,
+                    description: error.description,
+                    updated_at: modified_secs(&path),
+                });
+            }
+        }
+    }
+    Ok(sessions)
+}
+
+fn list_codex_error_sessions(include_tool_failures: bool) -> Result<Vec<ErrorSession>, String> {
+    let sessions_dir = get_codex_sessions_dir()?;
+    if !sessions_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut sessions = Vec::new();
+    for entry in walkdir::WalkDir::new(&sessions_dir).into_iter().flatten() {
+        let path = entry.path();
+        if path.extension().and_then(|s| s.to_str()) != Some("jsonl") {
+            continue;
+        }
+        let error = match first_error_in_file(path, codex_entry_outcome, include_tool_failures) {
+            Some(error) => error,
+            None => continue,
+        };
+        if let Some(session) = parse_codex_session_file(path) {
+            sessions.push(ErrorSession {
+                tool: "codex".to_string(),
+                session_id: session.id,
+                project_path: session.project_path,
+                kind: error.kind,
+                description: error.description,
+                updated_at: modified_secs(path),
+            });
+        }
+    }
+    Ok(sessions)
+}
+
+fn first_error_in_file(
+    path: &Path,
+    classify: fn(&Value) -> EntryOutcome,
+    include_tool_failures: bool,
+) -> Option<SessionError> {
+    let file = fs::File::open(path).ok()?;
+    let entries = BufReader::new(file)
+        .lines()
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<Value>(&line).ok());
+    first_error(entries.map(|entry| classify(&entry)), include_tool_failures)
+}
+
+/// The first API error or interruption; with `include_tool_failures`, also the
+/// first failed tool call that was the last tool call of its turn
+fn first_error(
+    outcomes: impl Iterator<Item = EntryOutcome>,
+    include_tool_failures: bool,
+) -> Option<SessionError> {
+    let mut tool_failure = None;
+    let mut ended_on_failure = None;
+    for outcome in outcomes {
+        match outcome {
+            EntryOutcome::Error(error) => return Some(error),
+            EntryOutcome::ToolFailed(error) => tool_failure = Some(error),
+            EntryOutcome::ToolSucceeded => tool_failure = None,
+            EntryOutcome::TurnStarted => {
+                if ended_on_failure.is_none() {
+                    ended_on_failure = tool_failure.take();
+                }
+                tool_failure = None;
+            }
+            EntryOutcome::Other => {}
+        }
+    }
+    if !include_tool_failures {
+        return None;
+    }
+    ended_on_failure.or(tool_failure)
+}
+
+fn modified_secs(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn session_error(kind: &str, description: &str) -> SessionError {
+    let description = description.trim();
+    SessionError {
+        kind: kind.to_string(),
+        description: if description.chars().count() > MAX_DESCRIPTION_CHARS {
+            let truncated: String = description.chars().take(MAX_DESCRIPTION_CHARS).collect();
+            format!("{}...", truncated)
+        } else {
+            description.to_string()
+        },
+    }
+}
+
+/// Classifies one line of a Claude session JSONL
+fn claude_entry_outcome(entry: &Value) -> EntryOutcome {
+    let content = &entry["message"]["content"];
+    if entry["isApiErrorMessage"].as_bool() == Some(true) {
+        let text = content
+            .as_array()
+            .and_then(|blocks| blocks.iter().find_map(|b| b["text"].as_str()))
+            .or_else(|| content.as_str())
+            .unwrap_or("API error");
+        return EntryOutcome::Error(session_error("api-error", text));
+    }
+    if entry["type"].as_str() != Some("user") {
+        return EntryOutcome::Other;
+    }
+
+    if let Some(text) = content.as_str() {
+        if text.starts_with("[Request interrupted") {
+            return EntryOutcome::Error(session_error("interrupted", text));
+        }
+    }
+    let mut outcome = EntryOutcome::Other;
+    for block in content.as_array().into_iter().flatten() {
+        match block["type"].as_str() {
+            Some("tool_result") if block["is_error"].as_bool() == Some(true) => {
+                let text = match &block["content"] {
+                    Value::String(text) => text.as_str(),
+                    Value::Array(parts) => parts
+                        .iter()
+                        .find_map(|p| p["text"].as_str())
+                        .unwrap_or("Tool call failed"),
+                    _ => "Tool call failed",
+                };
+                outcome = EntryOutcome::ToolFailed(session_error("tool-failure", text));
+            }
+            Some("tool_result") if outcome == EntryOutcome::Other => {
+                outcome = EntryOutcome::ToolSucceeded;
+            }
+            Some("text") => {
+                let text = block["text"].as_str().unwrap_or("");
+                if text.starts_with("[Request interrupted") {
+                    return EntryOutcome::Error(session_error("interrupted", text));
+                }
+            }
+            _ => {}
+        }
+    }
+    if outcome == EntryOutcome::Other && is_claude_prompt(entry) {
+        return EntryOutcome::TurnStarted;
+    }
+    outcome
+}
+
+/// Classifies one line of a Codex rollout JSONL
+fn codex_entry_outcome(entry: &Value) -> EntryOutcome {
+    let payload = &entry["payload"];
+    let (kind, payload_type) = match (entry["type"].as_str(), payload["type"].as_str()) {
+        (Some(kind), Some(payload_type)) => (kind, payload_type),
+        _ => return EntryOutcome::Other,
+    };
+    match (kind, payload_type) {
+        ("event_msg", "error") | ("event_msg", "stream_error") => {
+            EntryOutcome::Error(session_error(
+                "api-error",
+                payload["message"].as_str().unwrap_or("API error"),
+            ))
+        }
+        ("event_msg", "turn_aborted") => EntryOutcome::Error(session_error(
+            "interrupted",
+            &format!(
+                "Turn aborted ({})",
+                payload["reason"].as_str().unwrap_or("unknown reason")
+            ),
+        )),
+        ("response_item", "message")
+            if payload["role"].as_str() == Some("user") && is_user_prompt(payload) =>
+        {
+            EntryOutcome::TurnStarted
+        }
+        ("response_item", "function_call_output") => {
+            // Shell results are JSON: {"output": "...", "metadata": {"exit_code": N}}
+            let output: Value = match payload["output"]
+                .as_str()
+                .and_then(|o| serde_json::from_str(o).ok())
+            {
+                Some(output) => output,
+                None => return EntryOutcome::ToolSucceeded,
+            };
+            let exit_code = output["metadata"]["exit_code"].as_i64().unwrap_or(0);
+            if exit_code == 0 {
+                return EntryOutcome::ToolSucceeded;
+            }
+            let last_line = output["output"]
+                .as_str()
+                .and_then(|text| text.lines().rev().find(|l| !l.trim().is_empty()))
+                .unwrap_or("");
+            EntryOutcome::ToolFailed(session_error(
+                "tool-failure",
+                &format!("Command exited with code {}: {}", exit_code, last_line),
+            ))
+        }
+        _ => EntryOutcome::Other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn classifies_claude_and_codex_errors() {
+        let api = json!({"type": "assistant", "isApiErrorMessage": true,
+            "message": {"content": [{"type": "text", "text": "API Error: 529 Overloaded"}]}});
+        assert!(
+            matches!(claude_entry_outcome(&api), EntryOutcome::Error(e) if e.kind == "api-error")
+        );
+
+        let tool = json!({"type": "user", "message": {"content": [
+            {"type": "tool_result", "is_error": true, "content": "File not found"}]}});
+        assert_eq!(
+            claude_entry_outcome(&tool),
+            EntryOutcome::ToolFailed(session_error("tool-failure", "File not found"))
+        );
+
+        let interrupted = json!({"type": "user", "message": {"content": [
+            {"type": "text", "text": "[Request interrupted by user]"}]}});
+        assert!(
+            matches!(claude_entry_outcome(&interrupted), EntryOutcome::Error(e) if e.kind == "interrupted")
+        );
+
+        let prompt = json!({"type": "user", "message": {"content": "fix the build"}});
+        assert_eq!(claude_entry_outcome(&prompt), EntryOutcome::TurnStarted);
+
+        let failed = json!({"type": "response_item", "payload": {"type": "function_call_output",
+            "output": "{\"output\":\"error: build failed\\n\",\"metadata\":{\"exit_code\":101}}"}});
+        assert_eq!(
+            codex_entry_outcome(&failed),
+            EntryOutcome::ToolFailed(session_error(
+                "tool-failure",
+                "Command exited with code 101: error: build failed"
+            ))
+        );
+
+        let aborted = json!({"type": "event_msg", "payload": {"type": "turn_aborted", "reason": "interrupted"}});
+        assert!(
+            matches!(codex_entry_outcome(&aborted), EntryOutcome::Error(e) if e.kind == "interrupted")
+        );
+    }
+
+    #[test]
+    fn tool_failures_are_opt_in_and_only_count_when_the_turn_ended_on_them() {
+        let failure = || EntryOutcome::ToolFailed(session_error("tool-failure", "rg: no matches"));
+        let recovered = vec![
+            EntryOutcome::TurnStarted,
+            failure(),
+            EntryOutcome::ToolSucceeded,
+            EntryOutcome::TurnStarted,
+            EntryOutcome::Other,
+        ];
+        assert_eq!(first_error(recovered.into_iter(), true), None);
+
+        let ended_on_failure = vec![
+            EntryOutcome::TurnStarted,
+            failure(),
+            EntryOutcome::Other,
+            EntryOutcome::TurnStarted,
+        ];
+        assert_eq!(
+            first_error(ended_on_failure.clone().into_iter(), true),
+            Some(session_error("tool-failure", "rg: no matches"))
+        );
+        assert_eq!(first_error(ended_on_failure.into_iter(), false), None);
+
+        // API errors win over an earlier tool failure
+        let api_error = EntryOutcome::Error(session_error("api-error", "529 Overloaded"));
+        let outcomes = vec![failure(), EntryOutcome::TurnStarted, api_error.clone()];
+        assert_eq!(
+            first_error(outcomes.into_iter(), true),
+            Some(session_error("api-error", "529 Overloaded"))
+        );
+    }
+}
@@ -0,0 +1,131 @@
+//! Per-tool execution timeouts
+//!
+//! Stores a default timeout for Claude, Codex and Gemini runs in the app settings
+//! table and enforces it with a watchdog started next to each spawned process.
+//! When a run exceeds its timeout the process tree is killed and a
+//! `<tool>-timeout` event is emitted; the regular completion events follow.
+//! The `enhancer` value bounds the acemcp context search of prompt enhancement,
+//! which then falls back to the unenhanced prompt.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use super::claude::kill_process_tree;
+use super::settings_store::{self, SettingKey};
+
+/// Timeouts in seconds per tool; 0 means no timeout
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExecutionTimeouts {
+    pub claude: u64,
+    pub codex: u64,
+    pub gemini: u64,
+    pub enhancer: u64,
+}
+
+impl ExecutionTimeouts {
+    fn for_tool(&self, tool: &str) -> u64 {
+        match tool {
+            "claude" => self.claude,
+            "codex" => self.codex,
+            "gemini" => self.gemini,
+            "enhancer" => self.enhancer,
+            _ => 0,
+        }
+    }
+}
+
+/// Returns the configured execution timeouts (all 0 when never set)
+#[tauri::command]
+pub async fn get_execution_timeouts(app: AppHandle) -> Result<ExecutionTimeouts, String> {
+    match settings_store::get_setting(&app, SettingKey::ExecutionTimeouts)? {
+        Some(value) => serde_json::from_str(&value)
+            .map_err(|e| format!("Failed to parse execution timeouts: {}", e)),
+        None => Ok(ExecutionTimeouts::default()),
+    }
+}
+
+/// Saves the execution timeouts; they apply to runs started afterwards
+#[tauri::command]
+pub async fn set_execution_timeouts(app: AppHandle, timeouts: ExecutionTimeouts) -> Result<(), String> {
+    let value = serde_json::to_string(&timeouts)
+        .map_err(|e| format!("Failed to serialize execution timeouts: {}", e))?;
+    settings_store::set_setting(&app, SettingKey::ExecutionTimeouts, &value)?;
+    log::info!(
+        "Execution timeouts set: claude={}s, codex={}s, gemini={}s, enhancer={}s",
+        timeouts.claude,
+        timeouts.codex,
+        timeouts.gemini,
+        timeouts.enhancer
+    );
+    Ok(())
+}
+
+/// Timeout configured for `tool`, `None` when unlimited or not configured
+fn tool_timeout(app: &AppHandle, tool: &str) -> Option<Duration> {
+    let timeouts = match settings_store::get_setting(app, SettingKey::ExecutionTimeouts) {
+        Ok(Some(value)) => serde_json::from_str::<ExecutionTimeouts>(&value).unwrap_or_default(),
+        Ok(None) => return None,
+        Err(e) => {
+            log::warn!("Failed to read execution timeouts: {}", e);
+            return None;
+        }
+    };
+    match timeouts.for_tool(tool) {
+        0 => None,
+        secs => Some(Duration::from_secs(secs)),
+    }
+}
+
+/// Timeout for the acemcp context search of prompt enhancement, `None` when unlimited
+pub fn enhancer_timeout(app: &AppHandle) -> Option<Duration> {
+    tool_timeout(app, "enhancer")
+}
+
+/// Kills process `pid` once the tool's timeout elapses, unless `finished` was set first
+/// `session_id` is evaluated at timeout time, since Claude only reports its ID after start.
+pub fn spawn_timeout_watchdog<F>(
+    app: AppHandle,
+    tool: &'static str,
+    pid: u32,
+    finished: Arc<AtomicBool>,
+    session_id: F,
+) where
+    F: Fn() -> Option<String> + Send + 'static,
+{
+    let timeout = match tool_timeout(&app, tool) {
+        Some(timeout) => timeout,
+        None => return,
+    };
+
+    tokio::spawn(async move {
+        tokio::time::sleep(timeout).await;
+        if finished.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let session_id = session_id();
+        log::warn!(
+            "{} run {:?} (pid {}) exceeded its {}s timeout, killing it",
+            tool,
+            session_id,
+            pid,
+            timeout.as_secs()
+        );
+        if let Err(e) = kill_process_tree(pid) {
+            log::error!("Failed to kill timed out {} process {}: {}", tool, pid, e);
+        }
+
+        let payload = serde_json::json!({
+            "session_id": session_id,
+            "timeout_secs": timeout.as_secs(),
+        });
+        if let Some(id) = &session_id {
+            let _ = app.emit(&format!("{}-timeout:{}", tool, id), &payload);
+        }
+        let _ = app.emit(&format!("{}-timeout", tool), &payload);
+    });
+}
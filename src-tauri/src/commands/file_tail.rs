@@ -0,0 +1,153 @@
+//! Live file tail
+//!
+//! Streams content appended to a file (build logs, Codex `-o` output files)
+//! to the frontend as `file-tail` events. Files are polled rather than watched
+//! so the same code path works for local disks, network shares and WSL UNC paths.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::SeekFrom;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+use tokio::fs::File;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+/// Interval between size checks
+const POLL_INTERVAL_MS: u64 = 500;
+
+/// Bytes read from the end of the file when the caller does not specify an offset
+const DEFAULT_FROM_END_BYTES: u64 = 4096;
+
+/// Active tails keyed by file path
+#[derive(Default)]
+pub struct FileTailState {
+    tails: Arc<Mutex<HashMap<String, JoinHandle<()>>>>,
+}
+
+/// Payload of a `file-tail` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileTailChunk {
+    pub path: String,
+    pub content: String,
+    /// True when the file shrank (truncated or rotated) and reading restarted from the top
+    pub truncated: bool,
+}
+
+/// Starts streaming appended content of `path` as `file-tail` events
+/// The initial chunk contains the last `from_end_bytes` bytes of the file.
+/// Calling it again for the same path restarts the tail.
+#[tauri::command]
+pub async fn tail_file(
+    path: String,
+    from_end_bytes: Option<u64>,
+    app: AppHandle,
+    state: State<'_, FileTailState>,
+) -> Result<(), String> {
+    log::info!("Starting tail for file: {}", path);
+
+    let file_path = PathBuf::from(&path);
+    if !file_path.is_file() {
+        return Err(format!("File does not exist: {}", path));
+    }
+
+    let size = tokio::fs::metadata(&file_path)
+        .await
+        .map_err(|e| format!("Failed to read file metadata: {}", e))?
+        .len();
+    let start = size.saturating_sub(from_end_bytes.unwrap_or(DEFAULT_FROM_END_BYTES));
+
+    let task_path = path.clone();
+    let handle = tokio::spawn(async move {
+        let mut offset = start;
+        loop {
+            match read_appended(&file_path, offset).await {
+                Ok(Some((content, new_offset, truncated))) => {
+                    offset = new_offset;
+                    let chunk = FileTailChunk {
+                        path: task_path.clone(),
+                        content,
+                        truncated,
+                    };
+                    let _ = app.emit("file-tail", &chunk);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    log::warn!("Stopping tail for {}: {}", task_path, e);
+                    let _ = app.emit("file-tail-stopped", &task_path);
+                    break;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(POLL_INTERVAL_MS)).await;
+        }
+    });
+
+    let mut tails = state.tails.lock().await;
+    if let Some(previous) = tails.insert(path, handle) {
+        previous.abort();
+    }
+    Ok(())
+}
+
+/// Stops a tail started with `tail_file`
+/// Returns false if no tail was running for the path
+#[tauri::command]
+pub async fn stop_tail_file(path: String, state: State<'_, FileTailState>) -> Result<bool, String> {
+    let mut tails = state.tails.lock().await;
+    match tails.remove(&path) {
+        Some(handle) => {
+            handle.abort();
+            log::info!("Stopped tail for file: {}", path);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Reads everything after `offset`
+/// Returns `None` when nothing was appended, otherwise the content, the new offset
+/// and whether the file had shrunk below `offset`.
+async fn read_appended(
+    path: &PathBuf,
+    offset: u64,
+) -> Result<Option<(String, u64, bool)>, String> {
+    let size = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| format!("Failed to read file metadata: {}", e))?
+        .len();
+
+    let (start, truncated) = if size < offset { (0, true) } else { (offset, false) };
+    if size == start {
+        return Ok(None);
+    }
+
+    let mut file = File::open(path)
+        .await
+        .map_err(|e| format!("Failed to open file: {}", e))?;
+    file.seek(SeekFrom::Start(start))
+        .await
+        .map_err(|e| format!("Failed to seek file: {}", e))?;
+
+    let mut buffer = Vec::with_capacity((size - start) as usize);
+    file.take(size - start)
+        .read_to_end(&mut buffer)
+        .await
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    // Hold back a trailing partial UTF-8 sequence until the rest of it is written
+    let valid_len = match std::str::from_utf8(&buffer) {
+        Ok(_) => buffer.len(),
+        Err(e) if e.error_len().is_none() => e.valid_up_to(),
+        Err(_) => buffer.len(),
+    };
+    if valid_len == 0 {
+        return Ok(None);
+    }
+
+    let content = String::from_utf8_lossy(&buffer[..valid_len]).to_string();
+    Ok(Some((content, start + valid_len as u64, truncated)))
+}
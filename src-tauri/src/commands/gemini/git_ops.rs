@@ -19,6 +19,7 @@ use super::super::simple_git;
 use super::super::prompt_tracker::{RewindMode, RewindCapabilities, PromptRecord as ClaudePromptRecord, load_execution_config};
 // Import Gemini config helpers
 use super::config::get_gemini_dir;
+use super::super::session_meta::{ensure_session_unmodified, resolve_session_file};
 
 // Align Gemini prompt record type with Claude prompt tracker representation
 pub type PromptRecord = ClaudePromptRecord;
@@ -552,16 +553,24 @@ pub fn truncate_gemini_session_to_prompt(
 // ============================================================================
 
 /// Revert Gemini session to a specific prompt
+/// With `expected_mtime`, the rewind is refused if the session file changed since it was loaded.
 #[tauri::command]
 pub async fn revert_gemini_to_prompt(
     session_id: String,
     project_path: String,
     prompt_index: usize,
     mode: RewindMode,
+    expected_mtime: Option<u64>,
 ) -> Result<String, String> {
     log::info!("[Gemini Rewind] Reverting session {} to prompt #{} with mode: {:?}",
         session_id, prompt_index, mode);
 
+    // Refuse before touching code or history if another window rewrote the session
+    if expected_mtime.is_some() {
+        let session_file = resolve_session_file(&session_id, "gemini", None)?;
+        ensure_session_unmodified(&session_file, expected_mtime)?;
+    }
+
     // Load execution config to check if Git operations are disabled
     let execution_config = load_execution_config()
         .map_err(|e| format!("Failed to load execution config: {}", e))?;
@@ -285,6 +285,93 @@ pub fn convert_raw_to_unified_message(raw: &Value) -> Value {
     })
 }
 
+/// Convert a message of a saved Gemini chat (`chats/*.json`) to unified messages
+/// Tool calls become an assistant `tool_use` message followed by a user `tool_result`.
+pub fn convert_history_message_to_unified(message: &Value) -> Vec<Value> {
+    let timestamp = message.get("timestamp").cloned().unwrap_or(Value::Null);
+    let text = message.get("content").and_then(|c| c.as_str()).unwrap_or("");
+
+    match message.get("type").and_then(|t| t.as_str()) {
+        Some("user") => vec![json!({
+            "type": "user",
+            "message": {
+                "content": [{ "type": "text", "text": text }],
+                "role": "user"
+            },
+            "timestamp": timestamp,
+            "geminiMetadata": { "provider": "gemini", "eventType": "history" }
+        })],
+        Some("gemini") | Some("model") | Some("assistant") => {
+            let mut content = Vec::new();
+            for thought in message.get("thoughts").and_then(|t| t.as_array()).into_iter().flatten() {
+                let subject = thought.get("subject").and_then(|s| s.as_str()).unwrap_or("");
+                let description = thought.get("description").and_then(|d| d.as_str()).unwrap_or("");
+                content.push(json!({
+                    "type": "thinking",
+                    "thinking": format!("{}\n{}", subject, description).trim().to_string()
+                }));
+            }
+            if !text.is_empty() {
+                content.push(json!({ "type": "text", "text": text }));
+            }
+
+            let calls: Vec<&Value> = message
+                .get("toolCalls")
+                .and_then(|c| c.as_array())
+                .map(|calls| calls.iter().collect())
+                .unwrap_or_default();
+            for call in &calls {
+                content.push(json!({
+                    "type": "tool_use",
+                    "id": call.get("id").cloned().unwrap_or(Value::Null),
+                    "name": call.get("name").cloned().unwrap_or(Value::Null),
+                    "input": call.get("args").cloned().unwrap_or(json!({}))
+                }));
+            }
+
+            let mut messages = vec![json!({
+                "type": "assistant",
+                "message": { "content": content, "role": "assistant" },
+                "timestamp": timestamp,
+                "geminiMetadata": {
+                    "provider": "gemini",
+                    "eventType": "history",
+                    "model": message.get("model")
+                }
+            })];
+
+            let results: Vec<Value> = calls
+                .iter()
+                .filter(|call| call.get("resultDisplay").is_some() || call.get("status").is_some())
+                .map(|call| {
+                    let output = match call.get("resultDisplay") {
+                        Some(Value::String(s)) => s.clone(),
+                        Some(other) => serde_json::to_string(other).unwrap_or_default(),
+                        None => String::new(),
+                    };
+                    let status = call.get("status").and_then(|s| s.as_str()).unwrap_or("success");
+                    json!({
+                        "type": "tool_result",
+                        "tool_use_id": call.get("id").cloned().unwrap_or(Value::Null),
+                        "content": output,
+                        "is_error": status == "error"
+                    })
+                })
+                .collect();
+            if !results.is_empty() {
+                messages.push(json!({
+                    "type": "user",
+                    "message": { "content": results, "role": "user" },
+                    "timestamp": timestamp,
+                    "geminiMetadata": { "provider": "gemini", "eventType": "history" }
+                }));
+            }
+            messages
+        }
+        _ => Vec::new(),
+    }
+}
+
 // ============================================================================
 // Usage Extraction
 // ============================================================================
@@ -357,4 +444,27 @@ mod tests {
         assert_eq!(unified["type"], "assistant");
         assert_eq!(unified["geminiMetadata"]["provider"], "gemini");
     }
+
+    #[test]
+    fn test_convert_history_message_with_tool_call() {
+        let message = json!({
+            "type": "gemini",
+            "content": "Listing files",
+            "toolCalls": [{
+                "id": "call-1",
+                "name": "list_directory",
+                "args": { "path": "." },
+                "status": "success",
+                "resultDisplay": "src\nCargo.toml"
+            }]
+        });
+
+        let unified = convert_history_message_to_unified(&message);
+        assert_eq!(unified.len(), 2);
+        assert_eq!(unified[0]["type"], "assistant");
+        assert_eq!(unified[0]["message"]["content"][1]["type"], "tool_use");
+        assert_eq!(unified[1]["type"], "user");
+        assert_eq!(unified[1]["message"]["content"][0]["tool_use_id"], "call-1");
+        assert_eq!(unified[1]["message"]["content"][0]["is_error"], false);
+    }
 }
@@ -412,6 +412,7 @@ pub async fn clear_gemini_provider_config() -> Result<String, String> {
 #[tauri::command]
 pub async fn test_gemini_provider_connection(base_url: String, api_key: Option<String>) -> Result<String, String> {
     log::info!("[Gemini Provider] Testing connection to: {}", base_url);
+    crate::commands::network_mode::ensure_online("Provider connection test")?;
 
     // Simple connectivity test
     let client = reqwest::Client::builder()
@@ -4,6 +4,8 @@
 //! Uses --output-format stream-json for real-time JSONL output.
 
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::io::{AsyncBufReadExt, BufReader};
@@ -13,6 +15,8 @@ use super::config::{build_gemini_env, load_gemini_config};
 use super::parser::{convert_to_unified_message, parse_gemini_line, parse_gemini_line_flexible, convert_raw_to_unified_message};
 use super::types::{GeminiExecutionOptions, GeminiInstallStatus, GeminiProcessState};
 use crate::commands::claude::apply_no_window_async;
+use crate::commands::execution_timeout::spawn_timeout_watchdog;
+use crate::commands::run_error::{record_run_outcome, StderrTail};
 
 // ============================================================================
 // Binary Detection
@@ -156,6 +160,9 @@ pub async fn check_gemini_installed() -> Result<GeminiInstallStatus, String> {
 // Tauri Commands - Session Execution
 // ============================================================================
 
+/// Approval modes accepted by the Gemini CLI (`--approval-mode`, or `--yolo`)
+const GEMINI_APPROVAL_MODES: &[&str] = &["default", "auto_edit", "yolo", "plan"];
+
 /// Execute Gemini CLI with streaming output
 #[tauri::command]
 pub async fn execute_gemini(
@@ -190,7 +197,18 @@ pub async fn execute_gemini(
     args.push("--model".to_string());
     args.push(model.clone());
 
-    // Add approval mode
+    // Add approval mode (per-run override, falling back to the configured default)
+    // Only the per-run override is validated; the stored default predates the
+    // check and is passed through as before
+    if let Some(mode) = &options.approval_mode {
+        if !GEMINI_APPROVAL_MODES.contains(&mode.as_str()) {
+            return Err(format!(
+                "Invalid approval mode '{}'. Expected one of: {}",
+                mode,
+                GEMINI_APPROVAL_MODES.join(", ")
+            ));
+        }
+    }
     let approval_mode = options.approval_mode.as_ref().unwrap_or(&config.approval_mode);
     if approval_mode == "yolo" {
         args.push("--yolo".to_string());
@@ -227,6 +245,7 @@ pub async fn execute_gemini(
     for (key, value) in env_vars {
         cmd.env(&key, &value);
     }
+    cmd.envs(crate::commands::network_mode::offline_env_vars().iter().copied());
 
     // Execute process with prompt via stdin
     execute_gemini_process(cmd, options.project_path, model.clone(), Some(options.prompt), app_handle).await
@@ -325,6 +344,7 @@ async fn execute_gemini_process(
     let session_id = format!("gemini-{}", uuid::Uuid::new_v4());
 
     // Store process in state
+    let child_pid = child.id();
     let state: tauri::State<'_, GeminiProcessState> = app_handle.state();
     {
         let mut processes = state.processes.lock().await;
@@ -334,6 +354,15 @@ async fn execute_gemini_process(
         *last_session = Some(session_id.clone());
     }
 
+    // Kill the run once the configured Gemini timeout elapses
+    let finished = Arc::new(AtomicBool::new(false));
+    if let Some(pid) = child_pid {
+        let session_id_timeout = session_id.clone();
+        spawn_timeout_watchdog(app_handle.clone(), "gemini", pid, finished.clone(), move || {
+            Some(session_id_timeout.clone())
+        });
+    }
+
     // Emit session init event
     let init_payload = serde_json::json!({
         "type": "system",
@@ -447,12 +476,17 @@ async fn execute_gemini_process(
     });
 
     // Spawn task to read stderr
+    let stderr_tail = Arc::new(std::sync::Mutex::new(StderrTail::default()));
+    let stderr_tail_reader = stderr_tail.clone();
     tokio::spawn(async move {
         let mut reader = BufReader::new(stderr).lines();
 
         while let Ok(Some(line)) = reader.next_line().await {
             if !line.trim().is_empty() {
                 log::warn!("Gemini stderr: {}", line);
+                if let Ok(mut tail) = stderr_tail_reader.lock() {
+                    tail.push(&line);
+                }
 
                 // Emit stderr as error event
                 let error_message = serde_json::json!({
@@ -481,6 +515,7 @@ async fn execute_gemini_process(
     // Spawn task to wait for process completion
     let state_complete = app_handle.state::<GeminiProcessState>();
     let processes_complete = state_complete.processes.clone();
+    let last_errors_complete = state_complete.last_errors.clone();
 
     tokio::spawn(async move {
         // Wait a bit for stdout/stderr to be processed
@@ -497,6 +532,9 @@ async fn execute_gemini_process(
                         status,
                         success
                     );
+                    let tail = stderr_tail.lock().map(|t| t.text()).unwrap_or_default();
+                    record_run_outcome(&last_errors_complete, &session_id_complete, success, status.code(), tail)
+                        .await;
 
                     // Emit completion event
                     let complete_payload = serde_json::json!({
@@ -525,6 +563,9 @@ async fn execute_gemini_process(
                 }
                 Err(e) => {
                     log::error!("Failed to wait for Gemini process: {}", e);
+                    let tail = stderr_tail.lock().map(|t| t.text()).unwrap_or_default();
+                    record_run_outcome(&last_errors_complete, &session_id_complete, false, None, tail)
+                        .await;
 
                     let _ = app_handle_complete.emit(
                         &format!("gemini-complete:{}", session_id_complete),
@@ -534,6 +575,7 @@ async fn execute_gemini_process(
                 }
             }
         }
+        finished.store(true, Ordering::SeqCst);
     });
 
     Ok(())
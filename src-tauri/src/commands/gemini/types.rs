@@ -145,7 +145,8 @@ pub struct GeminiExecutionOptions {
     /// Model to use (e.g., "gemini-2.5-pro", "gemini-2.5-flash")
     pub model: Option<String>,
 
-    /// Approval mode: "auto_edit" or "yolo"
+    /// Approval mode for this run: "default", "auto_edit" or "yolo"
+    /// Overrides `GeminiConfig.approval_mode` when set
     pub approval_mode: Option<String>,
 
     /// Additional directories to include in context
@@ -212,10 +213,14 @@ use std::sync::Arc;
 use tokio::process::Child;
 use tokio::sync::Mutex;
 
+use crate::commands::run_error::LastRunErrors;
+
 /// Global state to track Gemini processes
 pub struct GeminiProcessState {
     pub processes: Arc<Mutex<HashMap<String, Child>>>,
     pub last_session_id: Arc<Mutex<Option<String>>>,
+    /// Exit code and stderr tail of each session's last failed run
+    pub last_errors: LastRunErrors,
 }
 
 impl Default for GeminiProcessState {
@@ -223,6 +228,7 @@ impl Default for GeminiProcessState {
         Self {
             processes: Arc::new(Mutex::new(HashMap::new())),
             last_session_id: Arc::new(Mutex::new(None)),
+            last_errors: Arc::new(Mutex::new(HashMap::new())),
         }
     }
 }
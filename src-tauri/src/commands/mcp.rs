@@ -1,10 +1,10 @@
 use anyhow::{Context, Result};
 use dirs;
-use log::{error, info};
+use log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 use tauri::AppHandle;
 
@@ -761,3 +761,277 @@ pub async fn mcp_save_project_config(
 
     Ok("Project MCP configuration saved".to_string())
 }
+
+/// An MCP server from Claude's user config with the result of a liveness check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClaudeMcpServerInfo {
+    pub name: String,
+    /// Command of stdio servers
+    pub command: Option<String>,
+    pub args: Vec<String>,
+    /// Endpoint of sse/http servers
+    pub url: Option<String>,
+    /// "ok", "failed", or "unchecked" for servers that are not stdio
+    pub status: String,
+    pub error: Option<String>,
+}
+
+/// Path of Claude's user config (~/.claude.json), which holds user-scoped MCP servers
+fn claude_json_path() -> Result<PathBuf, String> {
+    dirs::home_dir()
+        .map(|home| home.join(".claude.json"))
+        .ok_or_else(|| "Failed to get home directory".to_string())
+}
+
+/// Reads the `mcpServers` object of ~/.claude.json; empty when the file or section is missing
+fn read_claude_mcp_servers() -> Result<serde_json::Map<String, serde_json::Value>, String> {
+    let path = claude_json_path()?;
+    if !path.exists() {
+        return Ok(serde_json::Map::new());
+    }
+
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    let config: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+    Ok(config
+        .get("mcpServers")
+        .and_then(|servers| servers.as_object())
+        .cloned()
+        .unwrap_or_default())
+}
+
+/// Lists the MCP servers in Claude's user config, checking each stdio server
+/// by spawning it and completing the MCP initialize handshake
+#[tauri::command]
+pub async fn list_claude_mcp_servers() -> Result<Vec<ClaudeMcpServerInfo>, String> {
+    info!("Listing MCP servers from Claude config");
+    let servers = read_claude_mcp_servers()?;
+
+    let checks = servers.into_iter().map(|(name, server)| async move {
+        let command = server
+            .get("command")
+            .and_then(|v| v.as_str())
+            .map(String::from);
+        let args: Vec<String> = server
+            .get("args")
+            .and_then(|v| v.as_array())
+            .map(|args| {
+                args.iter()
+                    .filter_map(|a| a.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let env: HashMap<String, String> = server
+            .get("env")
+            .and_then(|v| v.as_object())
+            .map(|env| {
+                env.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|v| (k.clone(), v.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let url = server.get("url").and_then(|v| v.as_str()).map(String::from);
+
+        let (status, error) = match &command {
+            Some(program) => {
+                match crate::commands::acemcp::probe_mcp_server(program, &args, &env).await {
+                    Ok(()) => ("ok", None),
+                    Err(e) => ("failed", Some(e)),
+                }
+            }
+            None => ("unchecked", None),
+        };
+
+        ClaudeMcpServerInfo {
+            name,
+            command,
+            args,
+            url,
+            status: status.to_string(),
+            error,
+        }
+    });
+
+    let mut results = futures::future::join_all(checks).await;
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+    info!(
+        "Checked {} MCP server(s), {} reachable",
+        results.len(),
+        results.iter().filter(|s| s.status == "ok").count()
+    );
+    Ok(results)
+}
+
+/// File name prefix of ~/.claude.json backups, followed by a timestamp
+const CLAUDE_JSON_BACKUP_PREFIX: &str = ".claude.json.bak-";
+
+/// Backups kept next to ~/.claude.json; older ones are deleted
+const CLAUDE_JSON_BACKUPS_KEPT: usize = 5;
+
+/// Serializes read-modify-write cycles on ~/.claude.json within the app
+static CLAUDE_JSON_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+/// Updates the `mcpServers` object of ~/.claude.json and writes the file back,
+/// keeping a timestamped copy of the previous file next to it
+fn update_claude_mcp_servers<F>(update: F) -> Result<PathBuf, String>
+where
+    F: FnOnce(&mut serde_json::Map<String, serde_json::Value>) -> Result<(), String>,
+{
+    let _guard = CLAUDE_JSON_LOCK
+        .lock()
+        .map_err(|e| format!("Failed to lock .claude.json: {}", e))?;
+    let path = claude_json_path()?;
+    let mut config: serde_json::Value = if path.exists() {
+        let content = fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?
+    } else {
+        serde_json::json!({})
+    };
+
+    let root = config
+        .as_object_mut()
+        .ok_or_else(|| format!("{} is not a JSON object", path.display()))?;
+    let servers = root
+        .entry("mcpServers")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or_else(|| "mcpServers in .claude.json is not an object".to_string())?;
+    update(servers)?;
+
+    if path.exists() {
+        let backup = path.with_file_name(format!(
+            "{}{}",
+            CLAUDE_JSON_BACKUP_PREFIX,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ));
+        fs::copy(&path, &backup)
+            .map_err(|e| format!("Failed to back up {}: {}", path.display(), e))?;
+        info!("Backed up {} to {:?}", path.display(), backup);
+        prune_claude_json_backups(&path);
+    }
+
+    // Write to a temp file first so Claude never reads a half-written config
+    let content = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize .claude.json: {}", e))?;
+    let temp = path.with_file_name(".claude.json.tmp");
+    fs::write(&temp, content).map_err(|e| format!("Failed to write {}: {}", temp.display(), e))?;
+    fs::rename(&temp, &path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Deletes all but the newest `CLAUDE_JSON_BACKUPS_KEPT` backups of ~/.claude.json
+fn prune_claude_json_backups(path: &Path) {
+    let dir = match path.parent() {
+        Some(dir) => dir,
+        None => return,
+    };
+    let mut backups: Vec<PathBuf> = match fs::read_dir(dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with(CLAUDE_JSON_BACKUP_PREFIX))
+            })
+            .collect(),
+        Err(_) => return,
+    };
+
+    // Timestamps sort chronologically, newest last
+    backups.sort();
+    let excess = backups.len().saturating_sub(CLAUDE_JSON_BACKUPS_KEPT);
+    for old in &backups[..excess] {
+        if let Err(e) = fs::remove_file(old) {
+            warn!("Failed to remove old backup {:?}: {}", old, e);
+        }
+    }
+}
+
+/// Checks that an MCP server command is an existing file or is found on PATH
+fn validate_mcp_command(command: &str) -> Result<(), String> {
+    if command.trim().is_empty() {
+        return Err("MCP server command is empty".to_string());
+    }
+    let is_path = command.contains('/') || command.contains('\\');
+    let found = if is_path {
+        let expanded = match command.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir()
+                .map(|home| home.join(rest))
+                .unwrap_or_else(|| PathBuf::from(command)),
+            None => PathBuf::from(command),
+        };
+        expanded.is_file()
+    } else {
+        crate::commands::claude::find_in_path(command)
+    };
+    if found {
+        Ok(())
+    } else {
+        Err(format!("MCP server command not found: {}", command))
+    }
+}
+
+/// Writes a stdio MCP server entry to Claude's user config
+/// With `replace` false an existing entry of the same name is an error.
+/// Returns the path of the modified config file.
+pub(crate) fn write_claude_mcp_server(
+    name: &str,
+    command: &str,
+    args: &[String],
+    env: &HashMap<String, String>,
+    replace: bool,
+) -> Result<PathBuf, String> {
+    if name.trim().is_empty() {
+        return Err("MCP server name is empty".to_string());
+    }
+    validate_mcp_command(command)?;
+
+    let entry = serde_json::json!({
+        "type": "stdio",
+        "command": command,
+        "args": args,
+        "env": env,
+    });
+    update_claude_mcp_servers(|servers| {
+        if !replace && servers.contains_key(name) {
+            return Err(format!("MCP server {} already exists", name));
+        }
+        servers.insert(name.to_string(), entry);
+        Ok(())
+    })
+}
+
+/// Adds a stdio MCP server to Claude's user config (~/.claude.json)
+/// An existing server of the same name is an error. Returns the path of the
+/// modified config file.
+#[tauri::command]
+pub async fn add_claude_mcp_server(
+    name: String,
+    command: String,
+    args: Vec<String>,
+    env: Option<HashMap<String, String>>,
+) -> Result<String, String> {
+    info!("Adding MCP server {} to Claude config", name);
+    let path = write_claude_mcp_server(&name, &command, &args, &env.unwrap_or_default(), false)?;
+    info!("Added MCP server {} to {}", name, path.display());
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Removes an MCP server from Claude's user config (~/.claude.json)
+#[tauri::command]
+pub async fn remove_claude_mcp_server(name: String) -> Result<String, String> {
+    info!("Removing MCP server {} from Claude config", name);
+    let path = update_claude_mcp_servers(|servers| {
+        servers
+            .remove(&name)
+            .map(|_| ())
+            .ok_or_else(|| format!("MCP server {} not found", name))
+    })?;
+    info!("Removed MCP server {} from {}", name, path.display());
+    Ok(path.to_string_lossy().to_string())
+}
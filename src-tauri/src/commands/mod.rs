@@ -1,22 +1,57 @@
 pub mod acemcp;
+pub mod activity_heatmap;  // 使用活跃度热力图
+pub mod bookmarks;  // 文件书签
+pub mod buffer_stats;  // 运行输出缓冲区上限
+pub mod bug_report;  // 问题报告生成
+pub mod caches;  // 缓存清理
 pub mod claude;
 pub mod clipboard;
 pub mod codex;  // OpenAI Codex integration
+pub mod config_backup;  // 配置备份与恢复
 pub mod gemini;  // Google Gemini CLI integration
+pub mod content_search;  // 文件内容流式搜索
 pub mod context_commands;
 pub mod context_manager;
+pub mod drafts;  // 输入框草稿保存
+pub mod duplicate_prompts;  // 重复提示词检测
 pub mod enhanced_hooks;
+pub mod error_sessions;  // 出错会话筛选
+pub mod execution_timeout;  // 执行超时设置
 pub mod extensions;
 pub mod file_operations;
+pub mod file_tail;  // 文件实时跟踪
 pub mod git_stats;
 pub mod mcp;
+pub mod network_mode;  // 在线/离线网络模式
+pub mod path_length;  // Windows 路径长度检查
 pub mod permission_config;
+pub mod project_fingerprint;  // 项目状态指纹
+pub mod project_stack;  // 项目技术栈检测
+pub mod prompt_library;  // 提示词库提取
 pub mod prompt_tracker;
 pub mod provider;
+pub mod recent_dirs;  // 最近使用的目录
+pub mod rewind_stash;  // 回滚自动 stash 管理
+pub mod run_error;  // 运行失败信息
+pub mod secret_scan;  // 提示词密钥检测
+pub mod session_diff;  // 会话对比
+pub mod session_export;  // 会话导出
+pub mod session_html;  // 会话 HTML 导出
+pub mod session_meta;  // 会话元数据快速读取
+pub mod session_ops;  // 会话维护操作
+pub mod session_replay;  // 会话回放
+pub mod session_stream;  // 会话渐进式加载
+pub mod settings_store;  // 应用设置存储
+pub mod settings_watcher;  // Claude 设置文件监听
 pub mod simple_git;
 pub mod storage;
+pub mod token_estimate;  // 本地 token 估算
+pub mod tool_status;  // 工具状态总览
+pub mod toolchain_selftest;  // 工具链自检
+pub mod transcript;  // 会话记录渲染
 pub mod translator;
 pub mod url_utils;  // API URL 规范化工具
 pub mod usage;
 pub mod window;  // 多窗口管理
+pub mod workspace_snapshot;  // 工作区快照
 pub mod wsl_utils;  // WSL 兼容性工具
@@ -0,0 +1,118 @@
+//! Online/offline network mode
+//!
+//! In offline mode the app refuses its own outbound calls (acemcp context
+//! search, provider connection tests, translation) and adds environment
+//! variables to every CLI process it spawns (Claude, Codex, Gemini, also inside
+//! WSL) that stop telemetry, error reports and update checks. The app's own
+//! environment is never modified. The mode is persisted in app settings and
+//! restored on startup.
+
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use tauri::AppHandle;
+
+use super::settings_store::{self, SettingKey};
+
+/// Whether offline mode is active; read by the guarded commands
+static OFFLINE: AtomicBool = AtomicBool::new(false);
+
+/// Set on every CLI process spawned while offline
+const OFFLINE_ENV_VARS: &[(&str, &str)] = &[
+    ("CLAUDE_CODE_DISABLE_NONESSENTIAL_TRAFFIC", "1"),
+    ("DISABLE_TELEMETRY", "1"),
+    ("DISABLE_ERROR_REPORTING", "1"),
+    ("DISABLE_AUTOUPDATER", "1"),
+];
+
+/// App features that make network requests and are blocked while offline
+const OFFLINE_DISABLED_FEATURES: &[&str] = &[
+    "acemcp prompt enhancement",
+    "acemcp search and indexing",
+    "provider connection tests",
+    "translation",
+];
+
+/// Result of `set_network_mode` / `get_network_mode`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkModeStatus {
+    /// "online" or "offline"
+    pub mode: String,
+    pub disabled_features: Vec<String>,
+    /// Environment variables set for spawned CLIs
+    pub env_vars: Vec<String>,
+}
+
+/// Switches the app between "online" and "offline"
+#[tauri::command]
+pub async fn set_network_mode(app: AppHandle, mode: String) -> Result<NetworkModeStatus, String> {
+    let offline = match mode.as_str() {
+        "online" => false,
+        "offline" => true,
+        other => return Err(format!("Invalid network mode: {}", other)),
+    };
+    settings_store::set_setting(&app, SettingKey::NetworkMode, &mode)?;
+    apply_network_mode(offline);
+    log::info!("Network mode set to {}", mode);
+    Ok(network_mode_status())
+}
+
+/// Returns the active network mode
+#[tauri::command]
+pub async fn get_network_mode() -> Result<NetworkModeStatus, String> {
+    Ok(network_mode_status())
+}
+
+/// Restores the persisted mode; called once at startup
+pub fn load_network_mode(app: &AppHandle) {
+    match settings_store::get_setting(app, SettingKey::NetworkMode) {
+        Ok(Some(mode)) if mode == "offline" => {
+            log::info!("Starting in offline network mode");
+            apply_network_mode(true);
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Failed to load network mode: {}", e),
+    }
+}
+
+pub fn is_offline() -> bool {
+    OFFLINE.load(Ordering::SeqCst)
+}
+
+/// Fails with a uniform message when `feature` is used in offline mode
+pub fn ensure_online(feature: &str) -> Result<(), String> {
+    if is_offline() {
+        return Err(format!("{} is disabled in offline mode", feature));
+    }
+    Ok(())
+}
+
+/// Environment variables to set on a CLI process spawned now; empty while online
+pub fn offline_env_vars() -> &'static [(&'static str, &'static str)] {
+    if is_offline() {
+        OFFLINE_ENV_VARS
+    } else {
+        &[]
+    }
+}
+
+fn apply_network_mode(offline: bool) {
+    OFFLINE.store(offline, Ordering::SeqCst);
+}
+
+fn network_mode_status() -> NetworkModeStatus {
+    let offline = is_offline();
+    NetworkModeStatus {
+        mode: if offline { "offline" } else { "online" }.to_string(),
+        disabled_features: if offline {
+            OFFLINE_DISABLED_FEATURES.iter().map(|s| s.to_string()).collect()
+        } else {
+            Vec::new()
+        },
+        env_vars: if offline {
+            OFFLINE_ENV_VARS.iter().map(|(key, value)| format!("{}={}", key, value)).collect()
+        } else {
+            Vec::new()
+        },
+    }
+}
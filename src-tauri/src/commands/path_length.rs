@@ -0,0 +1,144 @@
+//! Windows path length check
+//!
+//! Without long-path support, Windows APIs fail on paths over MAX_PATH (260)
+//! characters with errors that do not mention the length. Two paths matter: files
+//! nested inside the project, and the session files Claude keeps under
+//! ~/.claude/projects/<encoded project path>/, whose name repeats the project path.
+
+use serde::{Deserialize, Serialize};
+
+use super::claude::{encode_project_path, get_claude_dir};
+
+/// Classic Windows path limit, including the terminating NUL
+const WINDOWS_MAX_PATH: usize = 260;
+
+/// Room left for files nested inside the project (e.g. `src/components/.../index.tsx`)
+const NESTED_FILE_HEADROOM: usize = 100;
+
+/// Length of `<session uuid>.jsonl` plus its separator
+const SESSION_FILE_NAME_LEN: usize = 1 + 36 + ".jsonl".len();
+
+/// Result of `check_path_length`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PathLengthCheck {
+    pub path_length: usize,
+    /// Length of the project's Claude session file paths
+    pub session_path_length: usize,
+    /// None on platforms without a practical limit
+    pub max_path: Option<usize>,
+    /// LongPathsEnabled registry value; None when not on Windows or it cannot be read
+    pub long_paths_enabled: Option<bool>,
+    pub within_limit: bool,
+    pub guidance: Option<String>,
+}
+
+/// Checks whether a project path leaves enough room under MAX_PATH on Windows
+/// Always within limits on other platforms.
+#[tauri::command]
+pub async fn check_path_length(path: String) -> Result<PathLengthCheck, String> {
+    let path_length = path.chars().count();
+    let session_dir = get_claude_dir()
+        .map_err(|e| e.to_string())?
+        .join("projects")
+        .join(encode_project_path(&path));
+    let session_path_length = session_dir.to_string_lossy().chars().count() + SESSION_FILE_NAME_LEN;
+
+    if !cfg!(target_os = "windows") {
+        return Ok(PathLengthCheck {
+            path_length,
+            session_path_length,
+            max_path: None,
+            long_paths_enabled: None,
+            within_limit: true,
+            guidance: None,
+        });
+    }
+
+    let long_paths_enabled = read_long_paths_enabled();
+    let guidance = path_length_guidance(path_length, session_path_length, long_paths_enabled);
+    Ok(PathLengthCheck {
+        path_length,
+        session_path_length,
+        max_path: Some(WINDOWS_MAX_PATH),
+        long_paths_enabled,
+        within_limit: guidance.is_none(),
+        guidance,
+    })
+}
+
+/// Guidance when a path is at risk, or None when it is safe
+fn path_length_guidance(
+    path_length: usize,
+    session_path_length: usize,
+    long_paths_enabled: Option<bool>,
+) -> Option<String> {
+    if long_paths_enabled == Some(true) {
+        return None;
+    }
+
+    let limit = WINDOWS_MAX_PATH - 1;
+    if session_path_length > limit {
+        Some(format!(
+            "Claude session files for this project would be {} characters long, over the {} character limit. \
+             Enable long paths (set HKLM\\SYSTEM\\CurrentControlSet\\Control\\FileSystem\\LongPathsEnabled to 1 and restart), \
+             or move the project to a shorter path such as C:\\src\\name.",
+            session_path_length, limit
+        ))
+    } else if path_length + NESTED_FILE_HEADROOM > limit {
+        Some(format!(
+            "The project path is {} characters long, leaving less than {} characters for nested files before the {} character limit. \
+             Enable long paths (LongPathsEnabled = 1) or move the project to a shorter path.",
+            path_length, NESTED_FILE_HEADROOM, limit
+        ))
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn read_long_paths_enabled() -> Option<bool> {
+    let mut cmd = std::process::Command::new("reg");
+    cmd.args([
+        "query",
+        r"HKLM\SYSTEM\CurrentControlSet\Control\FileSystem",
+        "/v",
+        "LongPathsEnabled",
+    ]);
+    {
+        use std::os::windows::process::CommandExt;
+        cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+    }
+
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    // e.g. "    LongPathsEnabled    REG_DWORD    0x1"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let value = stdout
+        .lines()
+        .find(|line| line.contains("LongPathsEnabled"))?
+        .split_whitespace()
+        .last()?
+        .to_string();
+    Some(value == "0x1")
+}
+
+#[cfg(not(target_os = "windows"))]
+fn read_long_paths_enabled() -> Option<bool> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_long_session_and_project_paths() {
+        assert!(path_length_guidance(40, 120, Some(false)).is_none());
+        assert!(path_length_guidance(40, 300, Some(false)).is_some());
+        assert!(path_length_guidance(200, 250, None).is_some());
+        assert!(path_length_guidance(200, 300, Some(true)).is_none());
+    }
+}
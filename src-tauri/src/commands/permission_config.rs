@@ -59,6 +59,30 @@ pub const ALL_TOOLS: &[&str] = &[
     "TodoWrite",
 ];
 
+/// Claude CLI 内置工具名（用于校验权限配置中的工具名）
+/// 各工具的引入/废弃版本没有可靠来源，因此只校验名称，不按版本判断
+pub const KNOWN_TOOLS: &[&str] = &[
+    "Bash",
+    "Read",
+    "Write",
+    "Edit",
+    "MultiEdit",
+    "Glob",
+    "Grep",
+    "LS",
+    "Task",
+    "WebFetch",
+    "WebSearch",
+    "NotebookRead",
+    "NotebookEdit",
+    "TodoWrite",
+    "ExitPlanMode",
+    "BashOutput",
+    "KillShell",
+    "SlashCommand",
+    "Skill",
+];
+
 /// Claude执行配置结构
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClaudeExecutionConfig {
@@ -70,6 +94,22 @@ pub struct ClaudeExecutionConfig {
     pub permissions: ClaudePermissionConfig,
     #[serde(default)]
     pub disable_rewind_git_operations: bool,
+    /// Codex 提示词完成后的自动提交策略
+    #[serde(default)]
+    pub auto_commit_mode: AutoCommitMode,
+}
+
+/// 自动提交策略
+/// - per-prompt: 每个提示词完成后创建可见提交（默认）
+/// - on-rewind-request: 只创建隐藏快照引用，不污染提交历史
+/// - off: 不做任何 Git 记录
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "kebab-case")]
+pub enum AutoCommitMode {
+    #[default]
+    PerPrompt,
+    OnRewindRequest,
+    Off,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,6 +129,7 @@ impl Default for ClaudeExecutionConfig {
             verbose: true,
             permissions: ClaudePermissionConfig::default(),
             disable_rewind_git_operations: false,
+            auto_commit_mode: AutoCommitMode::PerPrompt,
         }
     }
 }
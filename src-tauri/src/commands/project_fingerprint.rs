@@ -0,0 +1,74 @@
+//! Project fingerprint
+//!
+//! A cheap, stable digest of a project's file tree (relative paths, sizes and
+//! modification times). File contents are never read, so it is fast enough to
+//! compute whenever a cache keyed on project state needs validating.
+
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+use super::claude::is_skipped_search_dir;
+
+/// Number of hex characters of the digest returned to callers
+const FINGERPRINT_LEN: usize = 16;
+
+/// Returns a short digest that changes whenever a file is added, removed, resized or touched
+#[tauri::command]
+pub async fn get_project_fingerprint(project_path: String) -> Result<String, String> {
+    let root = PathBuf::from(&project_path);
+    if !root.is_dir() {
+        return Err(format!("Project path does not exist: {}", project_path));
+    }
+
+    tokio::task::spawn_blocking(move || compute_fingerprint(&root))
+        .await
+        .map_err(|e| format!("Failed to compute project fingerprint: {}", e))
+}
+
+/// Synchronous fingerprint computation, usable from other modules
+pub fn compute_fingerprint(root: &Path) -> String {
+    let mut entries: Vec<String> = WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|entry| {
+            !(entry.depth() > 0
+                && entry.file_type().is_dir()
+                && entry
+                    .file_name()
+                    .to_str()
+                    .map(is_skipped_search_dir)
+                    .unwrap_or(false))
+        })
+        .flatten()
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            let mtime = metadata
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                .map(|d| d.as_nanos())
+                .unwrap_or(0);
+            let relative = entry.path().strip_prefix(root).ok()?;
+            // Forward slashes keep the digest identical across platforms
+            Some(format!(
+                "{}\t{}\t{}",
+                relative.to_string_lossy().replace('\\', "/"),
+                metadata.len(),
+                mtime
+            ))
+        })
+        .collect();
+
+    // Directory iteration order is not stable; sorting makes the digest deterministic
+    entries.sort();
+
+    let mut hasher = Sha256::new();
+    for entry in &entries {
+        hasher.update(entry.as_bytes());
+        hasher.update(b"\n");
+    }
+    let digest = format!("{:x}", hasher.finalize());
+    digest[..FINGERPRINT_LEN].to_string()
+}
@@ -0,0 +1,301 @@
+//! Project stack detection
+//!
+//! Inspects well-known marker files (Cargo.toml, package.json, pyproject.toml,
+//! go.mod, ...) in the project root and its immediate subdirectories to infer
+//! the languages, frameworks and package managers a project uses.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::claude::is_skipped_search_dir;
+
+/// Detected technology stack of a project
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStack {
+    pub languages: Vec<String>,
+    pub frameworks: Vec<String>,
+    pub package_managers: Vec<String>,
+}
+
+impl ProjectStack {
+    fn add_language(&mut self, name: &str) {
+        push_unique(&mut self.languages, name);
+    }
+
+    fn add_framework(&mut self, name: &str) {
+        push_unique(&mut self.frameworks, name);
+    }
+
+    fn add_package_manager(&mut self, name: &str) {
+        push_unique(&mut self.package_managers, name);
+    }
+}
+
+fn push_unique(list: &mut Vec<String>, name: &str) {
+    if !list.iter().any(|s| s == name) {
+        list.push(name.to_string());
+    }
+}
+
+/// Dependency name → framework label, checked against package.json dependencies
+const JS_FRAMEWORKS: &[(&str, &str)] = &[
+    ("next", "Next.js"),
+    ("nuxt", "Nuxt"),
+    ("react", "React"),
+    ("vue", "Vue"),
+    ("svelte", "Svelte"),
+    ("@angular/core", "Angular"),
+    ("solid-js", "Solid"),
+    ("express", "Express"),
+    ("@nestjs/core", "NestJS"),
+    ("electron", "Electron"),
+    ("@tauri-apps/api", "Tauri"),
+    ("vite", "Vite"),
+    ("tailwindcss", "Tailwind CSS"),
+];
+
+/// Crate name → framework label, checked against Cargo.toml
+const RUST_FRAMEWORKS: &[(&str, &str)] = &[
+    ("tauri", "Tauri"),
+    ("actix-web", "Actix Web"),
+    ("axum", "Axum"),
+    ("rocket", "Rocket"),
+    ("tokio", "Tokio"),
+    ("bevy", "Bevy"),
+];
+
+/// Package name → framework label, checked against Python manifests
+const PYTHON_FRAMEWORKS: &[(&str, &str)] = &[
+    ("django", "Django"),
+    ("flask", "Flask"),
+    ("fastapi", "FastAPI"),
+    ("pytorch", "PyTorch"),
+    ("torch", "PyTorch"),
+    ("tensorflow", "TensorFlow"),
+];
+
+/// Module path fragment → framework label, checked against go.mod
+const GO_FRAMEWORKS: &[(&str, &str)] = &[
+    ("github.com/gin-gonic/gin", "Gin"),
+    ("github.com/labstack/echo", "Echo"),
+    ("github.com/gofiber/fiber", "Fiber"),
+];
+
+/// Detects the primary languages, frameworks and package managers of a project
+#[tauri::command]
+pub async fn detect_project_stack(project_path: String) -> Result<ProjectStack, String> {
+    log::info!("Detecting project stack for: {}", project_path);
+
+    let root = PathBuf::from(&project_path);
+    if !root.is_dir() {
+        return Err(format!("Project path does not exist: {}", project_path));
+    }
+
+    let stack = detect_stack_in(&root);
+    log::info!(
+        "Detected stack: languages={:?}, frameworks={:?}, package_managers={:?}",
+        stack.languages,
+        stack.frameworks,
+        stack.package_managers
+    );
+    Ok(stack)
+}
+
+/// Synchronous detection, usable from other modules (e.g. prompt enhancement)
+pub fn detect_stack_in(root: &Path) -> ProjectStack {
+    let mut stack = ProjectStack::default();
+    inspect_dir(root, &mut stack);
+
+    // Monorepos and Tauri apps keep manifests one level down (e.g. src-tauri/Cargo.toml)
+    if let Ok(entries) = fs::read_dir(root) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if path.is_dir() && !name.starts_with('.') && !is_skipped_search_dir(&name) {
+                inspect_dir(&path, &mut stack);
+            }
+        }
+    }
+
+    stack
+}
+
+fn inspect_dir(dir: &Path, stack: &mut ProjectStack) {
+    let has = |name: &str| dir.join(name).exists();
+    let read = |name: &str| fs::read_to_string(dir.join(name)).unwrap_or_default();
+
+    // Rust
+    if has("Cargo.toml") {
+        stack.add_language("Rust");
+        stack.add_package_manager("cargo");
+        let manifest = read("Cargo.toml");
+        for (krate, label) in RUST_FRAMEWORKS {
+            if toml_declares_dependency(&manifest, krate) {
+                stack.add_framework(label);
+            }
+        }
+    }
+
+    // JavaScript / TypeScript
+    if has("package.json") {
+        if has("tsconfig.json") {
+            stack.add_language("TypeScript");
+        }
+        stack.add_language("JavaScript");
+
+        if let Ok(pkg) = serde_json::from_str::<serde_json::Value>(&read("package.json")) {
+            let declares = |dep: &str| {
+                ["dependencies", "devDependencies", "peerDependencies"]
+                    .iter()
+                    .any(|section| pkg[section].get(dep).is_some())
+            };
+            if declares("typescript") {
+                stack.add_language("TypeScript");
+            }
+            for (dep, label) in JS_FRAMEWORKS {
+                if declares(*dep) {
+                    stack.add_framework(label);
+                }
+            }
+        }
+
+        if has("pnpm-lock.yaml") {
+            stack.add_package_manager("pnpm");
+        } else if has("yarn.lock") {
+            stack.add_package_manager("yarn");
+        } else if has("bun.lockb") || has("bun.lock") {
+            stack.add_package_manager("bun");
+        } else {
+            stack.add_package_manager("npm");
+        }
+    }
+
+    // Python
+    if has("pyproject.toml") || has("requirements.txt") || has("setup.py") || has("Pipfile") {
+        stack.add_language("Python");
+        let manifests = format!(
+            "{}\n{}\n{}",
+            read("pyproject.toml"),
+            read("requirements.txt"),
+            read("Pipfile")
+        )
+        .to_lowercase();
+        for (package, label) in PYTHON_FRAMEWORKS {
+            if manifests.contains(package) {
+                stack.add_framework(label);
+            }
+        }
+
+        if has("poetry.lock") {
+            stack.add_package_manager("poetry");
+        } else if has("uv.lock") {
+            stack.add_package_manager("uv");
+        } else if has("Pipfile") {
+            stack.add_package_manager("pipenv");
+        } else {
+            stack.add_package_manager("pip");
+        }
+    }
+
+    // Go
+    if has("go.mod") {
+        stack.add_language("Go");
+        stack.add_package_manager("go modules");
+        let manifest = read("go.mod");
+        for (module, label) in GO_FRAMEWORKS {
+            if manifest.contains(module) {
+                stack.add_framework(label);
+            }
+        }
+    }
+
+    // JVM
+    if has("pom.xml") {
+        stack.add_language("Java");
+        stack.add_package_manager("maven");
+        if read("pom.xml").contains("spring-boot") {
+            stack.add_framework("Spring Boot");
+        }
+    }
+    if has("build.gradle") || has("build.gradle.kts") {
+        if has("build.gradle.kts") {
+            stack.add_language("Kotlin");
+        } else {
+            stack.add_language("Java");
+        }
+        stack.add_package_manager("gradle");
+        let manifest = format!("{}\n{}", read("build.gradle"), read("build.gradle.kts"));
+        if manifest.contains("org.springframework.boot") {
+            stack.add_framework("Spring Boot");
+        }
+        if manifest.contains("com.android") {
+            stack.add_framework("Android");
+        }
+    }
+
+    // Ruby
+    if has("Gemfile") {
+        stack.add_language("Ruby");
+        stack.add_package_manager("bundler");
+        if read("Gemfile").contains("rails") {
+            stack.add_framework("Rails");
+        }
+    }
+
+    // PHP
+    if has("composer.json") {
+        stack.add_language("PHP");
+        stack.add_package_manager("composer");
+        if read("composer.json").contains("laravel/framework") {
+            stack.add_framework("Laravel");
+        }
+    }
+
+    // Dart / Flutter
+    if has("pubspec.yaml") {
+        stack.add_language("Dart");
+        stack.add_package_manager("pub");
+        if read("pubspec.yaml").contains("flutter:") {
+            stack.add_framework("Flutter");
+        }
+    }
+
+    // Swift
+    if has("Package.swift") {
+        stack.add_language("Swift");
+        stack.add_package_manager("swiftpm");
+    }
+
+    // C / C++
+    if has("CMakeLists.txt") {
+        stack.add_language("C/C++");
+        stack.add_package_manager("cmake");
+    }
+
+    // .NET
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.ends_with(".csproj") || name.ends_with(".sln") {
+                stack.add_language("C#");
+                stack.add_package_manager("dotnet");
+                break;
+            }
+        }
+    }
+}
+
+/// Checks whether a Cargo manifest declares a dependency (`name = ...` or `name.workspace = ...`)
+fn toml_declares_dependency(manifest: &str, krate: &str) -> bool {
+    manifest.lines().any(|line| {
+        let line = line.trim();
+        line.strip_prefix(krate)
+            .map(|rest| {
+                let rest = rest.trim_start();
+                rest.starts_with('=') || rest.starts_with('.')
+            })
+            .unwrap_or(false)
+    })
+}
@@ -0,0 +1,113 @@
+//! Prompt library extraction
+//!
+//! Scans all sessions of a tool for user prompts worth keeping as templates.
+//! Prompts are merged with the same identical/near-identical rule as
+//! `duplicate_prompts`, so a prompt re-sent across many sessions shows up once
+//! with its usage count. The caller saves the chosen ones as prompt templates.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::claude::list_projects;
+use super::codex::{extract_codex_prompts, list_codex_sessions};
+use super::duplicate_prompts::{char_bigrams, dice_similarity, normalize, NEAR_DUPLICATE_THRESHOLD};
+use super::prompt_tracker::extract_prompts_from_jsonl;
+
+/// A prompt proposed for the library
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptTemplateCandidate {
+    /// Text of the first occurrence
+    pub text: String,
+    /// How many times this prompt (or a near-identical one) was sent
+    pub occurrences: usize,
+    /// Sessions it was sent in, without repeats
+    pub session_ids: Vec<String>,
+}
+
+/// Collects distinct user prompts longer than `min_length` characters from every
+/// "claude" or "codex" session, most frequently used first
+#[tauri::command]
+pub async fn extract_prompts_to_library(
+    tool: String,
+    min_length: usize,
+) -> Result<Vec<PromptTemplateCandidate>, String> {
+    log::info!("Extracting {} prompts longer than {} chars to library", tool, min_length);
+
+    let mut prompts: Vec<(String, String)> = Vec::new();
+    match tool.as_str() {
+        "claude" => {
+            for project in list_projects().await? {
+                for session_id in &project.sessions {
+                    match extract_prompts_from_jsonl(session_id, &project.id) {
+                        Ok(records) => prompts.extend(
+                            records.into_iter().map(|p| (session_id.clone(), p.text)),
+                        ),
+                        Err(e) => log::warn!("Skipping session {}: {}", session_id, e),
+                    }
+                }
+            }
+        }
+        "codex" => {
+            for session in list_codex_sessions(None).await? {
+                match extract_codex_prompts(&session.id) {
+                    Ok(records) => prompts
+                        .extend(records.into_iter().map(|p| (session.id.clone(), p.text))),
+                    Err(e) => log::warn!("[Codex] Skipping session {}: {}", session.id, e),
+                }
+            }
+        }
+        other => return Err(format!("Unsupported tool for prompt library: {}", other)),
+    }
+
+    let candidates = merge_prompts(
+        prompts
+            .into_iter()
+            .filter(|(_, text)| text.trim().chars().count() > min_length),
+    );
+    log::info!("Found {} prompt library candidate(s)", candidates.len());
+    Ok(candidates)
+}
+
+/// Merges identical and near-identical prompts, ordered by occurrences then length
+fn merge_prompts(prompts: impl Iterator<Item = (String, String)>) -> Vec<PromptTemplateCandidate> {
+    let mut candidates: Vec<PromptTemplateCandidate> = Vec::new();
+    let mut bigrams: Vec<HashMap<(char, char), usize>> = Vec::new();
+    let mut exact: HashMap<String, usize> = HashMap::new();
+
+    for (session_id, text) in prompts {
+        let normalized = normalize(&text);
+        let grams = char_bigrams(&normalized);
+        let existing = exact.get(&normalized).copied().or_else(|| {
+            bigrams
+                .iter()
+                .position(|other| dice_similarity(&grams, other) >= NEAR_DUPLICATE_THRESHOLD)
+        });
+
+        match existing {
+            Some(i) => {
+                let candidate = &mut candidates[i];
+                candidate.occurrences += 1;
+                if !candidate.session_ids.contains(&session_id) {
+                    candidate.session_ids.push(session_id);
+                }
+            }
+            None => {
+                exact.insert(normalized, candidates.len());
+                bigrams.push(grams);
+                candidates.push(PromptTemplateCandidate {
+                    text: text.trim().to_string(),
+                    occurrences: 1,
+                    session_ids: vec![session_id],
+                });
+            }
+        }
+    }
+
+    candidates.sort_by(|a, b| {
+        b.occurrences
+            .cmp(&a.occurrences)
+            .then_with(|| b.text.len().cmp(&a.text.len()))
+    });
+    candidates
+}
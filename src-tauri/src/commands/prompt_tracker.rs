@@ -8,6 +8,7 @@ use log;
 
 use super::simple_git;
 use super::claude::get_claude_dir;
+use super::session_meta::{ensure_session_unmodified, resolve_session_file};
 use super::permission_config::ClaudeExecutionConfig;
 
 /// Rewind mode for reverting prompts
@@ -178,6 +179,16 @@ fn truncate_git_records(
     Ok(())
 }
 
+/// Remove all git records of a session
+pub fn clear_git_records(session_id: &str, project_id: &str) -> Result<()> {
+    let records_path = get_git_records_path(session_id, project_id)?;
+    if records_path.exists() {
+        fs::remove_file(&records_path).context("Failed to delete git records file")?;
+        log::info!("[Git Record] Cleared git records for session {}", session_id);
+    }
+    Ok(())
+}
+
 /// Truncate session JSONL file to before a specific prompt
 /// 🆕 Now supports multiple files (main session + agent files)
 fn truncate_session_to_prompt(
@@ -538,6 +549,7 @@ pub async fn mark_prompt_completed(
 }
 
 /// Revert to a specific prompt with support for different rewind modes
+/// With `expected_mtime`, the rewind is refused if the session file changed since it was loaded.
 #[tauri::command]
 pub async fn revert_to_prompt(
     session_id: String,
@@ -545,10 +557,17 @@ pub async fn revert_to_prompt(
     project_path: String,
     prompt_index: usize,
     mode: RewindMode,
+    expected_mtime: Option<u64>,
 ) -> Result<String, String> {
     log::info!("Reverting to prompt #{} in session: {} with mode: {:?}",
         prompt_index, session_id, mode);
 
+    // Refuse before touching code or history if another window rewrote the session
+    if expected_mtime.is_some() {
+        let session_file = resolve_session_file(&session_id, "claude", Some(&project_id))?;
+        ensure_session_unmodified(&session_file, expected_mtime)?;
+    }
+
     // Load execution config to check if Git operations are disabled
     let execution_config = load_execution_config()
         .map_err(|e| format!("Failed to load execution config: {}", e))?;
@@ -761,7 +780,7 @@ pub async fn check_rewind_capabilities(
 /// Extract prompts from JSONL session file
 /// This function reads the .jsonl file and extracts all user prompts
 /// This is the single source of truth for all prompts (both CLI and project interface)
-fn extract_prompts_from_jsonl(
+pub fn extract_prompts_from_jsonl(
     session_id: &str,
     project_id: &str,
 ) -> Result<Vec<PromptRecord>> {
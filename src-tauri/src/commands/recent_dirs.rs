@@ -0,0 +1,106 @@
+//! Recently used directories
+//!
+//! Aggregates the working directories of Claude, Codex and Gemini sessions into a
+//! deduplicated, most-recent-first list for the "recent" menu. Gemini stores its
+//! sessions under a hash of the project path, so it can only add activity to
+//! directories already known from Claude or Codex.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use super::claude::{list_projects, project_path_key};
+use super::codex::list_codex_sessions;
+use super::gemini::config::get_project_session_dir;
+
+/// Number of directories returned when no limit is given
+const DEFAULT_RECENT_LIMIT: usize = 20;
+
+/// A directory worked in recently
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecentDirectory {
+    pub path: String,
+    /// Unix timestamp (seconds) of the latest session activity in the directory
+    pub last_used: u64,
+    /// Tools that have sessions in the directory ("claude", "codex", "gemini")
+    pub tools: Vec<String>,
+    pub exists: bool,
+}
+
+/// Returns the most recently used session directories across all tools
+#[tauri::command]
+pub async fn get_recent_directories(limit: Option<usize>) -> Result<Vec<RecentDirectory>, String> {
+    let limit = limit.unwrap_or(DEFAULT_RECENT_LIMIT);
+    let mut directories: HashMap<String, RecentDirectory> = HashMap::new();
+
+    match list_projects().await {
+        Ok(projects) => {
+            for project in projects {
+                record_use(&mut directories, &project.path, project.created_at, "claude");
+            }
+        }
+        Err(e) => log::warn!("Failed to list Claude projects: {}", e),
+    }
+
+    match list_codex_sessions(None).await {
+        Ok(sessions) => {
+            for session in sessions {
+                let last_used = session.updated_at.max(session.created_at);
+                record_use(&mut directories, &session.project_path, last_used, "codex");
+            }
+        }
+        Err(e) => log::warn!("Failed to list Codex sessions: {}", e),
+    }
+
+    let known_paths: Vec<String> = directories.values().map(|dir| dir.path.clone()).collect();
+    for path in known_paths {
+        if let Some(last_used) = latest_gemini_activity(&path) {
+            record_use(&mut directories, &path, last_used, "gemini");
+        }
+    }
+
+    let mut recent: Vec<RecentDirectory> = directories.into_values().collect();
+    recent.sort_by(|a, b| b.last_used.cmp(&a.last_used));
+    recent.truncate(limit);
+    Ok(recent)
+}
+
+fn record_use(
+    directories: &mut HashMap<String, RecentDirectory>,
+    path: &str,
+    last_used: u64,
+    tool: &str,
+) {
+    if path.trim().is_empty() {
+        return;
+    }
+    let entry = directories
+        .entry(project_path_key(path))
+        .or_insert_with(|| RecentDirectory {
+            path: path.to_string(),
+            last_used,
+            tools: Vec::new(),
+            exists: Path::new(path).is_dir(),
+        });
+    if last_used > entry.last_used {
+        entry.last_used = last_used;
+    }
+    if !entry.tools.iter().any(|t| t == tool) {
+        entry.tools.push(tool.to_string());
+    }
+}
+
+/// Modification time of the newest Gemini chat file recorded for `project_path`
+fn latest_gemini_activity(project_path: &str) -> Option<u64> {
+    let chats_dir = get_project_session_dir(project_path).ok()?.join("chats");
+    fs::read_dir(chats_dir)
+        .ok()?
+        .flatten()
+        .filter_map(|entry| entry.metadata().ok()?.modified().ok())
+        .filter_map(|modified| modified.duration_since(UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+        .max()
+}
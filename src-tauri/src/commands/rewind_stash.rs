@@ -0,0 +1,91 @@
+//! Rewind auto-stashes
+//!
+//! Code rewinds (Claude, Codex, Gemini) and snapshot restores stash uncommitted work
+//! as "Auto-stash before ..." before touching the tree. These commands list those
+//! stashes and apply or drop them, so they don't pile up forgotten. Only stashes
+//! created by the workbench can be managed here.
+
+use serde::{Deserialize, Serialize};
+
+use super::simple_git::{self, StashEntry};
+
+/// Marker contained in every stash message written before a rewind or restore
+const AUTO_STASH_MARKER: &str = "Auto-stash before ";
+
+/// A stash left behind by a rewind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RewindStash {
+    /// Current `stash@{n}`; changes as other stashes come and go
+    pub stash_ref: String,
+    /// Stash commit; stable, and accepted wherever a stash ref is
+    pub commit: String,
+    pub message: String,
+    pub created_at: String,
+}
+
+/// Lists the auto-stashes created by rewinds, newest first
+#[tauri::command]
+pub async fn list_rewind_stashes(project_path: String) -> Result<Vec<RewindStash>, String> {
+    if !simple_git::is_git_repo(&project_path) {
+        return Ok(Vec::new());
+    }
+    Ok(simple_git::git_stash_list(&project_path)?
+        .into_iter()
+        .filter_map(to_rewind_stash)
+        .collect())
+}
+
+/// Applies a rewind stash on top of the working tree; the stash is kept
+#[tauri::command]
+pub async fn apply_rewind_stash(project_path: String, stash_ref: String) -> Result<(), String> {
+    let stash = resolve_rewind_stash(&project_path, &stash_ref)?;
+    simple_git::git_stash_apply(&project_path, &stash.stash_ref)
+        .map_err(|e| format!("Failed to apply stash {}: {}", stash.stash_ref, e))?;
+    log::info!("Applied rewind stash {} ({}) in {}", stash.stash_ref, stash.message, project_path);
+    Ok(())
+}
+
+/// Drops a rewind stash
+#[tauri::command]
+pub async fn drop_rewind_stash(project_path: String, stash_ref: String) -> Result<(), String> {
+    let stash = resolve_rewind_stash(&project_path, &stash_ref)?;
+    simple_git::git_stash_drop(&project_path, &stash.stash_ref)
+        .map_err(|e| format!("Failed to drop stash {}: {}", stash.stash_ref, e))?;
+    log::info!("Dropped rewind stash {} ({}) in {}", stash.stash_ref, stash.message, project_path);
+    Ok(())
+}
+
+/// Finds a rewind stash by `stash@{n}` or commit hash, refusing other stashes
+fn resolve_rewind_stash(project_path: &str, stash_ref: &str) -> Result<RewindStash, String> {
+    let stash_ref = stash_ref.trim();
+    let entry = simple_git::git_stash_list(project_path)?
+        .into_iter()
+        .find(|entry| {
+            entry.stash_ref == stash_ref
+                || (stash_ref.len() >= 7 && entry.commit.starts_with(stash_ref))
+        })
+        .ok_or_else(|| format!("Stash not found: {}", stash_ref))?;
+    let reference = entry.stash_ref.clone();
+    to_rewind_stash(entry)
+        .ok_or_else(|| format!("{} was not created by a rewind and is left untouched", reference))
+}
+
+fn to_rewind_stash(entry: StashEntry) -> Option<RewindStash> {
+    // Subjects look like "On main: Auto-stash before ..."
+    let message = entry
+        .subject
+        .split_once(": ")
+        .map(|(_, message)| message)
+        .unwrap_or(&entry.subject)
+        .to_string();
+    if !message.contains(AUTO_STASH_MARKER) {
+        return None;
+    }
+    Some(RewindStash {
+        message,
+        stash_ref: entry.stash_ref,
+        commit: entry.commit,
+        created_at: entry.created_at,
+    })
+}
@@ -0,0 +1,100 @@
+//! Last run error per session
+//!
+//! Runners keep the tail of each process's stderr; when a run exits unsuccessfully
+//! the exit code and that tail are stored in the tool's process state, keyed by
+//! session ID, so the UI can show a persistent error card after the output has
+//! scrolled away. The reason is classified like acemcp enhancement errors.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use tauri::State;
+use tokio::sync::Mutex;
+
+use super::acemcp::classify_error;
+use super::claude::ClaudeProcessState;
+use super::codex::CodexProcessState;
+use super::gemini::GeminiProcessState;
+
+/// Stderr lines kept per running process
+const STDERR_TAIL_LINES: usize = 40;
+
+/// Failed runs by session ID, one map per tool's process state
+pub type LastRunErrors = Arc<Mutex<HashMap<String, LastRunError>>>;
+
+/// Exit information of a failed run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastRunError {
+    /// None when the process was killed by a signal or could not be waited on
+    pub exit_code: Option<i32>,
+    pub stderr_tail: String,
+    /// "auth", "context-length", "network" or "other"
+    pub classified_reason: String,
+    pub finished_at: String,
+}
+
+impl LastRunError {
+    pub fn new(exit_code: Option<i32>, stderr_tail: String) -> Self {
+        Self {
+            exit_code,
+            classified_reason: classify_error(&stderr_tail).to_string(),
+            stderr_tail,
+            finished_at: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// The last `STDERR_TAIL_LINES` stderr lines of a process
+#[derive(Debug, Default)]
+pub struct StderrTail {
+    lines: VecDeque<String>,
+}
+
+impl StderrTail {
+    pub fn push(&mut self, line: &str) {
+        if self.lines.len() == STDERR_TAIL_LINES {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(line.to_string());
+    }
+
+    pub fn text(&self) -> String {
+        self.lines.iter().cloned().collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// Records the outcome of a run: failures are stored, a success clears the previous error
+pub async fn record_run_outcome(
+    errors: &LastRunErrors,
+    session_id: &str,
+    success: bool,
+    exit_code: Option<i32>,
+    stderr_tail: String,
+) {
+    let mut errors = errors.lock().await;
+    if success {
+        errors.remove(session_id);
+    } else {
+        errors.insert(session_id.to_string(), LastRunError::new(exit_code, stderr_tail));
+    }
+}
+
+/// Returns the error of the last failed run of a session ("claude", "codex" or "gemini")
+/// None when the session's last run succeeded or nothing was recorded.
+#[tauri::command]
+pub async fn get_last_run_error(
+    session_id: String,
+    tool: String,
+    claude_state: State<'_, ClaudeProcessState>,
+    codex_state: State<'_, CodexProcessState>,
+    gemini_state: State<'_, GeminiProcessState>,
+) -> Result<Option<LastRunError>, String> {
+    let errors = match tool.as_str() {
+        "claude" => &claude_state.last_errors,
+        "codex" => &codex_state.last_errors,
+        "gemini" => &gemini_state.last_errors,
+        other => return Err(format!("Unsupported tool: {}", other)),
+    };
+    Ok(errors.lock().await.get(&session_id).cloned())
+}
@@ -0,0 +1,110 @@
+//! Session comparison
+//!
+//! Aligns two sessions of the same tool prompt by prompt so the UI can render
+//! them side by side, e.g. after forking a session to explore an alternative.
+
+use serde::{Deserialize, Serialize};
+
+use super::transcript::{load_transcript, TranscriptEntry};
+
+/// A user prompt and the assistant response that followed it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionTurn {
+    pub prompt: String,
+    pub response: String,
+    pub timestamp: Option<String>,
+}
+
+/// One aligned row of the comparison
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDiffRow {
+    pub index: usize,
+    pub a: Option<SessionTurn>,
+    pub b: Option<SessionTurn>,
+    pub prompt_differs: bool,
+    pub response_differs: bool,
+}
+
+/// Result of comparing two sessions
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDiff {
+    pub rows: Vec<SessionDiffRow>,
+    /// Index of the first row whose prompt or response differs
+    pub first_divergence: Option<usize>,
+}
+
+/// Compares two sessions of the same tool ("claude", "codex" or "gemini") turn by turn
+#[tauri::command]
+pub async fn diff_sessions(
+    session_a: String,
+    session_b: String,
+    tool: String,
+) -> Result<SessionDiff, String> {
+    log::info!("Comparing {} sessions {} and {}", tool, session_a, session_b);
+
+    let turns_a = group_turns(&load_transcript(&tool, &session_a)?);
+    let turns_b = group_turns(&load_transcript(&tool, &session_b)?);
+
+    let row_count = turns_a.len().max(turns_b.len());
+    let mut rows = Vec::with_capacity(row_count);
+    let mut first_divergence = None;
+
+    for index in 0..row_count {
+        let a = turns_a.get(index).cloned();
+        let b = turns_b.get(index).cloned();
+
+        let (prompt_differs, response_differs) = match (&a, &b) {
+            (Some(a), Some(b)) => (
+                normalize(&a.prompt) != normalize(&b.prompt),
+                normalize(&a.response) != normalize(&b.response),
+            ),
+            _ => (true, true),
+        };
+        if first_divergence.is_none() && (prompt_differs || response_differs) {
+            first_divergence = Some(index);
+        }
+
+        rows.push(SessionDiffRow {
+            index,
+            a,
+            b,
+            prompt_differs,
+            response_differs,
+        });
+    }
+
+    Ok(SessionDiff {
+        rows,
+        first_divergence,
+    })
+}
+
+/// Groups a transcript into turns; consecutive assistant messages are joined
+fn group_turns(entries: &[TranscriptEntry]) -> Vec<SessionTurn> {
+    let mut turns: Vec<SessionTurn> = Vec::new();
+
+    for entry in entries {
+        if entry.role == "user" {
+            turns.push(SessionTurn {
+                prompt: entry.text.clone(),
+                response: String::new(),
+                timestamp: entry.timestamp.clone(),
+            });
+        } else if let Some(turn) = turns.last_mut() {
+            if !turn.response.is_empty() {
+                turn.response.push_str("\n\n");
+            }
+            turn.response.push_str(entry.text.trim());
+        }
+    }
+
+    turns
+}
+
+/// Whitespace-insensitive form used for comparison
+fn normalize(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
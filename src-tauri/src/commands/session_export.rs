@@ -0,0 +1,150 @@
+//! Session export
+//!
+//! Bulk export of session metadata for offline analysis.
+
+use std::fs;
+use std::path::PathBuf;
+
+use super::claude::{get_claude_dir, get_project_sessions, list_projects};
+use super::codex::{extract_codex_prompts, list_codex_sessions};
+use super::transcript::parse_claude_transcript;
+
+/// Maximum number of characters of the first message written to the CSV
+const FIRST_MESSAGE_MAX_CHARS: usize = 200;
+
+/// One exported row
+struct SessionRow {
+    session_id: String,
+    project_path: String,
+    created_at: String,
+    updated_at: String,
+    model: String,
+    first_message: String,
+    prompt_count: usize,
+}
+
+/// Exports one CSV row per session of the given tool ("claude" or "codex")
+/// Returns the number of rows written
+#[tauri::command]
+pub async fn export_sessions_csv(tool: String, target_path: String) -> Result<usize, String> {
+    log::info!("Exporting {} sessions as CSV to: {}", tool, target_path);
+
+    let rows = match tool.as_str() {
+        "claude" => collect_claude_rows().await?,
+        "codex" => collect_codex_rows().await?,
+        other => return Err(format!("Unsupported tool for CSV export: {}", other)),
+    };
+
+    let mut csv = String::from(
+        "session_id,project_path,created_at,updated_at,model,first_message,prompt_count\n",
+    );
+    for row in &rows {
+        let fields = [
+            row.session_id.as_str(),
+            row.project_path.as_str(),
+            row.created_at.as_str(),
+            row.updated_at.as_str(),
+            row.model.as_str(),
+            row.first_message.as_str(),
+        ];
+        let mut line: Vec<String> = fields.iter().map(|f| csv_escape(f)).collect();
+        line.push(row.prompt_count.to_string());
+        csv.push_str(&line.join(","));
+        csv.push('\n');
+    }
+
+    let target = PathBuf::from(&target_path);
+    if let Some(parent) = target.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create target directory: {}", e))?;
+        }
+    }
+    fs::write(&target, csv).map_err(|e| format!("Failed to write CSV file: {}", e))?;
+
+    log::info!("Exported {} {} sessions to {}", rows.len(), tool, target_path);
+    Ok(rows.len())
+}
+
+async fn collect_claude_rows() -> Result<Vec<SessionRow>, String> {
+    let projects_dir = get_claude_dir().map_err(|e| e.to_string())?.join("projects");
+    let mut rows = Vec::new();
+
+    for project in list_projects().await? {
+        let sessions = match get_project_sessions(project.id.clone()).await {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                log::warn!("Skipping project {} in CSV export: {}", project.id, e);
+                continue;
+            }
+        };
+
+        for session in sessions {
+            let session_file = projects_dir
+                .join(&session.project_id)
+                .join(format!("{}.jsonl", session.id));
+            let prompt_count = parse_claude_transcript(&session_file)
+                .map(|entries| entries.iter().filter(|e| e.role == "user").count())
+                .unwrap_or(0);
+
+            rows.push(SessionRow {
+                session_id: session.id,
+                project_path: session.project_path,
+                created_at: format_unix_seconds(session.created_at),
+                updated_at: session.last_message_timestamp.unwrap_or_default(),
+                model: session.model.unwrap_or_default(),
+                first_message: truncate_chars(&session.first_message.unwrap_or_default()),
+                prompt_count,
+            });
+        }
+    }
+
+    Ok(rows)
+}
+
+async fn collect_codex_rows() -> Result<Vec<SessionRow>, String> {
+    let mut rows = Vec::new();
+
+    for session in list_codex_sessions(None).await? {
+        let prompt_count = extract_codex_prompts(&session.id)
+            .map(|prompts| prompts.len())
+            .unwrap_or(0);
+
+        rows.push(SessionRow {
+            session_id: session.id,
+            project_path: session.project_path,
+            created_at: format_unix_seconds(session.created_at),
+            updated_at: format_unix_seconds(session.updated_at),
+            model: session.model.unwrap_or_default(),
+            first_message: truncate_chars(&session.first_message.unwrap_or_default()),
+            prompt_count,
+        });
+    }
+
+    Ok(rows)
+}
+
+fn format_unix_seconds(secs: u64) -> String {
+    chrono::DateTime::from_timestamp(secs as i64, 0)
+        .map(|dt| dt.to_rfc3339())
+        .unwrap_or_default()
+}
+
+fn truncate_chars(text: &str) -> String {
+    let single_line = text.replace(['\r', '\n'], " ");
+    if single_line.chars().count() <= FIRST_MESSAGE_MAX_CHARS {
+        single_line
+    } else {
+        let truncated: String = single_line.chars().take(FIRST_MESSAGE_MAX_CHARS).collect();
+        format!("{}...", truncated)
+    }
+}
+
+/// Quotes a CSV field when it contains separators, quotes or newlines (RFC 4180)
+fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
@@ -0,0 +1,358 @@
+//! Session HTML export
+//!
+//! Renders a session transcript as a single self-contained HTML file for sharing.
+//! CSS is embedded, fenced code blocks are highlighted with syntect's bundled
+//! syntaxes and themes, tool calls render as collapsible blocks, and
+//! diffs/patches get added/removed line colouring.
+
+use lazy_static::lazy_static;
+use std::fs;
+use std::path::PathBuf;
+use syntect::highlighting::ThemeSet;
+use syntect::html::highlighted_html_for_string;
+use syntect::parsing::SyntaxSet;
+
+use super::transcript::{load_transcript_with_tools, TranscriptEntry};
+
+/// Tool results longer than this are truncated in the export
+const TOOL_RESULT_MAX_CHARS: usize = 20_000;
+
+/// Bundled syntect theme used for code blocks
+const CODE_THEME: &str = "InspiredGitHub";
+
+lazy_static! {
+    static ref SYNTAX_SET: SyntaxSet = SyntaxSet::load_defaults_newlines();
+    static ref THEME_SET: ThemeSet = ThemeSet::load_defaults();
+}
+
+/// Renders a session as HTML and writes it to `target_path`
+/// Returns the number of transcript entries written.
+#[tauri::command]
+pub async fn export_session_html(
+    session_id: String,
+    tool: String,
+    target_path: String,
+) -> Result<usize, String> {
+    log::info!(
+        "Exporting {} session {} as HTML to: {}",
+        tool,
+        session_id,
+        target_path
+    );
+
+    let entries = load_transcript_with_tools(&tool, &session_id)?;
+    if entries.is_empty() {
+        return Err(format!("Session {} has no messages to export", session_id));
+    }
+
+    let title = format!("{} session {}", tool_display_name(&tool), session_id);
+    let html = render_session_html(&title, &entries);
+
+    let target = PathBuf::from(&target_path);
+    if let Some(parent) = target.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create target directory: {}", e))?;
+        }
+    }
+    fs::write(&target, html).map_err(|e| format!("Failed to write HTML file: {}", e))?;
+
+    log::info!("Exported {} entries to {}", entries.len(), target_path);
+    Ok(entries.len())
+}
+
+fn tool_display_name(tool: &str) -> &str {
+    match tool {
+        "claude" => "Claude",
+        "codex" => "Codex",
+        "gemini" => "Gemini",
+        other => other,
+    }
+}
+
+/// Renders transcript entries as a complete HTML document
+pub fn render_session_html(title: &str, entries: &[TranscriptEntry]) -> String {
+    let body: String = entries.iter().map(render_entry).collect();
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{title}</title>\n<style>{css}</style>\n</head>\n<body>\n<main>\n\
+         <h1>{title}</h1>\n<p class=\"meta\">Exported {exported} · {count} entries</p>\n\
+         {body}</main>\n</body>\n</html>\n",
+        title = escape_html(title),
+        css = STYLESHEET,
+        exported = chrono::Local::now().format("%Y-%m-%d %H:%M"),
+        count = entries.len(),
+        body = body,
+    )
+}
+
+fn render_entry(entry: &TranscriptEntry) -> String {
+    let time = entry
+        .timestamp
+        .as_deref()
+        .map(|ts| format!("<time>{}</time>", escape_html(ts)))
+        .unwrap_or_default();
+    let tool_name = escape_html(entry.tool_name.as_deref().unwrap_or("tool"));
+
+    match entry.role.as_str() {
+        "tool_call" => {
+            let code = match edit_as_diff(&entry.text) {
+                Some(diff) => render_diff(&diff),
+                None if looks_like_diff(&entry.text) => render_diff(&entry.text),
+                None => render_code(&entry.text, "json"),
+            };
+            format!(
+                "<details class=\"tool tool-call\"><summary>Tool call: <b>{}</b>{}</summary>{}</details>\n",
+                tool_name, time, code
+            )
+        }
+        "tool_result" => {
+            let text = truncate_chars(&entry.text, TOOL_RESULT_MAX_CHARS);
+            let code = if looks_like_diff(&text) {
+                render_diff(&text)
+            } else {
+                render_code(&text, "")
+            };
+            format!(
+                "<details class=\"tool tool-result\"><summary>Result: <b>{}</b>{}</summary>{}</details>\n",
+                tool_name, time, code
+            )
+        }
+        role => {
+            let (class, speaker) = if role == "user" {
+                ("user", "User")
+            } else {
+                ("assistant", "Assistant")
+            };
+            format!(
+                "<section class=\"message {}\"><header><span class=\"speaker\">{}</span>{}</header>{}</section>\n",
+                class,
+                speaker,
+                time,
+                render_markdown(&entry.text)
+            )
+        }
+    }
+}
+
+/// Renders prose with fenced code blocks; other Markdown is kept as preformatted text
+fn render_markdown(text: &str) -> String {
+    let mut out = String::new();
+    let mut prose: Vec<&str> = Vec::new();
+    let mut code: Vec<&str> = Vec::new();
+    let mut fence_lang: Option<String> = None;
+
+    for line in text.trim().lines() {
+        let fence = line.trim_start().strip_prefix("```");
+        match (&fence_lang, fence) {
+            (None, Some(lang)) => {
+                out.push_str(&render_prose(&prose.join("\n")));
+                prose.clear();
+                fence_lang = Some(lang.trim().to_string());
+            }
+            (Some(lang), Some(_)) => {
+                out.push_str(&render_code(&code.join("\n"), lang));
+                code.clear();
+                fence_lang = None;
+            }
+            (Some(_), None) => code.push(line),
+            (None, None) => prose.push(line),
+        }
+    }
+    // An unclosed fence still renders as code
+    if let Some(lang) = fence_lang {
+        out.push_str(&render_code(&code.join("\n"), &lang));
+    }
+    out.push_str(&render_prose(&prose.join("\n")));
+    out
+}
+
+fn render_prose(text: &str) -> String {
+    let text = text.trim();
+    if text.is_empty() {
+        return String::new();
+    }
+    // Inline `code` spans; the backticks themselves never need escaping
+    let escaped = escape_html(text);
+    let mut html = String::new();
+    for (i, part) in escaped.split('`').enumerate() {
+        if i % 2 == 1 {
+            html.push_str(&format!("<code>{}</code>", part));
+        } else {
+            html.push_str(part);
+        }
+    }
+    format!("<div class=\"prose\">{}</div>", html)
+}
+
+fn render_code(code: &str, lang: &str) -> String {
+    if lang.eq_ignore_ascii_case("diff") || lang.eq_ignore_ascii_case("patch") {
+        return render_diff(code);
+    }
+    let label = if lang.is_empty() {
+        String::new()
+    } else {
+        format!("<span class=\"lang\">{}</span>", escape_html(lang))
+    };
+    format!("<div class=\"code\">{}{}</div>", label, highlight(code, lang))
+}
+
+/// Highlights `code` for `lang` as a `<pre>` block; unknown languages are only escaped
+pub fn highlight(code: &str, lang: &str) -> String {
+    // The bundled JavaScript syntax also covers TypeScript and JSX well enough
+    let token = match lang.to_lowercase().as_str() {
+        "ts" | "typescript" | "tsx" | "jsx" | "mjs" | "cjs" => "js".to_string(),
+        other => other.to_string(),
+    };
+    SYNTAX_SET
+        .find_syntax_by_token(&token)
+        .and_then(|syntax| {
+            highlighted_html_for_string(code, &SYNTAX_SET, syntax, &THEME_SET.themes[CODE_THEME])
+                .ok()
+        })
+        .unwrap_or_else(|| format!("<pre>{}</pre>", escape_html(code)))
+}
+
+/// Renders a unified diff or Codex patch with per-line classes
+fn render_diff(diff: &str) -> String {
+    let lines: String = diff
+        .lines()
+        .map(|line| {
+            format!(
+                "<span class=\"line {}\">{}</span>\n",
+                diff_line_class(line),
+                escape_html(line)
+            )
+        })
+        .collect();
+    format!(
+        "<div class=\"code diff\"><span class=\"lang\">diff</span><pre><code>{}</code></pre></div>",
+        lines
+    )
+}
+
+fn diff_line_class(line: &str) -> &'static str {
+    if line.starts_with("+++") || line.starts_with("---") || line.starts_with("diff --git")
+        || line.starts_with("*** ")
+    {
+        "meta"
+    } else if line.starts_with("@@") {
+        "hunk"
+    } else if line.starts_with('+') {
+        "add"
+    } else if line.starts_with('-') {
+        "del"
+    } else {
+        "ctx"
+    }
+}
+
+/// True for unified diffs (`diff --git`, `@@` hunks) and Codex `*** Begin Patch` patches
+fn looks_like_diff(text: &str) -> bool {
+    let has_header = text.lines().any(|line| {
+        line.starts_with("@@") || line.starts_with("diff --git") || line == "*** Begin Patch"
+    });
+    has_header
+        && text
+            .lines()
+            .any(|line| line.starts_with('+') || line.starts_with('-'))
+}
+
+/// Renders Edit-style tool input (`old_string` / `new_string`) as a diff
+fn edit_as_diff(input: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(input).ok()?;
+    let old = value["old_string"].as_str()?;
+    let new = value["new_string"].as_str()?;
+
+    let mut diff = String::new();
+    if let Some(path) = value["file_path"].as_str() {
+        diff.push_str(&format!("--- {}\n+++ {}\n", path, path));
+    }
+    for line in old.lines() {
+        diff.push_str(&format!("-{}\n", line));
+    }
+    for line in new.lines() {
+        diff.push_str(&format!("+{}\n", line));
+    }
+    Some(diff)
+}
+
+fn truncate_chars(text: &str, max_chars: usize) -> String {
+    match text.char_indices().nth(max_chars) {
+        Some((idx, _)) => format!("{}\n… (truncated)", &text[..idx]),
+        None => text.to_string(),
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+const STYLESHEET: &str = r#"
+:root { color-scheme: light dark; --bg: #ffffff; --fg: #1f2328; --muted: #656d76; --border: #d0d7de;
+  --user: #ddf4ff; --assistant: #f6f8fa; --code: #f6f8fa; --add: #dafbe1; --del: #ffebe9; --hunk: #ddf4ff; }
+@media (prefers-color-scheme: dark) {
+  :root { --bg: #0d1117; --fg: #e6edf3; --muted: #8d96a0; --border: #30363d; --user: #0c2d4a;
+    --assistant: #161b22; --code: #161b22; --add: #12361f; --del: #4a1a1d; --hunk: #0c2d4a; }
+}
+body { margin: 0; background: var(--bg); color: var(--fg);
+  font: 15px/1.6 -apple-system, BlinkMacSystemFont, "Segoe UI", "PingFang SC", "Microsoft YaHei", sans-serif; }
+main { max-width: 920px; margin: 0 auto; padding: 32px 20px 64px; }
+h1 { font-size: 22px; margin: 0 0 4px; }
+.meta { color: var(--muted); margin: 0 0 24px; font-size: 13px; }
+.message { border: 1px solid var(--border); border-radius: 8px; padding: 12px 16px; margin: 16px 0; }
+.message.user { background: var(--user); }
+.message.assistant { background: var(--assistant); }
+header { display: flex; justify-content: space-between; margin-bottom: 6px; }
+.speaker { font-weight: 600; }
+time { color: var(--muted); font-size: 12px; margin-left: 8px; }
+.prose { white-space: pre-wrap; word-wrap: break-word; }
+code { font-family: ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; font-size: 13px; }
+.prose code { background: var(--code); border: 1px solid var(--border); border-radius: 4px; padding: 0 4px; }
+.code { position: relative; margin: 8px 0; }
+.code pre { background: var(--code); border: 1px solid var(--border); border-radius: 6px; padding: 12px;
+  overflow-x: auto; margin: 0; font: 13px/1.5 ui-monospace, SFMono-Regular, Menlo, Consolas, monospace; }
+.code .lang { position: absolute; top: 4px; right: 8px; color: var(--muted); font-size: 11px; }
+.diff .line { display: block; }
+.diff .add { background: var(--add); } .diff .del { background: var(--del); }
+.diff .hunk { background: var(--hunk); color: var(--muted); } .diff .meta { color: var(--muted); font-weight: 600; }
+details.tool { border: 1px dashed var(--border); border-radius: 8px; padding: 6px 12px; margin: 8px 0 8px 24px; }
+details.tool summary { cursor: pointer; color: var(--muted); font-size: 13px; }
+details.tool-result { margin-left: 48px; }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlights_tokens_and_escapes_html() {
+        let html = highlight("fn main() { let s = \"<a>\"; } // done", "rust");
+        assert!(html.starts_with("<pre"));
+        assert!(html.contains(">fn </span>"));
+        assert!(html.contains("&quot;&lt;a&gt;&quot;</span>"));
+        assert!(!html.contains("<a>"));
+        assert_eq!(highlight("<b>", "unknown"), "<pre>&lt;b&gt;</pre>");
+    }
+
+    #[test]
+    fn detects_and_classifies_diffs() {
+        let patch = "*** Begin Patch\n*** Update File: a.rs\n@@\n-old\n+new\n ctx\n*** End Patch";
+        assert!(looks_like_diff(patch));
+        assert!(!looks_like_diff("- a bullet\n- another"));
+        let classes: Vec<_> = patch.lines().map(diff_line_class).collect();
+        assert_eq!(classes, ["meta", "meta", "hunk", "del", "add", "ctx", "meta"]);
+    }
+}
@@ -0,0 +1,332 @@
+//! Lightweight session metadata
+//!
+//! Reads just the head of a Claude or Codex session file for the fields shown on
+//! session cards, and counts the remaining events without parsing them. Gemini
+//! stores one JSON document per session, so it is still parsed whole.
+//!
+//! The file's modification time doubles as a version for optimistic concurrency:
+//! commands that rewrite a session accept the `modifiedAtMs` seen when it was
+//! loaded and refuse to write if another window changed the file since.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use super::claude::{decode_project_path, extract_first_user_message, get_claude_dir};
+use super::codex::{find_session_file, get_codex_sessions_dir};
+use super::transcript::find_gemini_session_file;
+
+/// Leading lines parsed for metadata; the rest of the file is only counted
+const HEAD_SCAN_LINES: usize = 200;
+
+/// Header information of one session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMeta {
+    pub id: String,
+    /// Unknown for Gemini, whose files only record a hash of the project path
+    pub project_path: Option<String>,
+    /// Unix seconds
+    pub created_at: u64,
+    pub model: Option<String>,
+    pub first_message: Option<String>,
+    /// JSONL lines for Claude/Codex, messages for Gemini
+    pub event_count: usize,
+    /// Session file modification time in Unix milliseconds
+    pub modified_at_ms: u64,
+}
+
+/// Result of `check_session_write_conflict`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionWriteCheck {
+    /// True when the file changed after `expected_mtime`
+    pub conflict: bool,
+    pub current_mtime: u64,
+}
+
+/// Returns a session's header fields without loading its history
+/// `project_id` is required for Claude sessions and ignored otherwise.
+#[tauri::command]
+pub async fn get_session_meta(
+    session_id: String,
+    tool: String,
+    project_id: Option<String>,
+) -> Result<SessionMeta, String> {
+    let path = resolve_session_file(&session_id, &tool, project_id.as_deref())?;
+    let mut meta = match tool.as_str() {
+        "claude" => claude_meta(&path, &session_id, project_id.as_deref().unwrap_or_default())?,
+        "codex" => codex_meta(&path, &session_id)?,
+        _ => gemini_meta(&path, &session_id)?,
+    };
+    meta.modified_at_ms = file_mtime_ms(&path);
+    Ok(meta)
+}
+
+/// Reports whether a session file changed after `expected_mtime` (the
+/// `modifiedAtMs` from `get_session_meta`), without writing anything
+#[tauri::command]
+pub async fn check_session_write_conflict(
+    session_id: String,
+    tool: String,
+    project_id: Option<String>,
+    expected_mtime: u64,
+) -> Result<SessionWriteCheck, String> {
+    let path = resolve_session_file(&session_id, &tool, project_id.as_deref())?;
+    let current_mtime = file_mtime_ms(&path);
+    Ok(SessionWriteCheck {
+        conflict: current_mtime > expected_mtime,
+        current_mtime,
+    })
+}
+
+/// Locates a session file; `project_id` is required for Claude sessions
+pub(crate) fn resolve_session_file(
+    session_id: &str,
+    tool: &str,
+    project_id: Option<&str>,
+) -> Result<PathBuf, String> {
+    match tool {
+        "claude" => {
+            let project_id =
+                project_id.ok_or_else(|| "project_id is required for Claude sessions".to_string())?;
+            let path = get_claude_dir()
+                .map_err(|e| e.to_string())?
+                .join("projects")
+                .join(project_id)
+                .join(format!("{}.jsonl", session_id));
+            if !path.exists() {
+                return Err(format!("Session file not found for ID: {}", session_id));
+            }
+            Ok(path)
+        }
+        "codex" => find_session_file(&get_codex_sessions_dir()?, session_id)
+            .ok_or_else(|| format!("Session file not found for ID: {}", session_id)),
+        "gemini" => find_gemini_session_file(session_id),
+        other => Err(format!("Unsupported tool: {}", other)),
+    }
+}
+
+/// Rejects a write when the file changed after `expected_mtime` (Unix ms)
+/// No check is made when the caller did not capture an mtime.
+pub(crate) fn ensure_session_unmodified(path: &Path, expected_mtime: Option<u64>) -> Result<(), String> {
+    let expected = match expected_mtime {
+        Some(expected) => expected,
+        None => return Ok(()),
+    };
+    let current = file_mtime_ms(path);
+    if current > expected {
+        log::warn!(
+            "Refusing to rewrite {:?}: modified at {} ms, loaded at {} ms",
+            path,
+            current,
+            expected
+        );
+        return Err(format!(
+            "Conflict: the session was modified after it was loaded (expected mtime {}, found {}). \
+             Reload the session and try again.",
+            expected, current
+        ));
+    }
+    Ok(())
+}
+
+fn file_mtime_ms(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.modified())
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis() as u64
+}
+
+fn claude_meta(path: &Path, session_id: &str, project_id: &str) -> Result<SessionMeta, String> {
+    let (head, event_count) = read_head(path)?;
+
+    let mut meta = SessionMeta {
+        id: session_id.to_string(),
+        event_count,
+        ..Default::default()
+    };
+    for event in &head {
+        if meta.project_path.is_none() {
+            meta.project_path = event["cwd"].as_str().map(String::from);
+        }
+        if meta.created_at == 0 {
+            meta.created_at = event["timestamp"].as_str().map(parse_rfc3339).unwrap_or(0);
+        }
+        if meta.model.is_none() {
+            meta.model = event["message"]["model"]
+                .as_str()
+                .or_else(|| event["model"].as_str())
+                .map(String::from);
+        }
+    }
+
+    meta.project_path = meta
+        .project_path
+        .or_else(|| Some(decode_project_path(project_id)));
+    if meta.created_at == 0 {
+        meta.created_at = file_created_at(path);
+    }
+    meta.first_message = extract_first_user_message(path).0;
+    Ok(meta)
+}
+
+fn codex_meta(path: &Path, session_id: &str) -> Result<SessionMeta, String> {
+    let (head, event_count) = read_head(path)?;
+
+    let mut meta = SessionMeta {
+        id: session_id.to_string(),
+        event_count,
+        ..Default::default()
+    };
+    for event in &head {
+        let payload = &event["payload"];
+        match event["type"].as_str() {
+            Some("session_meta") => {
+                meta.project_path = payload["cwd"].as_str().map(String::from);
+                meta.created_at = payload["timestamp"].as_str().map(parse_rfc3339).unwrap_or(0);
+                meta.model = meta.model.or_else(|| payload["model"].as_str().map(String::from));
+            }
+            Some("turn_context") if meta.model.is_none() => {
+                meta.model = payload["model"].as_str().map(String::from);
+            }
+            Some("response_item")
+                if meta.first_message.is_none() && payload["role"].as_str() == Some("user") =>
+            {
+                meta.first_message = payload["content"]
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter(|item| item["type"].as_str() == Some("input_text"))
+                    .filter_map(|item| item["text"].as_str())
+                    .find(|text| {
+                        !text.trim().is_empty()
+                            && !text.contains("<environment_context>")
+                            && !text.contains("# AGENTS.md instructions")
+                    })
+                    .map(String::from);
+            }
+            _ => {}
+        }
+    }
+
+    if meta.created_at == 0 {
+        meta.created_at = file_created_at(path);
+    }
+    Ok(meta)
+}
+
+fn gemini_meta(path: &Path, session_id: &str) -> Result<SessionMeta, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read session file: {}", e))?;
+    let data: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse session JSON: {}", e))?;
+    let messages = data["messages"].as_array().cloned().unwrap_or_default();
+
+    Ok(SessionMeta {
+        id: session_id.to_string(),
+        project_path: None,
+        created_at: data["startTime"]
+            .as_str()
+            .map(parse_rfc3339)
+            .filter(|secs| *secs > 0)
+            .unwrap_or_else(|| file_created_at(path)),
+        model: messages
+            .iter()
+            .find_map(|m| m["model"].as_str())
+            .map(String::from),
+        first_message: messages
+            .iter()
+            .filter(|m| m["type"].as_str() == Some("user"))
+            .find_map(|m| m["content"].as_str())
+            .map(String::from),
+        event_count: messages.len(),
+        modified_at_ms: 0,
+    })
+}
+
+/// Parses the first `HEAD_SCAN_LINES` lines and counts all non-empty lines
+fn read_head(path: &Path) -> Result<(Vec<serde_json::Value>, usize), String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open session file: {}", e))?;
+    let mut reader = BufReader::new(file);
+
+    let mut head = Vec::new();
+    let mut count = 0;
+    let mut line = String::new();
+    for _ in 0..HEAD_SCAN_LINES {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|e| format!("Failed to read session file: {}", e))?;
+        if read == 0 {
+            return Ok((head, count));
+        }
+        if line.trim().is_empty() {
+            continue;
+        }
+        count += 1;
+        if let Ok(event) = serde_json::from_str(line.trim()) {
+            head.push(event);
+        }
+    }
+
+    // Count the remaining non-empty lines without decoding them
+    let mut buf = [0u8; 64 * 1024];
+    let mut at_line_start = true;
+    loop {
+        let read = reader
+            .read(&mut buf)
+            .map_err(|e| format!("Failed to read session file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        for &byte in &buf[..read] {
+            if byte == b'\n' {
+                at_line_start = true;
+            } else if at_line_start {
+                at_line_start = false;
+                count += 1;
+            }
+        }
+    }
+    Ok((head, count))
+}
+
+fn parse_rfc3339(timestamp: &str) -> u64 {
+    chrono::DateTime::parse_from_rfc3339(timestamp)
+        .map(|dt| dt.timestamp().max(0) as u64)
+        .unwrap_or(0)
+}
+
+fn file_created_at(path: &Path) -> u64 {
+    fs::metadata(path)
+        .and_then(|m| m.created().or_else(|_| m.modified()))
+        .unwrap_or(SystemTime::UNIX_EPOCH)
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_writes_only_when_the_file_changed_after_loading() {
+        let file = tempfile::NamedTempFile::new().unwrap();
+        let loaded = file_mtime_ms(file.path());
+        assert!(loaded > 0);
+
+        // Unchanged since loading, or no mtime captured: the write goes ahead
+        assert!(ensure_session_unmodified(file.path(), Some(loaded)).is_ok());
+        assert!(ensure_session_unmodified(file.path(), None).is_ok());
+
+        // Loaded before the last change: the write is refused
+        let err = ensure_session_unmodified(file.path(), Some(loaded - 1)).unwrap_err();
+        assert!(err.starts_with("Conflict"));
+    }
+}
@@ -0,0 +1,675 @@
+//! Session maintenance operations
+//!
+//! In-place operations on recorded Claude, Codex and Gemini sessions that keep
+//! the session ID and project association intact.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use std::time::{Duration, SystemTime};
+
+use super::claude::{
+    delete_session, get_claude_dir, get_project_sessions, list_projects, project_path_key,
+};
+use super::codex::session::is_user_prompt;
+use super::codex::{
+    delete_codex_session, extract_codex_prompts, find_session_file, get_codex_git_records_dir,
+    get_codex_sessions_dir, list_codex_sessions, load_codex_git_records, save_codex_git_records,
+    CodexGitRecords,
+};
+use super::prompt_tracker::clear_git_records;
+use super::session_meta::ensure_session_unmodified;
+use super::transcript::{find_gemini_session_file, is_claude_prompt, parse_claude_transcript};
+
+/// Result of clearing a session's history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClearSessionResult {
+    pub session_id: String,
+    pub removed_events: usize,
+    pub backup_path: String,
+}
+
+/// Wipes a session's conversation while keeping its ID and project association
+/// Claude sessions keep their leading summary/file-history-snapshot lines and
+/// Codex sessions their session_meta header; the original file is backed up
+/// next to it and the session's rewind git records are reset. `expected_mtime`
+/// (from `get_session_meta`) rejects the write if the file changed since.
+#[tauri::command]
+pub async fn clear_session_history(
+    session_id: String,
+    tool: String,
+    project_id: Option<String>,
+    expected_mtime: Option<u64>,
+) -> Result<ClearSessionResult, String> {
+    log::info!("Clearing {} session history: {}", tool, session_id);
+
+    let (backup_path, removed_events) = match tool.as_str() {
+        "claude" => {
+            let project_id = project_id
+                .as_deref()
+                .ok_or_else(|| "project_id is required for Claude sessions".to_string())?;
+            let session_file = get_claude_dir()
+                .map_err(|e| e.to_string())?
+                .join("projects")
+                .join(project_id)
+                .join(format!("{}.jsonl", session_id));
+            if !session_file.exists() {
+                return Err(format!("Session file not found for ID: {}", session_id));
+            }
+            ensure_session_unmodified(&session_file, expected_mtime)?;
+            let backup = backup_session_file(&session_file)?;
+            let removed = clear_claude_messages(&session_file)?;
+            clear_git_records(&session_id, project_id)
+                .map_err(|e| format!("Failed to reset git records: {}", e))?;
+            (backup, removed)
+        }
+        "codex" => {
+            let session_file = find_session_file(&get_codex_sessions_dir()?, &session_id)
+                .ok_or_else(|| format!("Session file not found for ID: {}", session_id))?;
+            ensure_session_unmodified(&session_file, expected_mtime)?;
+            let backup = backup_session_file(&session_file)?;
+            let removed = rewrite_jsonl(&session_file, |event| {
+                event["type"].as_str() == Some("session_meta")
+            })?;
+
+            let previous = load_codex_git_records(&session_id)?;
+            save_codex_git_records(
+                &session_id,
+                &CodexGitRecords {
+                    session_id: session_id.clone(),
+                    project_path: previous.project_path,
+                    records: Vec::new(),
+                },
+            )?;
+            (backup, removed)
+        }
+        "gemini" => {
+            let session_file = find_gemini_session_file(&session_id)?;
+            ensure_session_unmodified(&session_file, expected_mtime)?;
+            let backup = backup_session_file(&session_file)?;
+            let removed = clear_gemini_messages(&session_file)?;
+            (backup, removed)
+        }
+        other => return Err(format!("Unsupported tool: {}", other)),
+    };
+
+    log::info!(
+        "Cleared {} event(s) from session {} (backup: {:?})",
+        removed_events,
+        session_id,
+        backup_path
+    );
+    Ok(ClearSessionResult {
+        session_id,
+        removed_events,
+        backup_path: backup_path.to_string_lossy().to_string(),
+    })
+}
+
+/// Result of truncating a session at a point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TruncateToTimestampResult {
+    pub session_id: String,
+    pub removed_events: usize,
+    /// Codex rewind records created after the cutoff; 0 for other tools
+    pub removed_git_records: usize,
+    pub backup_path: String,
+}
+
+/// Removes every event after the last one at or before `cutoff_timestamp` (RFC 3339)
+/// Rewinds by time instead of prompt index; for Claude and Codex a cut inside a
+/// turn moves back to that turn's prompt. The original file is backed up next
+/// to it; Codex sessions also drop the git records made after the cutoff.
+/// `expected_mtime` rejects the write if the file changed since it was loaded.
+#[tauri::command]
+pub async fn truncate_session_to_timestamp(
+    session_id: String,
+    tool: String,
+    cutoff_timestamp: String,
+    project_id: Option<String>,
+    expected_mtime: Option<u64>,
+) -> Result<TruncateToTimestampResult, String> {
+    let cutoff = DateTime::parse_from_rfc3339(&cutoff_timestamp)
+        .map_err(|e| format!("Invalid cutoff timestamp {}: {}", cutoff_timestamp, e))?
+        .with_timezone(&Utc);
+    log::info!(
+        "Truncating {} session {} to {}",
+        tool,
+        session_id,
+        cutoff.to_rfc3339()
+    );
+
+    let (backup_path, removed_events, removed_git_records) = match tool.as_str() {
+        "claude" => {
+            let project_id = project_id
+                .as_deref()
+                .ok_or_else(|| "project_id is required for Claude sessions".to_string())?;
+            let session_file = get_claude_dir()
+                .map_err(|e| e.to_string())?
+                .join("projects")
+                .join(project_id)
+                .join(format!("{}.jsonl", session_id));
+            if !session_file.exists() {
+                return Err(format!("Session file not found for ID: {}", session_id));
+            }
+            ensure_session_unmodified(&session_file, expected_mtime)?;
+            let (backup, removed) = truncate_jsonl_to_timestamp(&session_file, cutoff)?;
+            (backup, removed, 0)
+        }
+        "codex" => {
+            let session_file = find_session_file(&get_codex_sessions_dir()?, &session_id)
+                .ok_or_else(|| format!("Session file not found for ID: {}", session_id))?;
+            ensure_session_unmodified(&session_file, expected_mtime)?;
+            let (backup, removed) = truncate_jsonl_to_timestamp(&session_file, cutoff)?;
+
+            let mut git_records = load_codex_git_records(&session_id)?;
+            let before = git_records.records.len();
+            git_records
+                .records
+                .retain(|record| !parse_timestamp(&record.timestamp).is_some_and(|t| t > cutoff));
+            let removed_records = before - git_records.records.len();
+            if removed_records > 0 {
+                save_codex_git_records(&session_id, &git_records)?;
+            }
+            (backup, removed, removed_records)
+        }
+        "gemini" => {
+            let session_file = find_gemini_session_file(&session_id)?;
+            ensure_session_unmodified(&session_file, expected_mtime)?;
+            let (backup, removed) = truncate_gemini_messages_to_timestamp(&session_file, cutoff)?;
+            (backup, removed, 0)
+        }
+        other => return Err(format!("Unsupported tool: {}", other)),
+    };
+
+    log::info!(
+        "Truncated session {}: removed {} event(s) and {} git record(s) (backup: {:?})",
+        session_id,
+        removed_events,
+        removed_git_records,
+        backup_path
+    );
+    Ok(TruncateToTimestampResult {
+        session_id,
+        removed_events,
+        removed_git_records,
+        backup_path: backup_path.to_string_lossy().to_string(),
+    })
+}
+
+/// Criteria for `delete_sessions_by_filter`; a session must match every criterion set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionDeleteFilter {
+    /// Last activity (session file modification) at least this many days ago
+    pub older_than_days: Option<u64>,
+    /// At most this many user prompts
+    pub max_prompts: Option<usize>,
+    /// Restricts the filter to one project
+    pub project_path: Option<String>,
+}
+
+/// Outcome of a filtered delete
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeleteByFilterResult {
+    pub deleted_ids: Vec<String>,
+    pub reclaimed_bytes: u64,
+    /// Sessions that matched but could not be deleted
+    pub errors: Vec<String>,
+}
+
+/// A session considered by the filter
+struct FilterCandidate {
+    session_id: String,
+    project_id: String,
+    file: PathBuf,
+}
+
+/// Deletes every Claude or Codex session matching `filter`
+/// At least one of `olderThanDays` / `maxPrompts` is required, so a project filter
+/// alone can't wipe a whole project. Codex sessions lose their git records too.
+#[tauri::command]
+pub async fn delete_sessions_by_filter(
+    tool: String,
+    filter: SessionDeleteFilter,
+) -> Result<DeleteByFilterResult, String> {
+    if filter.older_than_days.is_none() && filter.max_prompts.is_none() {
+        return Err("Filter must set olderThanDays or maxPrompts".to_string());
+    }
+    log::info!("Deleting {} sessions by filter: {:?}", tool, filter);
+
+    let project_path = filter
+        .project_path
+        .as_deref()
+        .map(str::trim)
+        .filter(|path| !path.is_empty());
+    let cutoff = match filter.older_than_days {
+        Some(days) => Some(
+            days.checked_mul(24 * 60 * 60)
+                .and_then(|secs| SystemTime::now().checked_sub(Duration::from_secs(secs)))
+                .ok_or_else(|| format!("olderThanDays is out of range: {}", days))?,
+        ),
+        None => None,
+    };
+
+    let candidates = match tool.as_str() {
+        "claude" => claude_filter_candidates(project_path).await?,
+        "codex" => codex_filter_candidates(project_path).await?,
+        other => return Err(format!("Unsupported tool for filtered delete: {}", other)),
+    };
+
+    let mut result = DeleteByFilterResult {
+        deleted_ids: Vec::new(),
+        reclaimed_bytes: 0,
+        errors: Vec::new(),
+    };
+
+    for candidate in candidates {
+        let metadata = match fs::metadata(&candidate.file) {
+            Ok(metadata) => metadata,
+            Err(_) => continue,
+        };
+        if let Some(cutoff) = cutoff {
+            match metadata.modified() {
+                Ok(modified) if modified <= cutoff => {}
+                _ => continue,
+            }
+        }
+        if let Some(max_prompts) = filter.max_prompts {
+            let prompts = match tool.as_str() {
+                "claude" => parse_claude_transcript(&candidate.file)
+                    .map(|entries| entries.iter().filter(|e| e.role == "user").count()),
+                _ => extract_codex_prompts(&candidate.session_id).map(|prompts| prompts.len()),
+            };
+            match prompts {
+                Ok(count) if count <= max_prompts => {}
+                _ => continue,
+            }
+        }
+
+        let mut reclaimed = metadata.len();
+        let deleted = match tool.as_str() {
+            "claude" => {
+                delete_session(candidate.session_id.clone(), candidate.project_id.clone()).await
+            }
+            _ => {
+                let outcome = delete_codex_session(candidate.session_id.clone()).await;
+                if outcome.is_ok() {
+                    reclaimed += remove_codex_git_records(&candidate.session_id);
+                }
+                outcome
+            }
+        };
+
+        match deleted {
+            Ok(_) => {
+                result.reclaimed_bytes += reclaimed;
+                result.deleted_ids.push(candidate.session_id);
+            }
+            Err(e) => result.errors.push(format!("{}: {}", candidate.session_id, e)),
+        }
+    }
+
+    log::info!(
+        "Deleted {} {} session(s) by filter, reclaimed {} bytes ({} error(s))",
+        result.deleted_ids.len(),
+        tool,
+        result.reclaimed_bytes,
+        result.errors.len()
+    );
+    Ok(result)
+}
+
+async fn claude_filter_candidates(project_path: Option<&str>) -> Result<Vec<FilterCandidate>, String> {
+    let projects_dir = get_claude_dir().map_err(|e| e.to_string())?.join("projects");
+    let project_key = project_path.map(project_path_key);
+    let mut candidates = Vec::new();
+
+    for project in list_projects().await? {
+        if let Some(key) = &project_key {
+            if project_path_key(&project.path) != *key {
+                continue;
+            }
+        }
+        let sessions = match get_project_sessions(project.id.clone()).await {
+            Ok(sessions) => sessions,
+            Err(e) => {
+                log::warn!("Skipping project {} in filtered delete: {}", project.id, e);
+                continue;
+            }
+        };
+        for session in sessions {
+            candidates.push(FilterCandidate {
+                file: projects_dir
+                    .join(&session.project_id)
+                    .join(format!("{}.jsonl", session.id)),
+                session_id: session.id,
+                project_id: session.project_id,
+            });
+        }
+    }
+    Ok(candidates)
+}
+
+async fn codex_filter_candidates(project_path: Option<&str>) -> Result<Vec<FilterCandidate>, String> {
+    let sessions_dir = get_codex_sessions_dir()?;
+    Ok(list_codex_sessions(project_path.map(String::from))
+        .await?
+        .into_iter()
+        .filter_map(|session| {
+            Some(FilterCandidate {
+                file: find_session_file(&sessions_dir, &session.id)?,
+                session_id: session.id,
+                project_id: String::new(),
+            })
+        })
+        .collect())
+}
+
+/// Removes a Codex session's rewind records, returning the bytes freed
+fn remove_codex_git_records(session_id: &str) -> u64 {
+    let records_file = match get_codex_git_records_dir() {
+        Ok(dir) => dir.join(format!("{}.json", session_id)),
+        Err(_) => return 0,
+    };
+    let size = fs::metadata(&records_file).map(|m| m.len()).unwrap_or(0);
+    match fs::remove_file(&records_file) {
+        Ok(()) => size,
+        Err(_) => 0,
+    }
+}
+
+/// Copies a session file to `<name>.<timestamp>.bak` and returns the backup path
+/// The `.bak` extension keeps backups out of every session listing
+fn backup_session_file(path: &Path) -> Result<PathBuf, String> {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .ok_or_else(|| format!("Invalid session file path: {:?}", path))?;
+    let backup = path.with_file_name(format!(
+        "{}.{}.bak",
+        file_name,
+        chrono::Utc::now().format("%Y%m%d%H%M%S")
+    ));
+    fs::copy(path, &backup).map_err(|e| format!("Failed to back up session file: {}", e))?;
+    Ok(backup)
+}
+
+/// Keeps only the JSONL events matching `keep`; returns how many were removed
+fn rewrite_jsonl<F>(path: &Path, keep: F) -> Result<usize, String>
+where
+    F: Fn(&serde_json::Value) -> bool,
+{
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read session file: {}", e))?;
+
+    let mut kept = Vec::new();
+    let mut removed = 0;
+    for line in content.lines().filter(|l| !l.trim().is_empty()) {
+        let keep_line = serde_json::from_str::<serde_json::Value>(line)
+            .map(|event| keep(&event))
+            .unwrap_or(false);
+        if keep_line {
+            kept.push(line);
+        } else {
+            removed += 1;
+        }
+    }
+
+    let mut new_content = kept.join("\n");
+    if !new_content.is_empty() {
+        new_content.push('\n');
+    }
+    fs::write(path, new_content).map_err(|e| format!("Failed to write session file: {}", e))?;
+    Ok(removed)
+}
+
+fn parse_timestamp(timestamp: &str) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(timestamp)
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// How many leading events to keep: everything up to the last event stamped at
+/// or before `cutoff`. Unstamped events stay with the events around them.
+fn events_to_keep(timestamps: &[Option<DateTime<Utc>>], cutoff: DateTime<Utc>) -> Option<usize> {
+    timestamps
+        .iter()
+        .rposition(|t| t.is_some_and(|t| t <= cutoff))
+        .map(|last| last + 1)
+}
+
+/// True for the event that opens a turn: a typed Claude prompt, or the user
+/// response_item of a Codex prompt
+fn is_turn_start(event: &serde_json::Value) -> bool {
+    is_claude_prompt(event)
+        || (event["type"].as_str() == Some("response_item")
+            && event["payload"]["role"].as_str() == Some("user")
+            && is_user_prompt(&event["payload"]))
+}
+
+/// True for bookkeeping written between turns rather than inside one: Claude's
+/// file-history snapshots and Codex's turn context and UI event lines
+fn is_between_turns(event: &serde_json::Value) -> bool {
+    matches!(
+        event["type"].as_str(),
+        Some("file-history-snapshot" | "summary" | "turn_context" | "event_msg")
+    )
+}
+
+/// Moves a cut back to the start of the turn it falls in, like the prompt-index
+/// rewind, so a tool_use is never kept without its tool_result. A cut followed
+/// only by unstamped or between-turn lines up to the next prompt (or the end)
+/// is already on a boundary.
+fn snap_to_turn_start(keep: usize, turn_starts: &[bool], skippable: &[bool]) -> Option<usize> {
+    let next = (keep..turn_starts.len())
+        .find(|&i| turn_starts[i] || !skippable[i])
+        .unwrap_or(turn_starts.len());
+    if next == turn_starts.len() || turn_starts[next] {
+        return Some(keep);
+    }
+    (1..=keep).rev().find(|&i| turn_starts[i])
+}
+
+/// How many leading JSONL events to keep for `cutoff`, snapped to a turn boundary
+fn jsonl_events_to_keep(
+    events: &[Option<serde_json::Value>],
+    cutoff: DateTime<Utc>,
+) -> Result<usize, String> {
+    let timestamps: Vec<Option<DateTime<Utc>>> = events
+        .iter()
+        .map(|event| {
+            event
+                .as_ref()
+                .and_then(|event| event["timestamp"].as_str().and_then(parse_timestamp))
+        })
+        .collect();
+    let turn_starts: Vec<bool> = events
+        .iter()
+        .map(|event| event.as_ref().is_some_and(is_turn_start))
+        .collect();
+    let skippable: Vec<bool> = events
+        .iter()
+        .zip(&timestamps)
+        .map(|(event, timestamp)| {
+            timestamp.is_none() || event.as_ref().is_some_and(is_between_turns)
+        })
+        .collect();
+
+    let keep = events_to_keep(&timestamps, cutoff)
+        .ok_or_else(|| format!("No events at or before {}", cutoff.to_rfc3339()))?;
+    snap_to_turn_start(keep, &turn_starts, &skippable)
+        .ok_or_else(|| format!("No complete turn at or before {}", cutoff.to_rfc3339()))
+}
+
+/// Truncates a JSONL session after the cutoff, returning the backup path and the
+/// number of events removed
+fn truncate_jsonl_to_timestamp(
+    path: &Path,
+    cutoff: DateTime<Utc>,
+) -> Result<(PathBuf, usize), String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read session file: {}", e))?;
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    let events: Vec<Option<serde_json::Value>> = lines
+        .iter()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .collect();
+    let keep = jsonl_events_to_keep(&events, cutoff)?;
+    let backup = backup_session_file(path)?;
+
+    let mut new_content = lines[..keep].join("\n");
+    new_content.push('\n');
+    fs::write(path, new_content).map_err(|e| format!("Failed to write session file: {}", e))?;
+    Ok((backup, lines.len() - keep))
+}
+
+/// Drops the messages of a Gemini chat file after the cutoff
+fn truncate_gemini_messages_to_timestamp(
+    path: &Path,
+    cutoff: DateTime<Utc>,
+) -> Result<(PathBuf, usize), String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read session file: {}", e))?;
+    let mut data: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse session JSON: {}", e))?;
+
+    let messages = data["messages"]
+        .as_array_mut()
+        .ok_or_else(|| "Session file has no messages".to_string())?;
+    let timestamps: Vec<Option<DateTime<Utc>>> = messages
+        .iter()
+        .map(|message| message["timestamp"].as_str().and_then(parse_timestamp))
+        .collect();
+    let keep = events_to_keep(&timestamps, cutoff)
+        .ok_or_else(|| format!("No messages at or before {}", cutoff.to_rfc3339()))?;
+    let removed = messages.len() - keep;
+    messages.truncate(keep);
+
+    let backup = backup_session_file(path)?;
+    let content = serde_json::to_string_pretty(&data)
+        .map_err(|e| format!("Failed to serialize session JSON: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write session file: {}", e))?;
+    Ok((backup, removed))
+}
+
+/// Removes every event of a Claude session except the leading summary and
+/// file-history-snapshot lines, writing a minimal snapshot if there were none
+/// so the session keeps showing up in listings; returns how many were removed
+fn clear_claude_messages(path: &Path) -> Result<usize, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read session file: {}", e))?;
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+
+    let header_len = lines
+        .iter()
+        .take_while(|line| {
+            serde_json::from_str::<serde_json::Value>(line)
+                .ok()
+                .and_then(|event| event["type"].as_str().map(|t| t.to_string()))
+                .is_some_and(|t| t == "summary" || t == "file-history-snapshot")
+        })
+        .count();
+
+    let mut new_content = if header_len > 0 {
+        lines[..header_len].join("\n")
+    } else {
+        let message_id = uuid::Uuid::new_v4().to_string();
+        let timestamp = Utc::now().to_rfc3339();
+        serde_json::json!({
+            "type": "file-history-snapshot",
+            "messageId": message_id,
+            "snapshot": {
+                "messageId": message_id,
+                "trackedFileBackups": {},
+                "timestamp": timestamp
+            },
+            "isSnapshotUpdate": false
+        })
+        .to_string()
+    };
+    new_content.push('\n');
+    fs::write(path, new_content).map_err(|e| format!("Failed to write session file: {}", e))?;
+    Ok(lines.len() - header_len)
+}
+
+/// Empties the `messages` array of a Gemini chat file; returns how many were removed
+fn clear_gemini_messages(path: &Path) -> Result<usize, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read session file: {}", e))?;
+    let mut data: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse session JSON: {}", e))?;
+
+    let removed = data["messages"].as_array().map(|m| m.len()).unwrap_or(0);
+    data["messages"] = serde_json::json!([]);
+
+    let content = serde_json::to_string_pretty(&data)
+        .map_err(|e| format!("Failed to serialize session JSON: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write session file: {}", e))?;
+    Ok(removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_events_up_to_the_last_one_before_the_cutoff() {
+        let at = |s: &str| parse_timestamp(s);
+        let timestamps = vec![
+            at("2025-06-01T10:00:00Z"),
+            None,
+            at("2025-06-01T14:59:59+00:00"),
+            None,
+            at("2025-06-01T15:30:00Z"),
+            at("2025-06-01T16:00:00Z"),
+        ];
+        let cutoff = at("2025-06-01T15:00:00Z").unwrap();
+        assert_eq!(events_to_keep(&timestamps, cutoff), Some(3));
+        assert_eq!(
+            events_to_keep(&timestamps, at("2025-06-01T09:00:00Z").unwrap()),
+            None
+        );
+    }
+
+    #[test]
+    fn snaps_a_cut_inside_a_turn_back_to_its_prompt() {
+        let event = |kind: &str, content: serde_json::Value, second: u32| {
+            Some(serde_json::json!({
+                "type": kind,
+                "timestamp": format!("2025-06-01T10:00:{:02}Z", second),
+                "message": {"content": content},
+            }))
+        };
+        let snapshot = Some(serde_json::json!({"type": "file-history-snapshot"}));
+        let cutoff = |second: u32| parse_timestamp(&format!("2025-06-01T10:00:{:02}Z", second)).unwrap();
+
+        let events = vec![
+            event("user", serde_json::json!("first"), 1),
+            event("assistant", serde_json::json!([{"type": "text"}]), 2),
+            snapshot.clone(),
+            event("user", serde_json::json!("second"), 5),
+            event("assistant", serde_json::json!([{"type": "tool_use"}]), 6),
+            event("user", serde_json::json!([{"type": "tool_result"}]), 8),
+            event("assistant", serde_json::json!([{"type": "text"}]), 9),
+            snapshot,
+            event("user", serde_json::json!("third"), 12),
+        ];
+
+        // After a completed turn, the snapshot before the next prompt doesn't count as content
+        assert_eq!(jsonl_events_to_keep(&events[..4], cutoff(3)), Ok(2));
+        assert_eq!(jsonl_events_to_keep(&events, cutoff(3)), Ok(2));
+        assert_eq!(jsonl_events_to_keep(&events, cutoff(10)), Ok(7));
+        // Cutting between the tool_use and its tool_result drops the whole second turn
+        assert_eq!(jsonl_events_to_keep(&events, cutoff(7)), Ok(3));
+        // Nothing is cut when the cutoff is past the last event
+        assert_eq!(jsonl_events_to_keep(&events, cutoff(30)), Ok(9));
+        // Inside the first turn there is no complete turn to keep
+        assert!(jsonl_events_to_keep(&events, cutoff(1)).is_err());
+    }
+}
@@ -0,0 +1,221 @@
+//! Session replay
+//!
+//! Re-emits the recorded events of a Claude, Codex or Gemini session as
+//! `session-replay-event` events, spaced by the original inter-message gaps
+//! scaled by a speed factor. Replays are keyed by session ID and can be
+//! paused, resumed and stopped.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, State};
+use tokio::sync::Mutex;
+use tokio::task::JoinHandle;
+
+use super::codex::{find_session_file, get_codex_sessions_dir};
+use super::transcript::{find_claude_session_file, find_gemini_session_file};
+
+/// Longest wait between two events, so idle gaps in the original session don't stall a replay
+const MAX_EVENT_DELAY_MS: u64 = 5000;
+
+/// Interval at which a paused replay checks whether it was resumed
+const PAUSE_POLL_MS: u64 = 100;
+
+struct ReplayHandle {
+    /// Distinguishes a replay from a later one of the same session
+    replay_id: String,
+    paused: Arc<AtomicBool>,
+    task: JoinHandle<()>,
+}
+
+/// Active replays keyed by session ID
+#[derive(Default)]
+pub struct SessionReplayState {
+    replays: Arc<Mutex<HashMap<String, ReplayHandle>>>,
+}
+
+/// Payload of a `session-replay-event` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionReplayEvent {
+    pub session_id: String,
+    pub index: usize,
+    pub total: usize,
+    pub event: serde_json::Value,
+}
+
+/// Starts replaying a session; returns the number of events that will be emitted
+/// A running replay of the same session is replaced.
+#[tauri::command]
+pub async fn start_session_replay(
+    session_id: String,
+    tool: String,
+    speed: Option<f64>,
+    app: AppHandle,
+    state: State<'_, SessionReplayState>,
+) -> Result<usize, String> {
+    let speed = speed.unwrap_or(1.0);
+    if speed.is_nan() || speed <= 0.0 {
+        return Err(format!("Replay speed must be positive, got {}", speed));
+    }
+
+    let events = load_replay_events(&tool, &session_id)?;
+    let total = events.len();
+    log::info!(
+        "Starting replay of {} session {} ({} events, speed {}x)",
+        tool,
+        session_id,
+        total,
+        speed
+    );
+
+    // Hold the map for the whole replace, so concurrent starts of the same session
+    // can't both insert and the new task can't finish before it is registered
+    let mut replays = state.replays.lock().await;
+    if let Some(previous) = replays.remove(&session_id) {
+        previous.task.abort();
+    }
+
+    let replay_id = uuid::Uuid::new_v4().to_string();
+    let paused = Arc::new(AtomicBool::new(false));
+    let task_paused = paused.clone();
+    let task_session_id = session_id.clone();
+    let task_replay_id = replay_id.clone();
+    let task_replays = state.replays.clone();
+
+    let task = tokio::spawn(async move {
+        let mut previous_ts: Option<i64> = None;
+
+        for (index, event) in events.into_iter().enumerate() {
+            let ts = event_timestamp_millis(&event);
+            if let (Some(prev), Some(current)) = (previous_ts, ts) {
+                let gap = (current - prev).max(0) as f64 / speed;
+                let delay = (gap as u64).min(MAX_EVENT_DELAY_MS);
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+            }
+            if ts.is_some() {
+                previous_ts = ts;
+            }
+
+            while task_paused.load(Ordering::SeqCst) {
+                tokio::time::sleep(Duration::from_millis(PAUSE_POLL_MS)).await;
+            }
+
+            let payload = SessionReplayEvent {
+                session_id: task_session_id.clone(),
+                index,
+                total,
+                event,
+            };
+            let _ = app.emit(&format!("session-replay-event:{}", task_session_id), &payload);
+            let _ = app.emit("session-replay-event", &payload);
+        }
+
+        // Only clear our own entry; a newer replay of the session may have replaced it
+        let mut replays = task_replays.lock().await;
+        if replays
+            .get(&task_session_id)
+            .is_some_and(|handle| handle.replay_id == task_replay_id)
+        {
+            replays.remove(&task_session_id);
+        }
+        drop(replays);
+        let _ = app.emit("session-replay-complete", &task_session_id);
+        log::info!("Replay of session {} finished", task_session_id);
+    });
+
+    replays.insert(
+        session_id,
+        ReplayHandle {
+            replay_id,
+            paused,
+            task,
+        },
+    );
+
+    Ok(total)
+}
+
+/// Pauses a running replay
+#[tauri::command]
+pub async fn pause_session_replay(
+    session_id: String,
+    state: State<'_, SessionReplayState>,
+) -> Result<(), String> {
+    set_replay_paused(&state, &session_id, true).await
+}
+
+/// Resumes a paused replay
+#[tauri::command]
+pub async fn resume_session_replay(
+    session_id: String,
+    state: State<'_, SessionReplayState>,
+) -> Result<(), String> {
+    set_replay_paused(&state, &session_id, false).await
+}
+
+/// Stops a replay; returns false if none was running
+#[tauri::command]
+pub async fn stop_session_replay(
+    session_id: String,
+    state: State<'_, SessionReplayState>,
+) -> Result<bool, String> {
+    match state.replays.lock().await.remove(&session_id) {
+        Some(handle) => {
+            handle.task.abort();
+            log::info!("Stopped replay of session {}", session_id);
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+async fn set_replay_paused(
+    state: &SessionReplayState,
+    session_id: &str,
+    paused: bool,
+) -> Result<(), String> {
+    let replays = state.replays.lock().await;
+    let handle = replays
+        .get(session_id)
+        .ok_or_else(|| format!("No replay running for session: {}", session_id))?;
+    handle.paused.store(paused, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Loads the raw events of a session in file order
+fn load_replay_events(tool: &str, session_id: &str) -> Result<Vec<serde_json::Value>, String> {
+    let path = match tool {
+        "claude" => find_claude_session_file(session_id)?,
+        "codex" => find_session_file(&get_codex_sessions_dir()?, session_id)
+            .ok_or_else(|| format!("Session file not found for ID: {}", session_id))?,
+        "gemini" => find_gemini_session_file(session_id)?,
+        other => return Err(format!("Unsupported tool: {}", other)),
+    };
+
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read session file: {}", e))?;
+
+    if tool == "gemini" {
+        // Gemini stores a single JSON document; its messages are the events
+        let data: serde_json::Value = serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse session JSON: {}", e))?;
+        return Ok(data["messages"].as_array().cloned().unwrap_or_default());
+    }
+
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+fn event_timestamp_millis(event: &serde_json::Value) -> Option<i64> {
+    event["timestamp"]
+        .as_str()
+        .and_then(|ts| chrono::DateTime::parse_from_rfc3339(ts).ok())
+        .map(|dt| dt.timestamp_millis())
+}
@@ -0,0 +1,157 @@
+//! Progressive session loading
+//!
+//! Converts a Claude, Codex or Gemini session to the unified Claude message format
+//! one message at a time and emits each as a `session-message` event, so large
+//! transcripts render progressively instead of after a full up-front conversion.
+//! A `session-message-done` event follows the last message.
+
+use serde::Serialize;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use tauri::{AppHandle, Emitter};
+
+use super::codex::session_converter::{CodexEvent, CodexToClaudeConverter};
+use super::codex::{find_session_file, get_codex_sessions_dir};
+use super::gemini::parser::convert_history_message_to_unified;
+use super::transcript::{find_claude_session_file, find_gemini_session_file};
+
+/// Payload of a `session-message` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMessageEvent {
+    pub session_id: String,
+    pub index: usize,
+    pub message: serde_json::Value,
+}
+
+/// Payload of the `session-message-done` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionMessageDone {
+    pub session_id: String,
+    pub total: usize,
+    /// Set when reading stopped early because of an error
+    pub error: Option<String>,
+}
+
+/// Streams a session ("claude", "codex" or "gemini") as unified messages
+/// Returns once the session file is located; messages arrive as events.
+#[tauri::command]
+pub async fn stream_session_unified(
+    session_id: String,
+    tool: String,
+    app: AppHandle,
+) -> Result<(), String> {
+    let path = match tool.as_str() {
+        "claude" => find_claude_session_file(&session_id)?,
+        "codex" => find_session_file(&get_codex_sessions_dir()?, &session_id)
+            .ok_or_else(|| format!("Session file not found for ID: {}", session_id))?,
+        "gemini" => find_gemini_session_file(&session_id)?,
+        other => return Err(format!("Unsupported tool: {}", other)),
+    };
+    log::info!("Streaming {} session {} from {:?}", tool, session_id, path);
+
+    tokio::task::spawn_blocking(move || {
+        let mut index = 0;
+        let mut emit = |message: serde_json::Value| {
+            let payload = SessionMessageEvent {
+                session_id: session_id.clone(),
+                index,
+                message,
+            };
+            let _ = app.emit(&format!("session-message:{}", session_id), &payload);
+            let _ = app.emit("session-message", &payload);
+            index += 1;
+        };
+
+        let result = match tool.as_str() {
+            "claude" => stream_claude(&path, &mut emit),
+            "codex" => stream_codex(&path, &session_id, &mut emit),
+            _ => stream_gemini(&path, &mut emit),
+        };
+        drop(emit);
+
+        if let Err(e) = &result {
+            log::error!("Streaming session {} stopped: {}", session_id, e);
+        }
+        let done = SessionMessageDone {
+            session_id: session_id.clone(),
+            total: index,
+            error: result.err(),
+        };
+        let _ = app.emit(&format!("session-message-done:{}", session_id), &done);
+        let _ = app.emit("session-message-done", &done);
+        log::info!("Streamed {} message(s) of session {}", done.total, session_id);
+    });
+
+    Ok(())
+}
+
+/// Claude JSONL lines already are unified messages; only conversation entries are kept
+fn stream_claude(path: &Path, emit: &mut impl FnMut(serde_json::Value)) -> Result<(), String> {
+    for line in open_lines(path)? {
+        let line = line.map_err(|e| format!("Failed to read line: {}", e))?;
+        let message: serde_json::Value = match serde_json::from_str(&line) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if matches!(
+            message["type"].as_str(),
+            Some("user") | Some("assistant") | Some("system") | Some("result")
+        ) {
+            emit(message);
+        }
+    }
+    Ok(())
+}
+
+fn stream_codex(
+    path: &Path,
+    session_id: &str,
+    emit: &mut impl FnMut(serde_json::Value),
+) -> Result<(), String> {
+    let cwd = open_lines(path)?
+        .take(10)
+        .map_while(Result::ok)
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(&line).ok())
+        .find(|event| event["type"].as_str() == Some("session_meta"))
+        .and_then(|meta| meta["payload"]["cwd"].as_str().map(String::from))
+        .unwrap_or_default();
+    let converter = CodexToClaudeConverter::new(session_id.to_string(), String::new(), cwd);
+
+    for line in open_lines(path)? {
+        let line = line.map_err(|e| format!("Failed to read line: {}", e))?;
+        let event: CodexEvent = match serde_json::from_str(&line) {
+            Ok(event) => event,
+            Err(_) => continue,
+        };
+        if let Some(message) = converter.convert_codex_event(&event) {
+            match serde_json::to_value(&message) {
+                Ok(value) => emit(value),
+                Err(e) => log::warn!("Failed to serialize converted Codex event: {}", e),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Gemini stores one JSON document, so it is parsed whole and emitted per message
+fn stream_gemini(path: &Path, emit: &mut impl FnMut(serde_json::Value)) -> Result<(), String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read session file: {}", e))?;
+    let data: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse session JSON: {}", e))?;
+
+    for message in data["messages"].as_array().into_iter().flatten() {
+        for unified in convert_history_message_to_unified(message) {
+            emit(unified);
+        }
+    }
+    Ok(())
+}
+
+fn open_lines(path: &Path) -> Result<std::io::Lines<BufReader<fs::File>>, String> {
+    let file = fs::File::open(path).map_err(|e| format!("Failed to open session file: {}", e))?;
+    Ok(BufReader::new(file).lines())
+}
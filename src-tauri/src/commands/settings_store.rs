@@ -0,0 +1,109 @@
+//! App settings store
+//!
+//! Typed access to the `app_settings` key/value table in agents.db. All reads and
+//! writes go through the managed [`AgentDb`] connection, so callers never open
+//! their own connection or repeat the table schema.
+
+use rusqlite::{params, Connection, OptionalExtension, Result as SqliteResult};
+use tauri::{AppHandle, Manager};
+
+use super::storage::AgentDb;
+
+/// Settings the backend reads and writes itself
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingKey {
+    /// Custom Claude CLI path chosen in the settings page
+    ClaudeBinaryPath,
+    /// Custom Codex CLI path chosen in the settings page
+    CodexBinaryPath,
+    /// JSON-encoded per-tool execution timeouts
+    ExecutionTimeouts,
+    /// "online" or "offline"
+    NetworkMode,
+    /// Per-session running output buffer cap in bytes
+    OutputBufferCap,
+}
+
+impl SettingKey {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SettingKey::ClaudeBinaryPath => "claude_binary_path",
+            SettingKey::CodexBinaryPath => "codex_binary_path",
+            SettingKey::ExecutionTimeouts => "execution_timeouts",
+            SettingKey::NetworkMode => "network_mode",
+            SettingKey::OutputBufferCap => "output_buffer_cap",
+        }
+    }
+}
+
+/// Runs `f` against the managed database connection
+pub fn with_connection<T>(
+    app: &AppHandle,
+    f: impl FnOnce(&Connection) -> SqliteResult<T>,
+) -> Result<T, String> {
+    let db = app
+        .try_state::<AgentDb>()
+        .ok_or_else(|| "Database is not initialized".to_string())?;
+    let conn = db.0.lock().map_err(|e| format!("Failed to lock database: {}", e))?;
+    f(&conn).map_err(|e| format!("Settings database error: {}", e))
+}
+
+pub fn get_setting(app: &AppHandle, key: SettingKey) -> Result<Option<String>, String> {
+    with_connection(app, |conn| read(conn, key.as_str()))
+}
+
+pub fn set_setting(app: &AppHandle, key: SettingKey, value: &str) -> Result<(), String> {
+    with_connection(app, |conn| write(conn, key.as_str(), value))
+}
+
+pub fn delete_setting(app: &AppHandle, key: SettingKey) -> Result<(), String> {
+    with_connection(app, |conn| delete(conn, key.as_str()))
+}
+
+/// Create the app_settings table if it does not exist
+pub fn ensure_table(conn: &Connection) -> SqliteResult<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS app_settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL
+        )",
+        [],
+    )?;
+    Ok(())
+}
+
+/// Read a raw setting, `None` when the key is not set
+pub fn read(conn: &Connection, key: &str) -> SqliteResult<Option<String>> {
+    ensure_table(conn)?;
+    conn.query_row(
+        "SELECT value FROM app_settings WHERE key = ?1",
+        params![key],
+        |row| row.get::<_, String>(0),
+    )
+    .optional()
+}
+
+/// Insert or replace a raw setting
+pub fn write(conn: &Connection, key: &str, value: &str) -> SqliteResult<()> {
+    ensure_table(conn)?;
+    conn.execute(
+        "INSERT OR REPLACE INTO app_settings (key, value) VALUES (?1, ?2)",
+        params![key, value],
+    )?;
+    Ok(())
+}
+
+/// Remove a raw setting; a missing key is not an error
+pub fn delete(conn: &Connection, key: &str) -> SqliteResult<()> {
+    ensure_table(conn)?;
+    conn.execute("DELETE FROM app_settings WHERE key = ?1", params![key])?;
+    Ok(())
+}
+
+/// All settings as (key, value), ordered by key
+pub fn list(conn: &Connection) -> SqliteResult<Vec<(String, String)>> {
+    ensure_table(conn)?;
+    let mut stmt = conn.prepare("SELECT key, value FROM app_settings ORDER BY key")?;
+    let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    rows.collect()
+}
@@ -0,0 +1,116 @@
+//! Claude settings watcher
+//!
+//! Watches `~/.claude` with debounced file system notifications and emits
+//! `claude-settings-changed` when a settings file is edited, created or removed
+//! outside the app. The backend reads settings fresh on every run and keeps no
+//! copy; the event carries the reloaded content so the frontend can replace
+//! what it has loaded instead of re-fetching. Started during app setup.
+
+use notify_debouncer_mini::notify::{RecommendedWatcher, RecursiveMode};
+use notify_debouncer_mini::{new_debouncer, DebounceEventResult, Debouncer};
+use serde::Serialize;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use super::claude::get_claude_dir;
+
+/// Quiet period before a burst of writes is reported as one change
+const DEBOUNCE_MS: u64 = 500;
+
+/// Files watched inside ~/.claude
+const WATCHED_FILES: &[&str] = &["settings.json", "execution_config.json"];
+
+/// The running watcher, if any; dropping it stops watching
+#[derive(Default)]
+pub struct SettingsWatcherState {
+    watcher: Mutex<Option<Debouncer<RecommendedWatcher>>>,
+}
+
+/// Payload of a `claude-settings-changed` event
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SettingsChangedEvent {
+    pub path: String,
+    /// True when the file was deleted
+    pub removed: bool,
+    /// The file's new content; None when removed or not valid JSON
+    pub settings: Option<serde_json::Value>,
+}
+
+/// Starts watching the Claude settings files; restarting replaces the running watcher
+#[tauri::command]
+pub async fn start_claude_settings_watcher(app: AppHandle) -> Result<(), String> {
+    start_settings_watcher(&app)
+}
+
+/// Stops the settings watcher; a no-op when none is running
+#[tauri::command]
+pub async fn stop_claude_settings_watcher(
+    state: State<'_, SettingsWatcherState>,
+) -> Result<(), String> {
+    let mut watcher = state
+        .watcher
+        .lock()
+        .map_err(|e| format!("Failed to lock settings watcher: {}", e))?;
+    if watcher.take().is_some() {
+        log::info!("Stopped Claude settings watcher");
+    }
+    Ok(())
+}
+
+/// Watches ~/.claude for changes to the settings files
+pub fn start_settings_watcher(app: &AppHandle) -> Result<(), String> {
+    let claude_dir = get_claude_dir().map_err(|e| e.to_string())?;
+
+    let emitter = app.clone();
+    let mut debouncer = new_debouncer(
+        Duration::from_millis(DEBOUNCE_MS),
+        move |result: DebounceEventResult| match result {
+            Ok(events) => {
+                for event in events {
+                    if is_watched_file(&event.path) {
+                        emit_settings_changed(&emitter, &event.path);
+                    }
+                }
+            }
+            Err(e) => log::warn!("Claude settings watcher error: {}", e),
+        },
+    )
+    .map_err(|e| format!("Failed to create settings watcher: {}", e))?;
+
+    // Watch the directory rather than the files, so atomic replaces and
+    // files created later are picked up
+    debouncer
+        .watcher()
+        .watch(&claude_dir, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {:?}: {}", claude_dir, e))?;
+    log::info!("Watching Claude settings files in {:?}", claude_dir);
+
+    let state = app.state::<SettingsWatcherState>();
+    let mut watcher = state
+        .watcher
+        .lock()
+        .map_err(|e| format!("Failed to lock settings watcher: {}", e))?;
+    *watcher = Some(debouncer);
+    Ok(())
+}
+
+fn is_watched_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| WATCHED_FILES.contains(&name))
+}
+
+fn emit_settings_changed(app: &AppHandle, path: &Path) {
+    let content = std::fs::read_to_string(path).ok();
+    log::info!("Claude settings changed on disk: {:?}", path);
+
+    let event = SettingsChangedEvent {
+        path: path.to_string_lossy().to_string(),
+        removed: content.is_none(),
+        settings: content.and_then(|content| serde_json::from_str(&content).ok()),
+    };
+    let _ = app.emit("claude-settings-changed", &event);
+}
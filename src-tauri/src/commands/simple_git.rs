@@ -1,4 +1,5 @@
 use log;
+use serde::Serialize;
 use std::path::Path;
 use std::process::Command;
 
@@ -207,6 +208,40 @@ pub fn git_commit_changes(project_path: &str, message: &str) -> Result<bool, Str
     Ok(true)
 }
 
+/// Paths with uncommitted changes (including untracked files), empty for a clean tree
+pub fn git_status(project_path: &str) -> Result<Vec<String>, String> {
+    // Not via run_git: trimming would eat the leading space of the first status code
+    let mut cmd = Command::new("git");
+    cmd.args(["status", "--porcelain", "-z"]);
+    cmd.current_dir(project_path);
+
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+
+    let output = cmd
+        .output()
+        .map_err(|e| format!("Failed to check git status: {}", e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Git status failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    let status = String::from_utf8_lossy(&output.stdout);
+    let mut files = Vec::new();
+    let mut entries = status.split('\0').filter(|entry| !entry.is_empty());
+    while let Some(entry) = entries.next() {
+        // "XY path"; renames and copies are followed by the original path
+        let (code, path) = entry.split_at(3.min(entry.len()));
+        files.push(path.to_string());
+        if code.starts_with('R') || code.starts_with('C') {
+            entries.next();
+        }
+    }
+    Ok(files)
+}
+
 /// Reset repository to a specific commit
 pub fn git_reset_hard(project_path: &str, commit: &str) -> Result<(), String> {
     log::info!("Resetting repository to commit: {}", commit);
@@ -275,6 +310,156 @@ pub fn git_stash_save(project_path: &str, message: &str) -> Result<(), String> {
     Ok(())
 }
 
+/// Run a git command and return trimmed stdout
+fn run_git(project_path: &str, args: &[&str], envs: &[(&str, &str)]) -> Result<String, String> {
+    let mut cmd = Command::new("git");
+    cmd.args(args);
+    cmd.current_dir(project_path);
+    for (key, value) in envs {
+        cmd.env(key, value);
+    }
+
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(0x08000000); // CREATE_NO_WINDOW
+
+    let output = cmd
+        .output()
+        .map_err(|e| format!("Failed to run git {}: {}", args.join(" "), e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Snapshot the working tree (including untracked files) without touching HEAD,
+/// the index or the branch history. The snapshot commit is kept alive by a hidden
+/// ref (e.g. `refs/claude-workbench/...`) so it survives garbage collection.
+/// Returns the snapshot commit hash.
+pub fn git_snapshot(project_path: &str, ref_name: &str, message: &str) -> Result<String, String> {
+    let head = git_current_commit(project_path)?;
+
+    // Use a throwaway index so the user's staging area is left untouched
+    let git_dir = run_git(project_path, &["rev-parse", "--absolute-git-dir"], &[])?;
+    let temp_index = Path::new(&git_dir).join(format!("workbench-snapshot-{}.index", uuid::Uuid::new_v4()));
+    let temp_index_str = temp_index.to_string_lossy().to_string();
+    let envs = [("GIT_INDEX_FILE", temp_index_str.as_str())];
+
+    let result = write_snapshot_commit(project_path, &envs, &head, message);
+
+    let _ = std::fs::remove_file(&temp_index);
+    let snapshot = result?;
+
+    run_git(project_path, &["update-ref", ref_name, &snapshot], &[])?;
+    log::info!("Created snapshot {} at {}", &snapshot[..8.min(snapshot.len())], ref_name);
+    Ok(snapshot)
+}
+
+/// Stage the whole working tree into the temporary index and commit it on top of HEAD
+fn write_snapshot_commit(
+    project_path: &str,
+    envs: &[(&str, &str)],
+    head: &str,
+    message: &str,
+) -> Result<String, String> {
+    run_git(project_path, &["read-tree", "HEAD"], envs)?;
+    run_git(project_path, &["add", "-A"], envs)?;
+    let tree = run_git(project_path, &["write-tree"], envs)?;
+
+    // Nothing changed since HEAD: the current commit is the snapshot
+    let head_tree = run_git(project_path, &["rev-parse", "HEAD^{tree}"], &[])?;
+    if tree == head_tree {
+        return Ok(head.to_string());
+    }
+
+    run_git(project_path, &["commit-tree", &tree, "-p", head, "-m", message], &[])
+}
+
+/// Restore the working tree to a snapshot created by `git_snapshot` while keeping
+/// HEAD and the branch history where they are. Changes relative to HEAD are left
+/// as unstaged modifications. The working tree should be clean (stashed) first.
+pub fn git_restore_snapshot(project_path: &str, snapshot: &str) -> Result<(), String> {
+    log::info!("Restoring working tree from snapshot: {}", snapshot);
+
+    run_git(project_path, &["read-tree", "-u", "--reset", snapshot], &[])?;
+    run_git(project_path, &["reset", "-q"], &[])?;
+
+    log::info!("Successfully restored snapshot: {}", snapshot);
+    Ok(())
+}
+
+/// Record the working tree and index as a stash commit with `git stash create`.
+/// Nothing is added to the stash list and HEAD is not moved. Untracked files are
+/// not included. Returns `None` when there are no local changes.
+pub fn git_stash_create(project_path: &str, message: &str) -> Result<Option<String>, String> {
+    let commit = run_git(project_path, &["stash", "create", message], &[])?;
+    Ok((!commit.is_empty()).then_some(commit))
+}
+
+/// An entry of `git stash list`
+#[derive(Debug, Clone)]
+pub struct StashEntry {
+    /// `stash@{n}`; shifts as newer stashes are pushed or older ones dropped
+    pub stash_ref: String,
+    pub commit: String,
+    /// Reflog subject, e.g. "On main: <message>"
+    pub subject: String,
+    /// Creation time as an ISO 8601 string
+    pub created_at: String,
+}
+
+/// List stashes, newest first
+pub fn git_stash_list(project_path: &str) -> Result<Vec<StashEntry>, String> {
+    let output = run_git(project_path, &["stash", "list", "--format=%gd%x1f%H%x1f%gs%x1f%cI"], &[])?;
+    Ok(output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\u{1f}');
+            Some(StashEntry {
+                stash_ref: fields.next()?.to_string(),
+                commit: fields.next()?.to_string(),
+                subject: fields.next()?.to_string(),
+                created_at: fields.next().unwrap_or("").to_string(),
+            })
+        })
+        .collect())
+}
+
+/// Apply a stash without removing it from the stash list
+pub fn git_stash_apply(project_path: &str, stash_ref: &str) -> Result<(), String> {
+    run_git(project_path, &["stash", "apply", stash_ref], &[]).map(|_| ())
+}
+
+/// Remove a stash from the stash list
+pub fn git_stash_drop(project_path: &str, stash_ref: &str) -> Result<(), String> {
+    run_git(project_path, &["stash", "drop", stash_ref], &[]).map(|_| ())
+}
+
+/// Absolute path of the repository's .git directory
+pub fn git_absolute_dir(project_path: &str) -> Result<String, String> {
+    run_git(project_path, &["rev-parse", "--absolute-git-dir"], &[])
+}
+
+/// Whether `commit` names a commit object present in the repository
+pub fn commit_exists(project_path: &str, commit: &str) -> bool {
+    run_git(project_path, &["cat-file", "-e", &format!("{}^{{commit}}", commit)], &[]).is_ok()
+}
+
+/// Point `ref_name` at `commit`, keeping the commit alive across garbage collection
+pub fn git_update_ref(project_path: &str, ref_name: &str, commit: &str) -> Result<(), String> {
+    run_git(project_path, &["update-ref", ref_name, commit], &[]).map(|_| ())
+}
+
+/// Delete `ref_name`
+pub fn git_delete_ref(project_path: &str, ref_name: &str) -> Result<(), String> {
+    run_git(project_path, &["update-ref", "-d", ref_name], &[]).map(|_| ())
+}
+
 /// Tauri command: Check and initialize Git repository
 #[tauri::command]
 pub fn check_and_init_git(project_path: String) -> Result<bool, String> {
@@ -285,3 +470,93 @@ pub fn check_and_init_git(project_path: String) -> Result<bool, String> {
 
     Ok(was_not_initialized)
 }
+
+/// True when the directory is inside a git work tree, including one rooted in a parent
+fn is_inside_work_tree(project_path: &str) -> bool {
+    run_git(project_path, &["rev-parse", "--is-inside-work-tree"], &[])
+        .map(|output| output == "true")
+        .unwrap_or(false)
+}
+
+/// Tauri command: Initialize git in a project on demand
+/// With `make_initial_commit`, the current tree is committed when the repository has
+/// no commits yet. A directory already inside an enclosing repository is left as is
+/// rather than getting a nested one. Returns the resulting HEAD commit (None when
+/// nothing is committed).
+#[tauri::command]
+pub fn init_project_git(
+    project_path: String,
+    make_initial_commit: bool,
+) -> Result<Option<String>, String> {
+    if !Path::new(&project_path).is_dir() {
+        return Err(format!("Directory does not exist: {}", project_path));
+    }
+
+    if !is_git_repo(&project_path) && is_inside_work_tree(&project_path) {
+        log::info!(
+            "{} is already inside a git work tree, not initializing a nested repository",
+            project_path
+        );
+        return Ok(git_current_commit(&project_path).ok());
+    }
+
+    if make_initial_commit {
+        ensure_git_repo(&project_path)?;
+    } else if !is_git_repo(&project_path) {
+        log::info!("Initializing Git repository at: {}", project_path);
+        run_git(&project_path, &["init"], &[])?;
+    }
+
+    Ok(git_current_commit(&project_path).ok())
+}
+
+/// Git status of a project directory
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GitInfo {
+    /// True when the directory is inside a git work tree (not necessarily its root)
+    pub is_repo: bool,
+    pub repo_root: Option<String>,
+    /// None on a detached HEAD
+    pub current_branch: Option<String>,
+    /// None before the first commit
+    pub head_commit: Option<String>,
+    /// Uncommitted changes, including untracked files
+    pub dirty: bool,
+}
+
+/// Tauri command: Report whether a project is in a git repository, without initializing one
+#[tauri::command]
+pub fn get_git_info(project_path: String) -> Result<GitInfo, String> {
+    if !Path::new(&project_path).is_dir() {
+        return Err(format!("Directory does not exist: {}", project_path));
+    }
+
+    let repo_root = match run_git(&project_path, &["rev-parse", "--show-toplevel"], &[]) {
+        Ok(root) => root,
+        Err(_) => {
+            return Ok(GitInfo {
+                is_repo: false,
+                repo_root: None,
+                current_branch: None,
+                head_commit: None,
+                dirty: false,
+            })
+        }
+    };
+
+    let current_branch = run_git(&project_path, &["symbolic-ref", "--short", "-q", "HEAD"], &[])
+        .ok()
+        .filter(|branch| !branch.is_empty());
+    let dirty = git_status(&project_path)
+        .map(|files| !files.is_empty())
+        .unwrap_or(false);
+
+    Ok(GitInfo {
+        is_repo: true,
+        repo_root: Some(repo_root),
+        current_branch,
+        head_commit: git_current_commit(&project_path).ok(),
+        dirty,
+    })
+}
@@ -6,6 +6,8 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 use tauri::{AppHandle, Manager, State};
 
+use super::settings_store;
+
 // Database wrapper for storage operations
 pub struct AgentDb(pub Mutex<Connection>);
 
@@ -95,6 +97,8 @@ pub fn init_database(app: &AppHandle) -> SqliteResult<Connection> {
 
     log::info!("✅ Database indexes created successfully (6 indexes)");
 
+    settings_store::ensure_table(&conn)?;
+
     Ok(conn)
 }
 
@@ -789,3 +793,108 @@ pub async fn storage_analyze_query(
 
     Ok(result)
 }
+
+// ========== 应用设置（app_settings 键值表）==========
+
+/// A key/value pair from the app_settings table
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AppSetting {
+    pub key: String,
+    pub value: String,
+}
+
+/// Get an app setting by key
+#[tauri::command]
+pub async fn get_app_setting(db: State<'_, AgentDb>, key: String) -> Result<Option<String>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    settings_store::read(&conn, &key).map_err(|e| format!("Failed to read setting '{}': {}", key, e))
+}
+
+/// Set an app setting
+#[tauri::command]
+pub async fn set_app_setting(
+    db: State<'_, AgentDb>,
+    key: String,
+    value: String,
+) -> Result<(), String> {
+    if key.trim().is_empty() {
+        return Err("Setting key cannot be empty".to_string());
+    }
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    settings_store::write(&conn, &key, &value)
+        .map_err(|e| format!("Failed to write setting '{}': {}", key, e))
+}
+
+/// List all app settings, ordered by key
+#[tauri::command]
+pub async fn list_app_settings(db: State<'_, AgentDb>) -> Result<Vec<AppSetting>, String> {
+    let conn = db.0.lock().map_err(|e| e.to_string())?;
+    let settings = settings_store::list(&conn)
+        .map_err(|e| format!("Failed to list settings: {}", e))?;
+
+    Ok(settings
+        .into_iter()
+        .map(|(key, value)| AppSetting { key, value })
+        .collect())
+}
+
+// ========== 数据库压缩 ==========
+
+/// Size of the database before and after compaction
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DatabaseCompactResult {
+    pub before_bytes: u64,
+    pub after_bytes: u64,
+    pub reclaimed_bytes: u64,
+}
+
+/// Checkpoint the WAL and VACUUM the database, reporting the on-disk size change
+#[tauri::command]
+pub async fn compact_database(
+    app: AppHandle,
+    db: State<'_, AgentDb>,
+) -> Result<DatabaseCompactResult, String> {
+    let db_path = app
+        .path()
+        .app_data_dir()
+        .map_err(|e| format!("Failed to get app data dir: {}", e))?
+        .join("agents.db");
+
+    let before_bytes = database_size_on_disk(&db_path);
+    {
+        let conn = db.0.lock().map_err(|e| e.to_string())?;
+        // Fold the WAL back into the main file first so VACUUM sees every page
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(|e| format!("Failed to checkpoint WAL: {}", e))?;
+        conn.execute("VACUUM", [])
+            .map_err(|e| format!("Failed to vacuum database: {}", e))?;
+        conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(|e| format!("Failed to checkpoint WAL: {}", e))?;
+    }
+    let after_bytes = database_size_on_disk(&db_path);
+
+    log::info!(
+        "Database compacted: {} -> {} bytes",
+        before_bytes,
+        after_bytes
+    );
+
+    Ok(DatabaseCompactResult {
+        before_bytes,
+        after_bytes,
+        reclaimed_bytes: before_bytes.saturating_sub(after_bytes),
+    })
+}
+
+/// Size of the database file plus its WAL
+fn database_size_on_disk(db_path: &std::path::Path) -> u64 {
+    let wal_path = db_path.with_file_name(format!(
+        "{}-wal",
+        db_path.file_name().unwrap_or_default().to_string_lossy()
+    ));
+    [db_path, wal_path.as_path()]
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum()
+}
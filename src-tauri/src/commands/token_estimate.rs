@@ -0,0 +1,200 @@
+//! Local token estimation
+//!
+//! Gives the composer a live token count without a network round trip. GPT/Codex
+//! models are counted exactly with OpenAI's public BPE encodings (o200k_base for
+//! current models, cl100k_base for GPT-4/GPT-3.5). Claude and Gemini do not
+//! publish their tokenizers, so a character-class heuristic is used for them.
+
+use serde::{Deserialize, Serialize};
+use tiktoken_rs::CoreBPE;
+
+use super::gemini::get_gemini_models;
+
+/// Token estimate returned to the UI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenEstimate {
+    pub tokens: usize,
+    /// "gpt", "claude" or "gemini"
+    pub model_family: String,
+    /// Context window of the model, when known
+    pub context_window: Option<u64>,
+    pub exceeds_context: bool,
+}
+
+/// Whether a prompt fits the context window of the model it is about to be sent to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PromptFitCheck {
+    pub estimated_tokens: usize,
+    /// None when the model's window is unknown (e.g. a custom provider model)
+    pub context_window: Option<u64>,
+    /// True when the window is unknown
+    pub fits: bool,
+    /// Tokens left in the window; negative when the prompt overflows it
+    pub headroom_tokens: Option<i64>,
+}
+
+/// Context windows of Claude models, matched in order against the lowercased model name
+/// Claude Code marks the long-context variants with a `[1m]` suffix.
+const CLAUDE_CONTEXT_WINDOWS: &[(&str, u64)] = &[("[1m]", 1_000_000)];
+
+/// Window of Claude models that are not in the table (aliases like "opus" or "sonnet")
+const CLAUDE_DEFAULT_CONTEXT_WINDOW: u64 = 200_000;
+
+/// Estimates the token count of `text` for `model` (defaults to a Claude model)
+#[tauri::command]
+pub async fn estimate_tokens(text: String, model: Option<String>) -> Result<TokenEstimate, String> {
+    let model = model.unwrap_or_default().to_lowercase();
+    let family = model_family(&model);
+
+    let tokens = estimate_for_family(family, &model, &text);
+    let context_window = context_window_for(family, &model);
+
+    Ok(TokenEstimate {
+        tokens,
+        model_family: family.to_string(),
+        context_window,
+        exceeds_context: context_window.is_some_and(|window| tokens as u64 > window),
+    })
+}
+
+/// Estimates `prompt` plus `context` (attached files, enhancer output) for the model
+/// of `tool` ("claude", "codex" or "gemini") and compares it with the model's window
+/// Gemini windows come from the Gemini model table, Codex and Claude from the tables here.
+#[tauri::command]
+pub async fn check_prompt_fits(
+    prompt: String,
+    context: Option<String>,
+    model: Option<String>,
+    tool: String,
+) -> Result<PromptFitCheck, String> {
+    let model = model.unwrap_or_default().to_lowercase();
+    let family = match tool.as_str() {
+        "claude" => "claude",
+        "codex" => "gpt",
+        "gemini" => "gemini",
+        other => return Err(format!("Unsupported tool: {}", other)),
+    };
+
+    // Codex runs gpt-5 family models unless a model is picked
+    let model = if family == "gpt" && model.is_empty() {
+        "gpt-5".to_string()
+    } else {
+        model
+    };
+
+    let estimated_tokens = estimate_for_family(family, &model, &prompt)
+        + context
+            .as_deref()
+            .map_or(0, |context| estimate_for_family(family, &model, context));
+
+    let context_window = match family {
+        "gemini" => get_gemini_models()
+            .await?
+            .into_iter()
+            .find(|info| if model.is_empty() { info.is_default } else { info.id == model })
+            .map(|info| info.context_window)
+            .or_else(|| context_window_for(family, &model)),
+        _ => context_window_for(family, &model),
+    };
+    let headroom_tokens = context_window.map(|window| window as i64 - estimated_tokens as i64);
+
+    Ok(PromptFitCheck {
+        estimated_tokens,
+        context_window,
+        fits: !matches!(headroom_tokens, Some(headroom) if headroom < 0),
+        headroom_tokens,
+    })
+}
+
+fn estimate_for_family(family: &str, model: &str, text: &str) -> usize {
+    match family {
+        "gpt" => count_gpt_tokens(model, text),
+        "gemini" => estimate_heuristic_tokens(text, 4.0),
+        _ => estimate_heuristic_tokens(text, 3.5),
+    }
+}
+
+fn model_family(model: &str) -> &'static str {
+    if model.starts_with("gpt") || model.contains("codex") || is_o_series(model) {
+        "gpt"
+    } else if model.starts_with("gemini") {
+        "gemini"
+    } else {
+        "claude"
+    }
+}
+
+/// OpenAI reasoning models ("o1", "o3-mini", "o4-mini", ...)
+fn is_o_series(model: &str) -> bool {
+    let mut chars = model.chars();
+    chars.next() == Some('o') && chars.next().is_some_and(|c| c.is_ascii_digit())
+}
+
+fn context_window_for(family: &str, model: &str) -> Option<u64> {
+    match family {
+        "claude" => Some(
+            CLAUDE_CONTEXT_WINDOWS
+                .iter()
+                .find(|(pattern, _)| model.contains(pattern))
+                .map_or(CLAUDE_DEFAULT_CONTEXT_WINDOW, |(_, window)| *window),
+        ),
+        "gemini" => Some(1_000_000),
+        _ if model.starts_with("gpt-5") || model.contains("codex") => Some(400_000),
+        _ if model.starts_with("gpt-4.1") => Some(1_047_576),
+        _ if model.starts_with("gpt-4o") => Some(128_000),
+        _ if is_o_series(model) => Some(200_000),
+        _ => None,
+    }
+}
+
+/// BPE encoding used by an OpenAI model; GPT-4 and GPT-3.5 predate o200k_base
+fn gpt_encoding(model: &str) -> &'static CoreBPE {
+    let is_cl100k = (model.starts_with("gpt-4")
+        && !model.starts_with("gpt-4o")
+        && !model.starts_with("gpt-4.1"))
+        || model.starts_with("gpt-3.5");
+    if is_cl100k {
+        tiktoken_rs::cl100k_base_singleton()
+    } else {
+        tiktoken_rs::o200k_base_singleton()
+    }
+}
+
+/// Exact token count of `text` with the encoding of `model`
+pub fn count_gpt_tokens(model: &str, text: &str) -> usize {
+    gpt_encoding(model).encode_ordinary(text).len()
+}
+
+/// Character-class heuristic: `chars_per_token` ASCII characters per token, one token per other character
+pub fn estimate_heuristic_tokens(text: &str, chars_per_token: f64) -> usize {
+    let non_ascii = text.chars().filter(|c| !c.is_ascii()).count();
+    let ascii = text.chars().count() - non_ascii;
+    (ascii as f64 / chars_per_token).ceil() as usize + non_ascii
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_gpt_tokens_with_the_model_encoding() {
+        assert_eq!(count_gpt_tokens("gpt-5", ""), 0);
+        // "Hello", ",", " world", "!"
+        assert_eq!(count_gpt_tokens("gpt-5", "Hello, world!"), 4);
+        assert_eq!(count_gpt_tokens("gpt-4", "Hello, world!"), 4);
+    }
+
+    #[test]
+    fn picks_family_and_context_window() {
+        assert_eq!(model_family("gpt-5.1-codex-max"), "gpt");
+        assert_eq!(model_family("gemini-2.5-pro"), "gemini");
+        assert_eq!(model_family("claude-sonnet-4-5"), "claude");
+        assert_eq!(model_family("opus"), "claude");
+        assert_eq!(model_family("o3-mini"), "gpt");
+        assert_eq!(context_window_for("gpt", "gpt-4o-mini"), Some(128_000));
+        assert_eq!(context_window_for("claude", "sonnet[1m]"), Some(1_000_000));
+        assert_eq!(context_window_for("claude", "opus"), Some(200_000));
+    }
+}
@@ -0,0 +1,343 @@
+//! Unified tool status
+//!
+//! Probes Claude Code, Codex, Gemini CLI and the acemcp sidecar concurrently so
+//! the status dashboard can render tool health from a single call instead of
+//! waiting on each availability check in turn.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::AppHandle;
+use tokio::process::Command;
+
+use super::claude::{apply_no_window_async, check_claude_version};
+use super::codex::check_codex_availability;
+use super::gemini::check_gemini_installed;
+use super::gemini::session::find_gemini_binary;
+use crate::claude_binary::{create_command_with_env, detect_binary_for_tool, find_claude_binary};
+
+/// Upper bound for a `--version` call during a benchmark
+const VERSION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Upper bound for the optional Claude `--print` round trip
+const ROUND_TRIP_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Availability of one tool
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolStatus {
+    /// "claude", "codex", "gemini" or "acemcp"
+    pub tool: String,
+    pub available: bool,
+    pub version: Option<String>,
+    pub path: Option<String>,
+    /// How the binary was found (e.g. "nvm", "homebrew", "wsl", "embedded")
+    pub source: Option<String>,
+    pub error: Option<String>,
+}
+
+impl ToolStatus {
+    fn unavailable(tool: &str, error: String) -> Self {
+        Self {
+            tool: tool.to_string(),
+            available: false,
+            version: None,
+            path: None,
+            source: None,
+            error: Some(error),
+        }
+    }
+}
+
+/// Probes all supported tools concurrently
+/// Results are always returned in the order claude, codex, gemini, acemcp.
+#[tauri::command]
+pub async fn get_all_tool_status(app: AppHandle) -> Result<Vec<ToolStatus>, String> {
+    log::info!("Probing status of all tools");
+
+    // Each probe runs on its own task: several of them do blocking binary discovery
+    let (claude, codex, gemini, acemcp) = tokio::join!(
+        tokio::spawn(probe_claude(app)),
+        tokio::spawn(probe_codex()),
+        tokio::spawn(probe_gemini()),
+        tokio::spawn(probe_acemcp()),
+    );
+
+    Ok([
+        ("claude", claude),
+        ("codex", codex),
+        ("gemini", gemini),
+        ("acemcp", acemcp),
+    ]
+    .into_iter()
+    .map(|(tool, result)| {
+        result.unwrap_or_else(|e| ToolStatus::unavailable(tool, format!("Probe failed: {}", e)))
+    })
+    .collect())
+}
+
+/// Cold-start timings of one CLI
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolStartupBenchmark {
+    pub tool: String,
+    pub binary_path: String,
+    /// Whether the CLI runs through WSL (Codex on Windows)
+    pub via_wsl: bool,
+    /// Wall time of `<cli> --version`
+    pub version_ms: u64,
+    pub version_output: String,
+    /// Wall time of a trivial `claude --print` call, when requested
+    pub round_trip_ms: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Times how long a CLI ("claude", "codex" or "gemini") takes to answer `--version`
+/// With `include_round_trip`, Claude also runs a one-word `--print` prompt, which
+/// costs a small API call but shows the full start-to-first-answer latency.
+#[tauri::command]
+pub async fn benchmark_tool_startup(
+    app: AppHandle,
+    tool: String,
+    include_round_trip: Option<bool>,
+) -> Result<ToolStartupBenchmark, String> {
+    log::info!("Benchmarking {} startup", tool);
+
+    let mut via_wsl = false;
+    let (binary_path, version_cmd) = match tool.as_str() {
+        "claude" => {
+            let path = find_claude_binary(&app)?;
+            if path == "claude-code" {
+                return Err("The bundled Claude Code sidecar cannot be benchmarked".to_string());
+            }
+            let mut cmd = Command::from(create_command_with_env(&path));
+            cmd.arg("--version");
+            (path, cmd)
+        }
+        "codex" => {
+            #[allow(unused_mut)]
+            let mut wsl_command: Option<(String, Command)> = None;
+            #[cfg(target_os = "windows")]
+            {
+                let wsl_config = super::wsl_utils::get_wsl_config();
+                if let (true, Some(path)) = (wsl_config.enabled, wsl_config.codex_path_in_wsl.clone()) {
+                    let cmd = super::wsl_utils::build_wsl_command_async(
+                        "codex",
+                        &["--version".to_string()],
+                        None,
+                        wsl_config.distro.as_deref(),
+                    );
+                    wsl_command = Some((path, cmd));
+                }
+            }
+            match wsl_command {
+                Some(found) => {
+                    via_wsl = true;
+                    found
+                }
+                None => {
+                    let path = detect_binary_for_tool("codex", "CODEX_PATH", "codex")
+                        .1
+                        .map(|inst| inst.path)
+                        .ok_or_else(|| "Codex CLI not found".to_string())?;
+                    let mut cmd = Command::new(&path);
+                    cmd.arg("--version");
+                    (path, cmd)
+                }
+            }
+        }
+        "gemini" => {
+            let path = find_gemini_binary()?;
+            let mut cmd = Command::new(&path);
+            cmd.arg("--version");
+            (path, cmd)
+        }
+        other => return Err(format!("Unsupported tool: {}", other)),
+    };
+
+    let mut benchmark = ToolStartupBenchmark {
+        tool: tool.clone(),
+        binary_path,
+        via_wsl,
+        version_ms: 0,
+        version_output: String::new(),
+        round_trip_ms: None,
+        error: None,
+    };
+
+    match timed_output(version_cmd, VERSION_TIMEOUT).await {
+        Ok((elapsed, output)) => {
+            benchmark.version_ms = elapsed;
+            benchmark.version_output = output;
+        }
+        Err(e) => {
+            benchmark.error = Some(e);
+            return Ok(benchmark);
+        }
+    }
+
+    if tool == "claude" && include_round_trip.unwrap_or(false) {
+        let mut cmd = Command::from(create_command_with_env(&benchmark.binary_path));
+        cmd.args(["--print", "Reply with the single word: ok", "--output-format", "text"]);
+        match timed_output(cmd, ROUND_TRIP_TIMEOUT).await {
+            Ok((elapsed, _)) => benchmark.round_trip_ms = Some(elapsed),
+            Err(e) => benchmark.error = Some(format!("Round trip failed: {}", e)),
+        }
+    }
+
+    log::info!(
+        "{} startup: --version {}ms, round trip {:?}ms",
+        tool,
+        benchmark.version_ms,
+        benchmark.round_trip_ms
+    );
+    Ok(benchmark)
+}
+
+/// Runs `cmd` to completion, returning elapsed milliseconds and trimmed output
+async fn timed_output(mut cmd: Command, timeout: Duration) -> Result<(u64, String), String> {
+    apply_no_window_async(&mut cmd);
+    cmd.stdin(std::process::Stdio::null()).kill_on_drop(true);
+
+    let started = Instant::now();
+    let output = tokio::time::timeout(timeout, cmd.output())
+        .await
+        .map_err(|_| format!("Timed out after {}s", timeout.as_secs()))?
+        .map_err(|e| format!("Failed to run command: {}", e))?;
+    let elapsed = started.elapsed().as_millis() as u64;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        return Err(format!(
+            "Exited with {}: {}",
+            output.status,
+            if stderr.is_empty() { stdout } else { stderr }
+        ));
+    }
+    Ok((elapsed, stdout))
+}
+
+async fn probe_claude(app: AppHandle) -> ToolStatus {
+    let path = match find_claude_binary(&app) {
+        Ok(path) => path,
+        Err(e) => return ToolStatus::unavailable("claude", e),
+    };
+    let source = if path == "claude-code" {
+        "bundled".to_string()
+    } else {
+        detection_source("claude", "CLAUDE_PATH", "claude", &path)
+    };
+
+    match check_claude_version(app).await {
+        Ok(status) => ToolStatus {
+            tool: "claude".to_string(),
+            available: status.is_installed,
+            version: status.version,
+            path: Some(path),
+            source: Some(source),
+            error: (!status.is_installed).then_some(status.output),
+        },
+        Err(e) => ToolStatus::unavailable("claude", e),
+    }
+}
+
+async fn probe_codex() -> ToolStatus {
+    let availability = match check_codex_availability().await {
+        Ok(availability) => availability,
+        Err(e) => return ToolStatus::unavailable("codex", e),
+    };
+
+    #[allow(unused_mut)]
+    let mut location: Option<(String, String)> = None;
+    #[cfg(target_os = "windows")]
+    {
+        let wsl_config = super::wsl_utils::get_wsl_config();
+        if wsl_config.enabled {
+            location = wsl_config
+                .codex_path_in_wsl
+                .clone()
+                .map(|path| (path, "wsl".to_string()));
+        }
+    }
+    let location = location.or_else(|| {
+        detect_binary_for_tool("codex", "CODEX_PATH", "codex")
+            .1
+            .map(|inst| (inst.path, inst.source))
+    });
+    let (path, source) = location.unzip();
+
+    ToolStatus {
+        tool: "codex".to_string(),
+        available: availability.available,
+        version: availability.version,
+        path,
+        source,
+        error: availability.error,
+    }
+}
+
+async fn probe_gemini() -> ToolStatus {
+    match check_gemini_installed().await {
+        Ok(status) => {
+            let from_env = std::env::var("GEMINI_CLI_PATH").ok();
+            let source = status.path.as_ref().map(|path| {
+                if from_env.as_ref() == Some(path) { "env" } else { "search" }.to_string()
+            });
+            ToolStatus {
+                tool: "gemini".to_string(),
+                available: status.installed,
+                version: status.version,
+                path: status.path,
+                source,
+                error: status.error,
+            }
+        }
+        Err(e) => ToolStatus::unavailable("gemini", e),
+    }
+}
+
+/// acemcp is embedded in the app; it is usable whenever Node.js is
+async fn probe_acemcp() -> ToolStatus {
+    let mut cmd = Command::new("node");
+    cmd.arg("--version");
+    #[cfg(target_os = "windows")]
+    {
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let node_version = match cmd.output().await {
+        Ok(output) if output.status.success() => {
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+        _ => {
+            return ToolStatus::unavailable(
+                "acemcp",
+                "Node.js not found. Please install Node.js to use acemcp.".to_string(),
+            )
+        }
+    };
+
+    let sidecar_path = dirs::home_dir()
+        .map(|home| home.join(".acemcp").join("acemcp-mcp-server.cjs"))
+        .filter(|path| path.exists())
+        .map(|path| path.to_string_lossy().to_string());
+
+    ToolStatus {
+        tool: "acemcp".to_string(),
+        available: true,
+        version: Some(format!("node {}", node_version)),
+        path: sidecar_path,
+        source: Some("embedded".to_string()),
+        error: None,
+    }
+}
+
+/// Source reported by binary discovery, or "custom" when the path was configured by hand
+fn detection_source(tool: &str, env_var: &str, config_key: &str, path: &str) -> String {
+    detect_binary_for_tool(tool, env_var, config_key)
+        .1
+        .filter(|inst| inst.path == path)
+        .map(|inst| inst.source)
+        .unwrap_or_else(|| "custom".to_string())
+}
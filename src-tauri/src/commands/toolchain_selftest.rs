@@ -0,0 +1,191 @@
+//! Toolchain self-test
+//!
+//! Runs the checks behind the "Setup Health" screen one after another: home
+//! directory writable, each CLI detected, acemcp sidecar usable, git on PATH and
+//! the tool config directories present. Every failed check carries a hint on
+//! how to fix it, which covers most first-run problems.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use tauri::AppHandle;
+use tokio::process::Command;
+
+use super::acemcp::test_acemcp_availability;
+use super::claude::apply_no_window_async;
+use super::tool_status::get_all_tool_status;
+
+/// Outcome of one check
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SelftestCheck {
+    /// Stable identifier, e.g. "home-writable" or "cli-codex"
+    pub id: String,
+    pub passed: bool,
+    pub detail: String,
+    /// How to fix a failed check
+    pub hint: Option<String>,
+}
+
+/// Result of `run_toolchain_selftest`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolchainSelftestReport {
+    pub checks: Vec<SelftestCheck>,
+    pub all_passed: bool,
+}
+
+impl SelftestCheck {
+    fn pass(id: &str, detail: String) -> Self {
+        Self {
+            id: id.to_string(),
+            passed: true,
+            detail,
+            hint: None,
+        }
+    }
+
+    fn fail(id: &str, detail: String, hint: &str) -> Self {
+        Self {
+            id: id.to_string(),
+            passed: false,
+            detail,
+            hint: Some(hint.to_string()),
+        }
+    }
+}
+
+/// Checks the whole toolchain in sequence and reports pass/fail per check
+#[tauri::command]
+pub async fn run_toolchain_selftest(app: AppHandle) -> Result<ToolchainSelftestReport, String> {
+    log::info!("Running toolchain self-test");
+    let mut checks = vec![check_home_writable()];
+
+    for status in get_all_tool_status(app.clone()).await? {
+        if status.tool == "acemcp" {
+            continue;
+        }
+        let id = format!("cli-{}", status.tool);
+        checks.push(if status.available {
+            SelftestCheck::pass(
+                &id,
+                format!(
+                    "{} {}",
+                    status.path.as_deref().unwrap_or(&status.tool),
+                    status.version.as_deref().unwrap_or("")
+                )
+                .trim()
+                .to_string(),
+            )
+        } else {
+            SelftestCheck::fail(
+                &id,
+                status.error.unwrap_or_else(|| format!("{} not found", status.tool)),
+                cli_install_hint(&status.tool),
+            )
+        });
+    }
+
+    checks.push(if test_acemcp_availability(app).await.unwrap_or(false) {
+        SelftestCheck::pass("acemcp", "Sidecar extracted and initialized".to_string())
+    } else {
+        SelftestCheck::fail(
+            "acemcp",
+            "Sidecar could not be started or initialized".to_string(),
+            "Install Node.js, configure acemcp in the settings, and make sure offline mode is off",
+        )
+    });
+
+    checks.push(check_git().await);
+    checks.extend(check_config_dirs());
+
+    let all_passed = checks.iter().all(|check| check.passed);
+    log::info!(
+        "Toolchain self-test finished: {}/{} checks passed",
+        checks.iter().filter(|check| check.passed).count(),
+        checks.len()
+    );
+    Ok(ToolchainSelftestReport { checks, all_passed })
+}
+
+fn check_home_writable() -> SelftestCheck {
+    const ID: &str = "home-writable";
+    let home = match dirs::home_dir() {
+        Some(home) => home,
+        None => {
+            return SelftestCheck::fail(
+                ID,
+                "Cannot find home directory".to_string(),
+                "Set HOME (or USERPROFILE on Windows) for the app's environment",
+            )
+        }
+    };
+
+    let probe = home.join(format!(".workbench-selftest-{}", std::process::id()));
+    match fs::write(&probe, b"ok") {
+        Ok(()) => {
+            let _ = fs::remove_file(&probe);
+            SelftestCheck::pass(ID, home.to_string_lossy().to_string())
+        }
+        Err(e) => SelftestCheck::fail(
+            ID,
+            format!("Failed to write to {}: {}", home.display(), e),
+            "Check the permissions and free space of your home directory",
+        ),
+    }
+}
+
+async fn check_git() -> SelftestCheck {
+    const ID: &str = "git";
+    let mut cmd = Command::new("git");
+    cmd.arg("--version");
+    apply_no_window_async(&mut cmd);
+
+    match cmd.output().await {
+        Ok(output) if output.status.success() => {
+            SelftestCheck::pass(ID, String::from_utf8_lossy(&output.stdout).trim().to_string())
+        }
+        Ok(output) => SelftestCheck::fail(
+            ID,
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            "Reinstall git; rewind and diff features depend on it",
+        ),
+        Err(e) => SelftestCheck::fail(
+            ID,
+            format!("Failed to run git: {}", e),
+            "Install git and make sure it is on PATH",
+        ),
+    }
+}
+
+fn check_config_dirs() -> Vec<SelftestCheck> {
+    let home = match dirs::home_dir() {
+        Some(home) => home,
+        None => return Vec::new(),
+    };
+
+    [("claude", ".claude"), ("codex", ".codex"), ("gemini", ".gemini")]
+        .iter()
+        .map(|(tool, dir)| {
+            let id = format!("config-{}", tool);
+            let path = home.join(dir);
+            if path.is_dir() {
+                SelftestCheck::pass(&id, path.to_string_lossy().to_string())
+            } else {
+                SelftestCheck::fail(
+                    &id,
+                    format!("{} does not exist", path.display()),
+                    "Run the CLI once (and log in) so it creates its config directory",
+                )
+            }
+        })
+        .collect()
+}
+
+fn cli_install_hint(tool: &str) -> &'static str {
+    match tool {
+        "claude" => "Install with `npm install -g @anthropic-ai/claude-code` or set a custom path in the settings",
+        "codex" => "Install with `npm install -g @openai/codex` or set a custom path in the settings",
+        "gemini" => "Install with `npm install -g @google/gemini-cli`",
+        _ => "Install the CLI and make sure it is on PATH",
+    }
+}
@@ -0,0 +1,413 @@
+//! Session transcript rendering
+//!
+//! Loads a Claude, Codex or Gemini session from disk into a flat list of
+//! user/assistant turns and renders it as Markdown or plain text.
+//! System and context injections are stripped, matching the Codex parser.
+//! Tool calls and their results are only kept when explicitly requested.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::claude::get_claude_dir;
+use super::codex::{find_session_file, get_codex_sessions_dir};
+use super::gemini::config::get_gemini_dir;
+
+/// A single turn of a session transcript
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TranscriptEntry {
+    /// "user" or "assistant"; "tool_call" / "tool_result" when tools are included
+    pub role: String,
+    pub text: String,
+    pub timestamp: Option<String>,
+    /// Tool name of tool_call / tool_result entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool_name: Option<String>,
+}
+
+impl TranscriptEntry {
+    fn tool(role: &str, tool_name: Option<String>, text: String, timestamp: Option<String>) -> Self {
+        Self {
+            role: role.to_string(),
+            text,
+            timestamp,
+            tool_name,
+        }
+    }
+}
+
+/// Returns true for text that is injected by the CLI rather than typed by the user
+pub fn is_injected_text(text: &str) -> bool {
+    let trimmed = text.trim();
+    trimmed.is_empty()
+        || trimmed.contains("<environment_context>")
+        || trimmed.contains("# AGENTS.md instructions")
+        || trimmed.starts_with("<system-reminder>")
+        || trimmed.starts_with("<command-name>")
+        || trimmed.starts_with("<command-message>")
+        || trimmed.starts_with("<local-command-stdout>")
+        || trimmed.starts_with("Caveat: The messages below were generated by the user")
+        || trimmed == "Warmup"
+}
+
+/// A user-typed Claude message, not a tool result or injected meta message
+pub fn is_claude_prompt(entry: &serde_json::Value) -> bool {
+    if entry["type"].as_str() != Some("user") || entry["isMeta"].as_bool() == Some(true) {
+        return false;
+    }
+    match &entry["message"]["content"] {
+        serde_json::Value::String(_) => true,
+        serde_json::Value::Array(blocks) => blocks.iter().any(|b| b["type"].as_str() == Some("text")),
+        _ => false,
+    }
+}
+
+/// Loads the transcript of a session for the given tool ("claude", "codex" or "gemini")
+pub fn load_transcript(tool: &str, session_id: &str) -> Result<Vec<TranscriptEntry>, String> {
+    load_entries(tool, session_id, false)
+}
+
+/// Like `load_transcript`, but keeps tool calls and results in conversation order
+pub fn load_transcript_with_tools(
+    tool: &str,
+    session_id: &str,
+) -> Result<Vec<TranscriptEntry>, String> {
+    load_entries(tool, session_id, true)
+}
+
+fn load_entries(
+    tool: &str,
+    session_id: &str,
+    include_tools: bool,
+) -> Result<Vec<TranscriptEntry>, String> {
+    match tool {
+        "claude" => load_claude_transcript(session_id, include_tools),
+        "codex" => load_codex_transcript(session_id, include_tools),
+        "gemini" => load_gemini_transcript(session_id, include_tools),
+        other => Err(format!("Unsupported tool: {}", other)),
+    }
+}
+
+/// Renders transcript entries as "markdown" (default) or "text"
+pub fn render_transcript(entries: &[TranscriptEntry], format: &str) -> String {
+    let mut out = String::new();
+
+    for entry in entries {
+        let speaker = if entry.role == "user" { "User" } else { "Assistant" };
+        match format {
+            "text" | "plain" => {
+                out.push_str(&format!("{}:\n{}\n\n", speaker, entry.text.trim()));
+            }
+            _ => {
+                match &entry.timestamp {
+                    Some(ts) => out.push_str(&format!("### {} ({})\n\n", speaker, ts)),
+                    None => out.push_str(&format!("### {}\n\n", speaker)),
+                }
+                out.push_str(entry.text.trim());
+                out.push_str("\n\n");
+            }
+        }
+    }
+
+    out.trim_end().to_string()
+}
+
+/// Finds a Claude session file by scanning all project directories
+pub fn find_claude_session_file(session_id: &str) -> Result<PathBuf, String> {
+    let projects_dir = get_claude_dir().map_err(|e| e.to_string())?.join("projects");
+    let entries = fs::read_dir(&projects_dir)
+        .map_err(|e| format!("Failed to read projects directory: {}", e))?;
+
+    for entry in entries.flatten() {
+        let candidate = entry.path().join(format!("{}.jsonl", session_id));
+        if candidate.is_file() {
+            return Ok(candidate);
+        }
+    }
+
+    Err(format!("Session file not found for ID: {}", session_id))
+}
+
+/// Finds a Gemini session file by scanning all project hash directories
+pub fn find_gemini_session_file(session_id: &str) -> Result<PathBuf, String> {
+    let tmp_dir = get_gemini_dir()?.join("tmp");
+    let entries = fs::read_dir(&tmp_dir)
+        .map_err(|e| format!("Failed to read Gemini tmp directory: {}", e))?;
+
+    for entry in entries.flatten() {
+        let chats_dir = entry.path().join("chats");
+        let chat_files = match fs::read_dir(&chats_dir) {
+            Ok(files) => files,
+            Err(_) => continue,
+        };
+        for chat in chat_files.flatten() {
+            let path = chat.path();
+            if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                continue;
+            }
+            if let Ok(content) = fs::read_to_string(&path) {
+                if let Ok(data) = serde_json::from_str::<serde_json::Value>(&content) {
+                    if data.get("sessionId").and_then(|v| v.as_str()) == Some(session_id) {
+                        return Ok(path);
+                    }
+                }
+            }
+        }
+    }
+
+    Err(format!("Session file not found for ID: {}", session_id))
+}
+
+fn load_claude_transcript(
+    session_id: &str,
+    include_tools: bool,
+) -> Result<Vec<TranscriptEntry>, String> {
+    let path = find_claude_session_file(session_id)?;
+    parse_claude_entries(&path, include_tools)
+}
+
+/// Parses a Claude session JSONL file into transcript entries
+pub fn parse_claude_transcript(path: &Path) -> Result<Vec<TranscriptEntry>, String> {
+    parse_claude_entries(path, false)
+}
+
+fn parse_claude_entries(path: &Path, include_tools: bool) -> Result<Vec<TranscriptEntry>, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read session file: {}", e))?;
+
+    let mut entries = Vec::new();
+    // tool_use id -> tool name, so results can be labelled
+    let mut tool_names: HashMap<String, String> = HashMap::new();
+    for line in content.lines() {
+        let msg: serde_json::Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        let role = match msg["type"].as_str() {
+            Some("user") => "user",
+            Some("assistant") => "assistant",
+            _ => continue,
+        };
+        if msg["isSidechain"].as_bool() == Some(true) || msg["isMeta"].as_bool() == Some(true) {
+            continue;
+        }
+
+        // Content is either a plain string or an array of blocks; only text blocks are kept
+        let timestamp = msg["timestamp"].as_str().map(String::from);
+        let (text, blocks) = match &msg["message"]["content"] {
+            serde_json::Value::String(s) => (s.clone(), None),
+            serde_json::Value::Array(blocks) => (
+                blocks
+                    .iter()
+                    .filter(|b| b["type"].as_str() == Some("text"))
+                    .filter_map(|b| b["text"].as_str())
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+                Some(blocks),
+            ),
+            _ => continue,
+        };
+        if !is_injected_text(&text) {
+            entries.push(TranscriptEntry {
+                role: role.to_string(),
+                text,
+                timestamp: timestamp.clone(),
+                tool_name: None,
+            });
+        }
+
+        if !include_tools {
+            continue;
+        }
+        for block in blocks.into_iter().flatten() {
+            match block["type"].as_str() {
+                Some("tool_use") => {
+                    let name = block["name"].as_str().unwrap_or("tool").to_string();
+                    if let Some(id) = block["id"].as_str() {
+                        tool_names.insert(id.to_string(), name.clone());
+                    }
+                    let input = serde_json::to_string_pretty(&block["input"]).unwrap_or_default();
+                    entries.push(TranscriptEntry::tool("tool_call", Some(name), input, timestamp.clone()));
+                }
+                Some("tool_result") => {
+                    let name = block["tool_use_id"]
+                        .as_str()
+                        .and_then(|id| tool_names.get(id))
+                        .cloned();
+                    let output = tool_result_text(&block["content"]);
+                    entries.push(TranscriptEntry::tool("tool_result", name, output, timestamp.clone()));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Flattens tool_result content (a string or an array of text blocks)
+fn tool_result_text(content: &serde_json::Value) -> String {
+    match content {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Array(blocks) => blocks
+            .iter()
+            .filter_map(|b| b["text"].as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+fn load_codex_transcript(
+    session_id: &str,
+    include_tools: bool,
+) -> Result<Vec<TranscriptEntry>, String> {
+    let sessions_dir = get_codex_sessions_dir()?;
+    let path = find_session_file(&sessions_dir, session_id)
+        .ok_or_else(|| format!("Session file not found for ID: {}", session_id))?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read session file: {}", e))?;
+
+    let mut entries = Vec::new();
+    // call_id -> tool name, so outputs can be labelled
+    let mut tool_names: HashMap<String, String> = HashMap::new();
+    for line in content.lines() {
+        let event: serde_json::Value = match serde_json::from_str(line) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if event["type"].as_str() != Some("response_item") {
+            continue;
+        }
+
+        let payload = &event["payload"];
+        let timestamp = event["timestamp"].as_str().map(String::from);
+        match payload["type"].as_str() {
+            Some("function_call") | Some("custom_tool_call") => {
+                if include_tools {
+                    let name = payload["name"].as_str().unwrap_or("tool").to_string();
+                    if let Some(id) = payload["call_id"].as_str() {
+                        tool_names.insert(id.to_string(), name.clone());
+                    }
+                    // function_call carries JSON arguments, custom_tool_call raw input (e.g. a patch)
+                    let input = match payload["arguments"].as_str() {
+                        Some(args) => serde_json::from_str::<serde_json::Value>(args)
+                            .and_then(|v| serde_json::to_string_pretty(&v))
+                            .unwrap_or_else(|_| args.to_string()),
+                        None => payload["input"].as_str().unwrap_or("").to_string(),
+                    };
+                    entries.push(TranscriptEntry::tool("tool_call", Some(name), input, timestamp));
+                }
+                continue;
+            }
+            Some("function_call_output") | Some("custom_tool_call_output") => {
+                if include_tools {
+                    let name = payload["call_id"]
+                        .as_str()
+                        .and_then(|id| tool_names.get(id))
+                        .cloned();
+                    let output = codex_tool_output(&payload["output"]);
+                    entries.push(TranscriptEntry::tool("tool_result", name, output, timestamp));
+                }
+                continue;
+            }
+            _ => {}
+        }
+
+        let role = match payload["role"].as_str() {
+            Some("user") => "user",
+            Some("assistant") => "assistant",
+            _ => continue,
+        };
+
+        let text = payload["content"]
+            .as_array()
+            .map(|items| {
+                items
+                    .iter()
+                    .filter(|i| matches!(i["type"].as_str(), Some("input_text") | Some("output_text")))
+                    .filter_map(|i| i["text"].as_str())
+                    .filter(|t| !is_injected_text(t))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .unwrap_or_default();
+        if text.trim().is_empty() {
+            continue;
+        }
+
+        entries.push(TranscriptEntry {
+            role: role.to_string(),
+            text,
+            timestamp,
+            tool_name: None,
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Codex tool output is either plain text or a JSON string of `{ "output": ... }`
+fn codex_tool_output(output: &serde_json::Value) -> String {
+    let raw = match output.as_str() {
+        Some(raw) => raw,
+        None => return tool_result_text(output),
+    };
+    serde_json::from_str::<serde_json::Value>(raw)
+        .ok()
+        .and_then(|v| v["output"].as_str().map(String::from))
+        .unwrap_or_else(|| raw.to_string())
+}
+
+fn load_gemini_transcript(
+    session_id: &str,
+    include_tools: bool,
+) -> Result<Vec<TranscriptEntry>, String> {
+    let path = find_gemini_session_file(session_id)?;
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read session file: {}", e))?;
+    let data: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse session JSON: {}", e))?;
+
+    let mut entries = Vec::new();
+    for message in data["messages"].as_array().into_iter().flatten() {
+        let role = match message["type"].as_str() {
+            Some("user") => "user",
+            Some("gemini") | Some("model") | Some("assistant") => "assistant",
+            _ => continue,
+        };
+        let timestamp = message["timestamp"].as_str().map(String::from);
+        let text = message["content"].as_str().unwrap_or("").to_string();
+        if !is_injected_text(&text) {
+            entries.push(TranscriptEntry {
+                role: role.to_string(),
+                text,
+                timestamp: timestamp.clone(),
+                tool_name: None,
+            });
+        }
+
+        if !include_tools {
+            continue;
+        }
+        for call in message["toolCalls"].as_array().into_iter().flatten() {
+            let name = call["name"].as_str().map(String::from);
+            let args = serde_json::to_string_pretty(&call["args"]).unwrap_or_default();
+            entries.push(TranscriptEntry::tool("tool_call", name.clone(), args, timestamp.clone()));
+            if let Some(result) = call["resultDisplay"].as_str() {
+                entries.push(TranscriptEntry::tool(
+                    "tool_result",
+                    name,
+                    result.to_string(),
+                    timestamp.clone(),
+                ));
+            }
+        }
+    }
+
+    Ok(entries)
+}
@@ -376,11 +376,16 @@ impl TranslationService {
         self.config = new_config;
     }
 
-    /// 清空缓存
-    pub async fn clear_cache(&self) {
+    /// 清空翻译缓存，返回释放的文本字节数（键与译文）
+    pub async fn clear_cache(&self) -> u64 {
         let mut cache = self.cache.lock().await;
+        let bytes = cache
+            .iter()
+            .map(|(key, entry)| (key.len() + entry.translated_text.len()) as u64)
+            .sum();
         cache.clear();
         info!("Translation cache cleared");
+        bytes
     }
 
     /// 获取缓存统计信息
@@ -438,7 +443,7 @@ pub async fn init_translation_service_with_saved_config() {
 }
 
 /// 获取全局翻译服务
-fn get_translation_service() -> Arc<Mutex<TranslationService>> {
+pub(crate) fn get_translation_service() -> Arc<Mutex<TranslationService>> {
     TRANSLATION_SERVICE.clone()
 }
 
@@ -452,6 +457,7 @@ pub async fn translate_text(text: &str, target_lang: Option<&str>) -> Result<Str
 /// Tauri命令：翻译文本
 #[tauri::command]
 pub async fn translate(text: String, target_lang: Option<String>) -> Result<String, String> {
+    crate::commands::network_mode::ensure_online("Translation")?;
     let target = target_lang.as_deref();
 
     translate_text(&text, target)
@@ -465,6 +471,7 @@ pub async fn translate_batch(
     texts: Vec<String>,
     target_lang: Option<String>,
 ) -> Result<Vec<String>, String> {
+    crate::commands::network_mode::ensure_online("Translation")?;
     let service_arc = get_translation_service();
     let service = service_arc.lock().await;
     let target = target_lang.as_deref();
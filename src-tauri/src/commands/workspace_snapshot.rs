@@ -0,0 +1,135 @@
+//! Workspace snapshots
+//!
+//! Labelled snapshots of a project's working tree for users who want rewind without
+//! auto-commits. Snapshots are created with `git stash create`, so neither the
+//! branch history nor the stash list changes. The label index lives in
+//! `.git/workbench-snapshots.json`, and each snapshot commit is pinned by a hidden
+//! ref under `refs/claude-workbench/workspace/` so it survives garbage collection.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use super::simple_git;
+
+const SNAPSHOT_INDEX_FILE: &str = "workbench-snapshots.json";
+
+/// A labelled working tree snapshot
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkspaceSnapshot {
+    pub label: String,
+    /// Stash commit, or HEAD when the tree was clean at snapshot time
+    pub commit: String,
+    pub created_at: String,
+}
+
+/// Snapshots the working tree (tracked files only) under `label`
+/// An existing snapshot with the same label is replaced.
+#[tauri::command]
+pub async fn create_workspace_snapshot(
+    project_path: String,
+    label: String,
+) -> Result<WorkspaceSnapshot, String> {
+    let label = label.trim().to_string();
+    if label.is_empty() {
+        return Err("Snapshot label cannot be empty".to_string());
+    }
+    if !simple_git::is_git_repo(&project_path) {
+        return Err(format!("Not a git repository: {}", project_path));
+    }
+
+    let message = format!("[Workbench] Snapshot: {}", label);
+    let commit = match simple_git::git_stash_create(&project_path, &message)? {
+        Some(commit) => commit,
+        None => simple_git::git_current_commit(&project_path)?,
+    };
+    simple_git::git_update_ref(&project_path, &snapshot_ref(&commit), &commit)?;
+
+    let snapshot = WorkspaceSnapshot {
+        label: label.clone(),
+        commit,
+        created_at: chrono::Utc::now().to_rfc3339(),
+    };
+
+    let mut snapshots = load_snapshots(&project_path)?;
+    let replaced: Vec<WorkspaceSnapshot> = snapshots
+        .iter()
+        .filter(|s| s.label == label)
+        .cloned()
+        .collect();
+    snapshots.retain(|s| s.label != label);
+    snapshots.push(snapshot.clone());
+    save_snapshots(&project_path, &snapshots)?;
+
+    // Unpin replaced commits that no other label refers to
+    for old in replaced {
+        if !snapshots.iter().any(|s| s.commit == old.commit) {
+            let _ = simple_git::git_delete_ref(&project_path, &snapshot_ref(&old.commit));
+        }
+    }
+
+    log::info!(
+        "Created workspace snapshot '{}' at {} in {}",
+        label,
+        &snapshot.commit[..8.min(snapshot.commit.len())],
+        project_path
+    );
+    Ok(snapshot)
+}
+
+/// Restores the working tree to the snapshot named `label`
+/// Current uncommitted changes are stashed first; HEAD and the branch are not moved.
+#[tauri::command]
+pub async fn restore_workspace_snapshot(project_path: String, label: String) -> Result<(), String> {
+    let snapshot = load_snapshots(&project_path)?
+        .into_iter()
+        .find(|s| s.label == label)
+        .ok_or_else(|| format!("Workspace snapshot not found: {}", label))?;
+
+    simple_git::git_stash_save(
+        &project_path,
+        &format!("[Workbench] Auto-stash before restoring snapshot: {}", label),
+    )?;
+    simple_git::git_restore_snapshot(&project_path, &snapshot.commit)?;
+
+    log::info!("Restored workspace snapshot '{}' in {}", label, project_path);
+    Ok(())
+}
+
+/// Lists the project's workspace snapshots, newest first
+#[tauri::command]
+pub async fn list_workspace_snapshots(project_path: String) -> Result<Vec<WorkspaceSnapshot>, String> {
+    if !simple_git::is_git_repo(&project_path) {
+        return Ok(Vec::new());
+    }
+    let mut snapshots = load_snapshots(&project_path)?;
+    snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+    Ok(snapshots)
+}
+
+fn snapshot_ref(commit: &str) -> String {
+    format!("refs/claude-workbench/workspace/{}", commit)
+}
+
+fn index_path(project_path: &str) -> Result<PathBuf, String> {
+    Ok(PathBuf::from(simple_git::git_absolute_dir(project_path)?).join(SNAPSHOT_INDEX_FILE))
+}
+
+fn load_snapshots(project_path: &str) -> Result<Vec<WorkspaceSnapshot>, String> {
+    let path = index_path(project_path)?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)
+        .map_err(|e| format!("Failed to read workspace snapshots: {}", e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse workspace snapshots: {}", e))
+}
+
+fn save_snapshots(project_path: &str, snapshots: &[WorkspaceSnapshot]) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(snapshots)
+        .map_err(|e| format!("Failed to serialize workspace snapshots: {}", e))?;
+    fs::write(index_path(project_path)?, content)
+        .map_err(|e| format!("Failed to write workspace snapshots: {}", e))
+}
@@ -773,6 +773,18 @@ pub fn build_wsl_command_async(
         cmd.arg(arg);
     }
 
+    // 离线模式变量需通过 WSLENV 才能传入 WSL
+    let offline_vars = crate::commands::network_mode::offline_env_vars();
+    if !offline_vars.is_empty() {
+        let names: Vec<&str> = offline_vars.iter().map(|(key, _)| *key).collect();
+        let wslenv = match std::env::var("WSLENV") {
+            Ok(existing) if !existing.is_empty() => format!("{}:{}", existing, names.join(":")),
+            _ => names.join(":"),
+        };
+        cmd.envs(offline_vars.iter().copied());
+        cmd.env("WSLENV", wslenv);
+    }
+
     // 隐藏控制台窗口
     cmd.creation_flags(CREATE_NO_WINDOW);
 
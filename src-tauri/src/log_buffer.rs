@@ -0,0 +1,73 @@
+//! In-memory tail of the application log
+//!
+//! Wraps env_logger so records still go to stderr as filtered by `RUST_LOG`,
+//! and keeps the most recent info-and-above lines for bug reports regardless
+//! of that filter. The app keeps no log file, so this is the only log history.
+
+use std::collections::VecDeque;
+use std::sync::Mutex;
+
+/// Lines kept; older lines are dropped
+const LOG_BUFFER_LINES: usize = 2000;
+
+static LOG_BUFFER: Mutex<VecDeque<String>> = Mutex::new(VecDeque::new());
+
+struct BufferedLogger {
+    inner: env_logger::Logger,
+}
+
+impl log::Log for BufferedLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::Level::Info || self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &log::Record) {
+        if record.level() <= log::Level::Info {
+            push_line(format!(
+                "{} {:<5} {}: {}",
+                chrono::Local::now().format("%Y-%m-%d %H:%M:%S%.3f"),
+                record.level(),
+                record.target(),
+                record.args()
+            ));
+        }
+        // env_logger applies its own filter
+        self.inner.log(record);
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+fn push_line(line: String) {
+    if let Ok(mut buffer) = LOG_BUFFER.lock() {
+        if buffer.len() == LOG_BUFFER_LINES {
+            buffer.pop_front();
+        }
+        buffer.push_back(line);
+    }
+}
+
+/// Installs the logger; replaces `env_logger::init()`
+pub fn init() {
+    let inner = env_logger::Builder::from_default_env().build();
+    let max_level = inner.filter().max(log::LevelFilter::Info);
+    if log::set_boxed_logger(Box::new(BufferedLogger { inner })).is_ok() {
+        log::set_max_level(max_level);
+    }
+}
+
+/// The buffered log lines, oldest first, each terminated by a newline
+pub fn recent_log_text() -> String {
+    let buffer = match LOG_BUFFER.lock() {
+        Ok(buffer) => buffer,
+        Err(_) => return String::new(),
+    };
+    let mut text = String::new();
+    for line in buffer.iter() {
+        text.push_str(line);
+        text.push('\n');
+    }
+    text
+}
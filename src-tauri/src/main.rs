@@ -3,6 +3,7 @@
 
 mod claude_binary;
 mod commands;
+mod log_buffer;
 mod process;
 
 use claude_binary::init_shell_environment;
@@ -11,31 +12,38 @@ use std::sync::{Arc, Mutex};
 
 use commands::acemcp::{
     enhance_prompt_with_context, test_acemcp_availability,
-    save_acemcp_config, load_acemcp_config, preindex_project,
-    export_acemcp_sidecar, get_extracted_sidecar_path
+    save_acemcp_config, load_acemcp_config, preindex_project, clear_acemcp_index,
+    export_acemcp_sidecar, get_extracted_sidecar_path, register_acemcp_with_claude,
+    get_acemcp_index_patterns, set_acemcp_index_patterns, acemcp_search,
+    rank_project_files_for_prompt,
 };
 use commands::claude::{
     cancel_claude_execution, check_claude_version, clear_custom_claude_path, continue_claude_code,
     delete_project, delete_project_permanently, delete_session, delete_sessions_batch,
     execute_claude_code, find_claude_md_files,
     get_available_tools, get_claude_execution_config, get_claude_path, get_claude_permission_config,
-    get_claude_session_output, get_claude_settings, get_codex_system_prompt, get_hooks_config, get_permission_presets,
-    get_project_sessions, get_system_prompt, list_directory_contents, list_hidden_projects,
+    get_claude_session_output, get_claude_settings, get_effective_claude_settings, get_codex_system_prompt, get_hooks_config, get_permission_presets,
+    get_project_sessions, get_system_prompt, list_claude_binary_candidates, list_directory_contents, list_hidden_projects,
     list_projects, list_running_claude_sessions, load_session_history, open_new_session,
-    read_claude_md_file, reset_claude_execution_config, restore_project, resume_claude_code,
+    read_claude_md_file, add_project, rename_project_directory, move_session, canonicalize_project_path, reset_claude_execution_config, restore_project,
+    resume_claude_code,
     save_claude_md_file, save_claude_settings, save_codex_system_prompt, save_system_prompt, search_files,
     set_custom_claude_path, update_claude_execution_config, update_claude_permission_config,
-    update_hooks_config, update_thinking_mode, validate_hook_command, validate_permission_config,
+    update_hooks_config, update_thinking_mode, validate_all_hooks, validate_hook_command, validate_permission_config,
+    validate_permission_config_for_version,
     ClaudeProcessState,
 };
 use commands::mcp::{
     mcp_add, mcp_add_from_claude_desktop, mcp_add_json, mcp_export_config, mcp_get,
     mcp_get_server_status, mcp_list, mcp_read_project_config, mcp_remove,
     mcp_reset_project_choices, mcp_save_project_config, mcp_serve, mcp_test_connection,
+    list_claude_mcp_servers, add_claude_mcp_server, remove_claude_mcp_server,
 };
 use commands::storage::{init_database, AgentDb};
 
-use commands::clipboard::{read_from_clipboard, save_clipboard_image, write_to_clipboard};
+use commands::clipboard::{
+    copy_session_to_clipboard, read_from_clipboard, save_clipboard_image, write_to_clipboard,
+};
 use commands::prompt_tracker::{
     check_rewind_capabilities, get_prompt_list, get_unified_prompt_list, mark_prompt_completed,
     record_prompt_sent, revert_to_prompt,
@@ -45,11 +53,12 @@ use commands::provider::{
     get_current_provider_config, get_provider_config, get_provider_presets, switch_provider_config,
     test_provider_connection, update_provider_config,
 };
-use commands::simple_git::check_and_init_git;
+use commands::simple_git::{check_and_init_git, get_git_info, init_project_git};
 use commands::storage::{
     storage_analyze_query, storage_delete_row, storage_execute_sql,
     storage_get_performance_stats, storage_insert_row, storage_list_tables,
-    storage_read_table, storage_reset_database, storage_update_row,
+    storage_read_table, storage_reset_database, storage_update_row, compact_database,
+    get_app_setting, set_app_setting, list_app_settings,
 };
 use commands::translator::{
     clear_translation_cache, detect_text_language, get_translation_cache_stats,
@@ -57,6 +66,7 @@ use commands::translator::{
     update_translation_config,
 };
 use commands::usage::{get_session_stats, get_usage_by_date_range, get_usage_stats};
+use commands::activity_heatmap::get_activity_heatmap;
 use commands::window::{
     create_session_window, close_session_window, list_session_windows,
     focus_session_window, emit_to_window, broadcast_to_session_windows,
@@ -70,23 +80,72 @@ use commands::extensions::{
     open_agents_directory, open_plugins_directory, open_skills_directory, read_skill, read_subagent,
 };
 use commands::file_operations::{open_directory_in_explorer, open_file_with_default_app};
+use commands::project_stack::detect_project_stack;
+use commands::project_fingerprint::get_project_fingerprint;
+use commands::file_tail::{stop_tail_file, tail_file, FileTailState};
+use commands::content_search::{cancel_content_search, start_content_search, ContentSearchState};
+use commands::settings_watcher::{
+    start_claude_settings_watcher, start_settings_watcher, stop_claude_settings_watcher,
+    SettingsWatcherState,
+};
+use commands::bookmarks::{add_bookmark, list_bookmarks, remove_bookmark};
+use commands::drafts::{get_draft, save_draft};
+use commands::caches::clear_all_caches;
+use commands::path_length::check_path_length;
+use commands::config_backup::{export_all_config, import_all_config};
+use commands::bug_report::generate_bug_report;
+use commands::secret_scan::scan_prompt_for_secrets;
+use commands::token_estimate::{check_prompt_fits, estimate_tokens};
+use commands::tool_status::{benchmark_tool_startup, get_all_tool_status};
+use commands::toolchain_selftest::run_toolchain_selftest;
+use commands::recent_dirs::get_recent_directories;
+use commands::rewind_stash::{apply_rewind_stash, drop_rewind_stash, list_rewind_stashes};
+use commands::execution_timeout::{get_execution_timeouts, set_execution_timeouts};
+use commands::network_mode::{get_network_mode, load_network_mode, set_network_mode};
+use commands::buffer_stats::{get_session_buffer_stats, load_output_buffer_cap, set_output_buffer_cap};
+use commands::run_error::get_last_run_error;
+use commands::error_sessions::list_error_sessions;
+use commands::workspace_snapshot::{
+    create_workspace_snapshot, list_workspace_snapshots, restore_workspace_snapshot,
+};
+use commands::session_diff::diff_sessions;
+use commands::duplicate_prompts::find_duplicate_prompts;
+use commands::prompt_library::extract_prompts_to_library;
+use commands::session_export::export_sessions_csv;
+use commands::session_html::export_session_html;
+use commands::session_meta::{check_session_write_conflict, get_session_meta};
+use commands::session_ops::{
+    clear_session_history, delete_sessions_by_filter, truncate_session_to_timestamp,
+};
+use commands::session_stream::stream_session_unified;
+use commands::session_replay::{
+    pause_session_replay, resume_session_replay, start_session_replay, stop_session_replay,
+    SessionReplayState,
+};
 use commands::git_stats::{get_git_diff_stats, get_session_code_changes};
 use commands::codex::{
-    execute_codex, resume_codex, resume_last_codex, cancel_codex,
-    list_codex_sessions, delete_codex_session,
-    load_codex_session_history, get_codex_prompt_list, check_codex_rewind_capabilities,
+    execute_codex, resume_codex, resume_last_codex, resume_last_codex_for_project, resume_codex_by_query, can_resume_codex, cancel_codex, force_kill_codex_session, approve_codex_plan,
+    list_codex_sessions, list_codex_sessions_grouped, delete_codex_session, migrate_session_storage,
+    load_codex_session_history, get_codex_session_model_timeline, read_codex_output_file,
+    get_codex_session_activity,
+    get_codex_run_command, get_codex_running_output, validate_output_against_schema,
+    get_codex_prompt_list, check_codex_rewind_capabilities,
     check_codex_availability,
     set_custom_codex_path, get_codex_path, clear_custom_codex_path,
+    validate_binaries_json, repair_binaries_json,
     // Codex mode configuration
     get_codex_mode_config, set_codex_mode_config,
     // Codex rewind commands
-    record_codex_prompt_sent, record_codex_prompt_completed, revert_codex_to_prompt,
+    record_codex_prompt_sent, record_codex_prompt_completed, revert_codex_to_prompt, compact_codex_git_records,
     // Codex provider management
-    get_codex_provider_presets, get_current_codex_config, switch_codex_provider,
-    add_codex_provider_config, update_codex_provider_config, delete_codex_provider_config,
-    clear_codex_provider_config, test_codex_provider_connection,
+    get_codex_provider_presets, get_current_codex_config, get_active_codex_provider, dedupe_codex_providers,
+    switch_codex_provider, preview_codex_provider_switch,
+    add_codex_provider_config, clone_codex_provider, update_codex_provider_config, delete_codex_provider_config,
+    clear_codex_provider_config, ensure_codex_config_exists, get_codex_config_health, test_codex_provider_connection,
     // Session conversion
-    convert_session, convert_claude_to_codex, convert_codex_to_claude,
+    convert_session, convert_claude_to_codex, convert_codex_to_claude, convert_session_between_tools,
+    // Terminal
+    open_codex_terminal,
     CodexProcessState,
 };
 use commands::gemini::{
@@ -109,8 +168,8 @@ use tauri::{Manager, WindowEvent};
 use tauri_plugin_window_state::Builder as WindowStatePlugin;
 
 fn main() {
-    // Initialize logger
-    env_logger::init();
+    // Initialize logger (also keeps a tail for bug reports)
+    log_buffer::init();
 
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
@@ -137,6 +196,10 @@ fn main() {
             let conn = init_database(&app.handle()).expect("Failed to initialize database");
             app.manage(AgentDb(Mutex::new(conn)));
 
+            // Restore offline mode before anything can reach the network
+            load_network_mode(&app.handle());
+            load_output_buffer_cap(&app.handle());
+
             // Initialize process registry
             app.manage(ProcessRegistryState::default());
 
@@ -149,6 +212,21 @@ fn main() {
             // Initialize Gemini process state
             app.manage(GeminiProcessState::default());
 
+            // Initialize file tail state
+            app.manage(FileTailState::default());
+
+            // Initialize content search state
+            app.manage(ContentSearchState::default());
+
+            // Initialize Claude settings watcher state
+            app.manage(SettingsWatcherState::default());
+            if let Err(e) = start_settings_watcher(&app.handle()) {
+                log::warn!("Failed to start Claude settings watcher: {}", e);
+            }
+
+            // Initialize session replay state
+            app.manage(SessionReplayState::default());
+
             // Initialize auto-compact manager for context management
             let auto_compact_manager =
                 Arc::new(commands::context_manager::AutoCompactManager::new());
@@ -232,7 +310,12 @@ fn main() {
             restore_project,
             list_hidden_projects,
             delete_project_permanently,
+            add_project,
+            rename_project_directory,
+            move_session,
+            canonicalize_project_path,
             get_claude_settings,
+            get_effective_claude_settings,
             open_new_session,
             get_system_prompt,
             get_codex_system_prompt,
@@ -253,9 +336,12 @@ fn main() {
             get_claude_session_output,
             list_directory_contents,
             search_files,
+            start_content_search,
+            cancel_content_search,
             get_hooks_config,
             update_hooks_config,
             validate_hook_command,
+            validate_all_hooks,
             // 权限管理命令
             get_claude_execution_config,
             update_claude_execution_config,
@@ -265,16 +351,24 @@ fn main() {
             get_permission_presets,
             get_available_tools,
             validate_permission_config,
+            validate_permission_config_for_version,
             set_custom_claude_path,
             get_claude_path,
             clear_custom_claude_path,
+            list_claude_binary_candidates,
             // Acemcp Integration
             enhance_prompt_with_context,
             test_acemcp_availability,
+            acemcp_search,
+            rank_project_files_for_prompt,
             save_acemcp_config,
             load_acemcp_config,
+            get_acemcp_index_patterns,
+            set_acemcp_index_patterns,
             preindex_project,
+            clear_acemcp_index,
             export_acemcp_sidecar,
+            register_acemcp_with_claude,
             get_extracted_sidecar_path,
             // Enhanced Hooks Automation
             trigger_hook_event,
@@ -283,6 +377,7 @@ fn main() {
             // Usage & Analytics (Simplified from opcode)
             get_usage_stats,
             get_usage_by_date_range,
+            get_activity_heatmap,
             get_session_stats,
             // MCP (Model Context Protocol)
             mcp_add,
@@ -298,6 +393,9 @@ fn main() {
             mcp_export_config,
             mcp_read_project_config,
             mcp_save_project_config,
+            list_claude_mcp_servers,
+            add_claude_mcp_server,
+            remove_claude_mcp_server,
             // Storage Management
             storage_list_tables,
             storage_read_table,
@@ -308,10 +406,46 @@ fn main() {
             storage_reset_database,
             storage_get_performance_stats,
             storage_analyze_query,
+            compact_database,
+            get_app_setting,
+            set_app_setting,
+            list_app_settings,
             // Clipboard
             save_clipboard_image,
             write_to_clipboard,
             read_from_clipboard,
+            copy_session_to_clipboard,
+            // Prompt Safety
+            scan_prompt_for_secrets,
+            // Token Estimation
+            estimate_tokens,
+            check_prompt_fits,
+            // Tool Status
+            get_all_tool_status,
+            benchmark_tool_startup,
+            run_toolchain_selftest,
+            // Recent Directories
+            get_recent_directories,
+            // Session Export
+            export_sessions_csv,
+            export_session_html,
+            // Session Comparison
+            diff_sessions,
+            find_duplicate_prompts,
+            extract_prompts_to_library,
+            // Session Maintenance
+            get_session_meta,
+            check_session_write_conflict,
+            clear_session_history,
+            truncate_session_to_timestamp,
+            delete_sessions_by_filter,
+            // Session Replay
+            start_session_replay,
+            pause_session_replay,
+            resume_session_replay,
+            stop_session_replay,
+            // Progressive Session Loading
+            stream_session_unified,
             // Provider Management
             get_provider_presets,
             get_current_provider_config,
@@ -346,12 +480,34 @@ fn main() {
             commands::context_commands::get_auto_compact_status,
             // Prompt Revert System
             check_and_init_git,
+            get_git_info,
+            init_project_git,
             record_prompt_sent,
             mark_prompt_completed,
             revert_to_prompt,
             get_prompt_list,
             get_unified_prompt_list,
             check_rewind_capabilities,
+            // Workspace Snapshots
+            create_workspace_snapshot,
+            restore_workspace_snapshot,
+            list_workspace_snapshots,
+            // Rewind Stashes
+            list_rewind_stashes,
+            apply_rewind_stash,
+            drop_rewind_stash,
+            // Execution Timeouts
+            get_execution_timeouts,
+            set_execution_timeouts,
+            // Network Mode
+            set_network_mode,
+            get_network_mode,
+            // Output Buffers
+            get_session_buffer_stats,
+            set_output_buffer_cap,
+            // Run Errors
+            get_last_run_error,
+            list_error_sessions,
             // Claude Extensions (Plugins, Subagents & Skills)
             list_plugins,
             list_subagents,
@@ -366,6 +522,31 @@ fn main() {
             // File Operations
             open_directory_in_explorer,
             open_file_with_default_app,
+            // Project Stack Detection
+            detect_project_stack,
+            get_project_fingerprint,
+            // File Tail
+            tail_file,
+            stop_tail_file,
+            // Claude Settings Watcher
+            start_claude_settings_watcher,
+            stop_claude_settings_watcher,
+            // Bookmarks
+            add_bookmark,
+            remove_bookmark,
+            list_bookmarks,
+            // Composer Drafts
+            save_draft,
+            get_draft,
+            // Caches
+            clear_all_caches,
+            // Path Length
+            check_path_length,
+            // Configuration Backup
+            export_all_config,
+            import_all_config,
+            // Bug Report
+            generate_bug_report,
             // Git Statistics
             get_git_diff_stats,
             get_session_code_changes,
@@ -373,10 +554,23 @@ fn main() {
             execute_codex,
             resume_codex,
             resume_last_codex,
+            resume_last_codex_for_project,
+            resume_codex_by_query,
+            can_resume_codex,
             cancel_codex,
+            force_kill_codex_session,
+            approve_codex_plan,
             list_codex_sessions,
+            list_codex_sessions_grouped,
             delete_codex_session,
+            migrate_session_storage,
             load_codex_session_history,
+            get_codex_session_model_timeline,
+            get_codex_session_activity,
+            read_codex_output_file,
+            get_codex_run_command,
+            get_codex_running_output,
+            validate_output_against_schema,
             get_codex_prompt_list,
             check_codex_rewind_capabilities,
             check_codex_availability,
@@ -387,23 +581,35 @@ fn main() {
             record_codex_prompt_sent,
             record_codex_prompt_completed,
             revert_codex_to_prompt,
+            compact_codex_git_records,
             // Codex custom path
             set_custom_codex_path,
             get_codex_path,
             clear_custom_codex_path,
+            validate_binaries_json,
+            repair_binaries_json,
             // Codex Provider Management
             get_codex_provider_presets,
             get_current_codex_config,
+            get_active_codex_provider,
+            dedupe_codex_providers,
             switch_codex_provider,
+            preview_codex_provider_switch,
             add_codex_provider_config,
+            clone_codex_provider,
             update_codex_provider_config,
             delete_codex_provider_config,
             clear_codex_provider_config,
+            ensure_codex_config_exists,
+            get_codex_config_health,
             test_codex_provider_connection,
-            // Session Conversion (Claude ↔ Codex)
+            // Session Conversion (Claude ↔ Codex, Gemini → Claude)
             convert_session,
             convert_claude_to_codex,
             convert_codex_to_claude,
+            convert_session_between_tools,
+            // Codex Terminal
+            open_codex_terminal,
             // Window Management (Multi-window support)
             create_session_window,
             close_session_window,
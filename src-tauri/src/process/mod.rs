@@ -1,5 +1,7 @@
 pub mod job_object;
+pub mod output_buffer;
 pub mod registry;
 
 pub use job_object::JobObject;
+pub use output_buffer::{OutputBuffer, OutputBufferStats};
 pub use registry::*;
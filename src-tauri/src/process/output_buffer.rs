@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Default per-session cap on buffered output
+pub const DEFAULT_OUTPUT_BUFFER_CAP: usize = 5 * 1024 * 1024;
+
+/// Current cap in bytes, shared by all buffers; 0 disables the cap
+static OUTPUT_BUFFER_CAP: AtomicUsize = AtomicUsize::new(DEFAULT_OUTPUT_BUFFER_CAP);
+
+pub fn output_buffer_cap() -> usize {
+    OUTPUT_BUFFER_CAP.load(Ordering::Relaxed)
+}
+
+pub fn set_output_buffer_cap(bytes: usize) {
+    OUTPUT_BUFFER_CAP.store(bytes, Ordering::Relaxed);
+}
+
+/// Size and truncation state of one buffer
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OutputBufferStats {
+    pub bytes: usize,
+    pub lines: usize,
+    pub cap_bytes: usize,
+    /// True once any line has been dropped
+    pub truncated: bool,
+    pub dropped_lines: usize,
+}
+
+/// Output lines of a running process, dropping the oldest lines past the cap
+#[derive(Debug, Default)]
+pub struct OutputBuffer {
+    lines: VecDeque<String>,
+    bytes: usize,
+    dropped_lines: usize,
+}
+
+impl OutputBuffer {
+    pub fn push(&mut self, line: String) {
+        self.push_capped(line, output_buffer_cap());
+    }
+
+    fn push_capped(&mut self, line: String, cap: usize) {
+        self.bytes += line.len();
+        self.lines.push_back(line);
+        // The newest line is always kept, even when it alone exceeds the cap
+        while cap > 0 && self.bytes > cap && self.lines.len() > 1 {
+            if let Some(dropped) = self.lines.pop_front() {
+                self.bytes -= dropped.len();
+                self.dropped_lines += 1;
+            }
+        }
+    }
+
+    pub fn lines(&self) -> Vec<String> {
+        self.lines.iter().cloned().collect()
+    }
+
+    /// All lines, each terminated by a newline
+    pub fn to_text(&self) -> String {
+        let mut text = String::with_capacity(self.bytes + self.lines.len());
+        for line in &self.lines {
+            text.push_str(line);
+            text.push('\n');
+        }
+        text
+    }
+
+    pub fn stats(&self) -> OutputBufferStats {
+        OutputBufferStats {
+            bytes: self.bytes,
+            lines: self.lines.len(),
+            cap_bytes: output_buffer_cap(),
+            truncated: self.dropped_lines > 0,
+            dropped_lines: self.dropped_lines,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drops_oldest_lines_past_cap() {
+        let mut buffer = OutputBuffer::default();
+        for line in ["aaaa", "bbbb", "cccc"] {
+            buffer.push_capped(line.to_string(), 10);
+        }
+        assert_eq!(buffer.lines(), vec!["bbbb", "cccc"]);
+        assert_eq!(buffer.bytes, 8);
+        assert_eq!(buffer.dropped_lines, 1);
+
+        buffer.push_capped("x".repeat(20), 10);
+        assert_eq!(buffer.lines().len(), 1);
+        assert_eq!(buffer.dropped_lines, 3);
+        assert_eq!(buffer.to_text().len(), 21);
+    }
+}
@@ -1,4 +1,4 @@
-use super::JobObject;
+use super::{JobObject, OutputBuffer, OutputBufferStats};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -29,7 +29,7 @@ pub struct ProcessInfo {
 pub struct ProcessHandle {
     pub info: ProcessInfo,
     pub child: Arc<Mutex<Option<Child>>>,
-    pub live_output: Arc<Mutex<String>>,
+    pub live_output: Arc<Mutex<OutputBuffer>>,
     #[cfg(windows)]
     pub job_object: Option<Arc<JobObject>>, // Job object for automatic cleanup on Windows
 }
@@ -139,7 +139,7 @@ impl ProcessRegistry {
         let process_handle = ProcessHandle {
             info: process_info,
             child: Arc::new(Mutex::new(None)), // No child handle for Claude sessions
-            live_output: Arc::new(Mutex::new(String::new())),
+            live_output: Arc::new(Mutex::new(OutputBuffer::default())),
             #[cfg(windows)]
             job_object,
         };
@@ -189,7 +189,7 @@ impl ProcessRegistry {
         let process_handle = ProcessHandle {
             info: process_info,
             child: Arc::new(Mutex::new(Some(child))),
-            live_output: Arc::new(Mutex::new(String::new())),
+            live_output: Arc::new(Mutex::new(OutputBuffer::default())),
             #[cfg(windows)]
             job_object,
         };
@@ -603,8 +603,7 @@ impl ProcessRegistry {
         let processes = self.processes.lock().map_err(|e| e.to_string())?;
         if let Some(handle) = processes.get(&run_id) {
             let mut live_output = handle.live_output.lock().map_err(|e| e.to_string())?;
-            live_output.push_str(output);
-            live_output.push('\n');
+            live_output.push(output.to_string());
         }
         Ok(())
     }
@@ -614,12 +613,24 @@ impl ProcessRegistry {
         let processes = self.processes.lock().map_err(|e| e.to_string())?;
         if let Some(handle) = processes.get(&run_id) {
             let live_output = handle.live_output.lock().map_err(|e| e.to_string())?;
-            Ok(live_output.clone())
+            Ok(live_output.to_text())
         } else {
             Ok(String::new())
         }
     }
 
+    /// Size and truncation state of a process's live output buffer
+    pub fn get_live_output_stats(&self, run_id: i64) -> Result<Option<OutputBufferStats>, String> {
+        let processes = self.processes.lock().map_err(|e| e.to_string())?;
+        match processes.get(&run_id) {
+            Some(handle) => {
+                let live_output = handle.live_output.lock().map_err(|e| e.to_string())?;
+                Ok(Some(live_output.stats()))
+            }
+            None => Ok(None),
+        }
+    }
+
     /// Cleanup finished processes
     #[allow(dead_code)]
     pub async fn cleanup_finished_processes(&self) -> Result<Vec<i64>, String> {